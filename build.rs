@@ -24,17 +24,402 @@ fn env_var(name: &str) -> Option<OsString> {
     std::env::var_os(name)
 }
 
-#[cfg(target_os = "linux")]
 fn main() {
-    if env_var("DOCS_RS").is_some() {
-        // Skip linking and bindgen when building docs as docs.rs won't have the
-        // dependency present and can't download it.
+    println!("cargo::rustc-env=MS_COG_SVC_SPEECH_SDK_VERSION={SPEECH_SDK_VERSION}");
+
+    let runtime_loading = env::var_os("CARGO_FEATURE_RUNTIME_LOADING").is_some();
+    let out_dir_bindings = build_native(runtime_loading);
+
+    if runtime_loading {
+        let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+        let bindings = if out_dir_bindings {
+            out_path.join("bindings.rs")
+        } else {
+            println!("cargo::rerun-if-changed=src/ffi/bindings.rs");
+            PathBuf::from("src/ffi/bindings.rs")
+        };
+        generate_runtime_bindings(&bindings, &out_path);
+    }
+}
+
+/// Splits *text* at commas which are not nested in parentheses or angle brackets.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' => depth -= 1,
+            // '>' of '->' in function pointer types does not close anything
+            '>' if prev != '-' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(&text[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Turns single *pub fn* declaration of extern block into function
+/// resolving the native symbol on first call.
+fn runtime_function(attributes: &str, declaration: &str) -> String {
+    let declaration = declaration
+        .trim()
+        .strip_prefix("pub fn ")
+        .unwrap_or_else(|| panic!("unexpected extern declaration: {}", declaration));
+    let open = declaration.find('(').unwrap();
+    let name = declaration[..open].trim();
+
+    let mut depth = 0;
+    let mut close = open;
+    for (index, c) in declaration[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = open + index;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let args = split_top_level(&declaration[open + 1..close]);
+    if args.contains(&"...") {
+        // variadic functions cannot be defined in stable Rust, they are not used by this crate
+        return format!("// {name} (variadic) is not available with runtime-loading\n");
+    }
+    let args: Vec<(&str, &str)> = args
+        .into_iter()
+        .map(|arg| {
+            let (arg_name, arg_type) = arg
+                .split_once(':')
+                .unwrap_or_else(|| panic!("unexpected argument {:?} of {}", arg, name));
+            (arg_name.trim(), arg_type.trim())
+        })
+        .collect();
+    let ret = declaration[close + 1..]
+        .trim()
+        .trim_end_matches(';')
+        .trim()
+        .strip_prefix("->")
+        .map(str::trim);
+
+    let params = args
+        .iter()
+        .map(|(n, t)| format!("{n}: {t}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let types = args.iter().map(|(_, t)| *t).collect::<Vec<_>>().join(", ");
+    let names = args.iter().map(|(n, _)| *n).collect::<Vec<_>>().join(", ");
+    let ret_sig = ret.map(|r| format!(" -> {r}")).unwrap_or_default();
+    let missing_value = match ret {
+        // error code recognized by convert_err
+        Some("AZACHR") | Some("SPXHR") => "crate::runtime::MISSING_SYMBOL_HR",
+        None => "",
+        Some(_) => "::std::mem::zeroed()",
+    };
+
+    format!(
+        r#"{attributes}pub unsafe extern "C" fn {name}({params}){ret_sig} {{
+    type Symbol = unsafe extern "C" fn({types}){ret_sig};
+    static SYMBOL: ::std::sync::OnceLock<::std::option::Option<Symbol>> =
+        ::std::sync::OnceLock::new();
+    match *SYMBOL.get_or_init(|| crate::runtime::native_symbol(b"{name}\0")) {{
+        ::std::option::Option::Some(symbol) => symbol({names}),
+        ::std::option::Option::None => {{
+            crate::runtime::missing_symbol(b"{name}\0");
+            {missing_value}
+        }}
+    }}
+}}
+"#
+    )
+}
+
+/// Rewrites *extern "C"* blocks of bindgen generated *bindings* into functions
+/// resolving native symbols at runtime (feature *runtime-loading*).
+/// Result is written into OUT_DIR/bindings_runtime.rs.
+fn generate_runtime_bindings(bindings: &Path, out_path: &Path) {
+    const BLOCK_START: &str = "extern \"C\" {\n";
+    const BLOCK_END: &str = "\n}\n";
+
+    let source = fs::read_to_string(bindings)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", bindings.display()));
+    let mut output = String::with_capacity(source.len() * 3);
+    let mut rest = source.as_str();
+
+    while let Some(start) = rest.find(BLOCK_START) {
+        let block_start = start + BLOCK_START.len();
+        // nested occurrences (function pointer types) never start at the beginning of line
+        if start > 0 && !rest[..start].ends_with('\n') {
+            output.push_str(&rest[..block_start]);
+            rest = &rest[block_start..];
+            continue;
+        }
+        output.push_str(&rest[..start]);
+        let block_len = rest[block_start..]
+            .find(BLOCK_END)
+            .unwrap_or_else(|| panic!("unterminated extern block in {}", bindings.display()));
+        let block = &rest[block_start..block_start + block_len];
+
+        let mut attributes = String::new();
+        let mut declaration = String::new();
+        for line in block.lines() {
+            let line = line.trim();
+            if declaration.is_empty() && line.starts_with("#[") {
+                // symbol is always looked up by the function name
+                if !line.starts_with("#[link_name") {
+                    attributes.push_str(line);
+                    attributes.push('\n');
+                }
+                continue;
+            }
+            declaration.push_str(line);
+            declaration.push(' ');
+            if line.ends_with(';') {
+                output.push_str(&runtime_function(&attributes, &declaration));
+                attributes.clear();
+                declaration.clear();
+            }
+        }
+        rest = &rest[block_start + block_len + BLOCK_END.len()..];
+    }
+    output.push_str(rest);
+
+    fs::write(out_path.join("bindings_runtime.rs"), output)
+        .expect("Couldn't write runtime bindings!");
+}
+
+#[cfg(target_os = "linux")]
+const SDK_CORE_LIBRARY: &str = "libMicrosoft.CognitiveServices.Speech.core.so";
+
+#[cfg(all(target_os = "linux", target_arch = "x86"))]
+const SDK_LIB_ARCH: &str = "x86";
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+const SDK_LIB_ARCH: &str = "x64";
+#[cfg(all(target_os = "linux", target_arch = "arm"))]
+const SDK_LIB_ARCH: &str = "arm32";
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const SDK_LIB_ARCH: &str = "arm64";
+
+/// Panics with clear message if file required from pre-provisioned SDK is missing.
+#[cfg(target_os = "linux")]
+fn require_file(path: &Path, source: &str) {
+    if !path.is_file() {
+        panic!(
+            "Speech SDK file {} not found (Speech SDK taken from {source})",
+            path.display()
+        );
+    }
+    println!("cargo::rerun-if-changed={}", path.display());
+}
+
+#[cfg(target_os = "linux")]
+fn sha256_file(path: &Path) -> String {
+    use sha2::{Digest, Sha256};
+    let mut file = fs::File::open(path)
+        .unwrap_or_else(|e| panic!("failed to open {} for checksum: {e}", path.display()));
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .unwrap_or_else(|e| panic!("failed to read {} for checksum: {e}", path.display()));
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Verifies checksum of *path* against MS_COG_SVC_SPEECH_SDK_SHA256 (if set).
+#[cfg(target_os = "linux")]
+fn verify_checksum(path: &Path) {
+    if let Some(expected) = env_var("MS_COG_SVC_SPEECH_SDK_SHA256") {
+        let expected = expected.to_string_lossy().trim().to_ascii_lowercase();
+        let actual = sha256_file(path);
+        if actual != expected {
+            panic!(
+                "Speech SDK checksum mismatch for {}: expected sha256 {expected}, got {actual}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Native library embeds its version string, version of SDK directory of unknown
+/// origin is verified by looking it up. Can be disabled by MS_COG_SVC_SPEECH_SKIP_VERSION_CHECK=1.
+#[cfg(target_os = "linux")]
+fn verify_library_version(library: &Path) {
+    if env_var("MS_COG_SVC_SPEECH_SKIP_VERSION_CHECK").is_some_and(|v| v == "1") {
         return;
     }
+    let content =
+        fs::read(library).unwrap_or_else(|e| panic!("failed to read {}: {e}", library.display()));
+    let version = SPEECH_SDK_VERSION.as_bytes();
+    if !content.windows(version.len()).any(|w| w == version) {
+        panic!(
+            "{} does not look like Speech SDK version {SPEECH_SDK_VERSION} \
+             (set MS_COG_SVC_SPEECH_SKIP_VERSION_CHECK=1 to skip this check)",
+            library.display()
+        );
+    }
+}
+
+/// Verifies that the archive was produced for expected SDK version,
+/// i.e. its top level folder is *SpeechSDK-Linux-{SPEECH_SDK_VERSION}*.
+#[cfg(target_os = "linux")]
+fn verify_tarball_version(tarball: &Path) {
+    let output = Command::new("tar")
+        .arg("-tzf")
+        .arg(tarball)
+        .output()
+        .unwrap_or_else(|e| panic!("failed to run tar for {}: {e}", tarball.display()));
+    if !output.status.success() {
+        panic!(
+            "failed to list Speech SDK archive {}: {}",
+            tarball.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let expected = format!("SpeechSDK-Linux-{SPEECH_SDK_VERSION}");
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let top_level = listing
+        .lines()
+        .next()
+        .and_then(|entry| entry.trim_start_matches("./").split('/').next())
+        .unwrap_or_default();
+    if top_level != expected {
+        panic!(
+            "Speech SDK archive {} contains {top_level:?}, expected {expected:?}",
+            tarball.display()
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn extract_tarball(tarball: &Path, sdk_output_dir: &Path) {
+    let status = Command::new("tar")
+        .args(["--strip", "1", "-xzf"])
+        .arg(tarball)
+        .arg("-C")
+        .arg(sdk_output_dir)
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run tar for {}: {e}", tarball.display()));
+    if !status.success() {
+        panic!("failed to extract Speech SDK archive {}", tarball.display());
+    }
+}
+
+/// Returns SDK root folder (containing include/c_api and lib/<arch>) and flag
+/// whether the SDK was provisioned locally. Locally provisioned SDK is either
+/// installed folder (MS_COG_SVC_SPEECH_SDK_DIR) or archive (MS_COG_SVC_SPEECH_SDK_TARBALL),
+/// in both cases network is never used.
+#[cfg(target_os = "linux")]
+fn provision_sdk(out_path: &Path) -> (PathBuf, bool) {
+    if let Some(sdk_dir) = env_var("MS_COG_SVC_SPEECH_SDK_DIR") {
+        let sdk_dir = PathBuf::from(sdk_dir);
+        let source = format!("MS_COG_SVC_SPEECH_SDK_DIR={}", sdk_dir.display());
+        let library = sdk_dir
+            .join("lib")
+            .join(SDK_LIB_ARCH)
+            .join(SDK_CORE_LIBRARY);
+        require_file(
+            &sdk_dir.join("include").join("c_api").join("speechapi_c.h"),
+            &source,
+        );
+        require_file(&library, &source);
+        verify_checksum(&library);
+        verify_library_version(&library);
+        return (sdk_dir, true);
+    }
+
+    let sdk_output_dir = out_path.join("sdk_output");
+
+    if let Some(tarball) = env_var("MS_COG_SVC_SPEECH_SDK_TARBALL") {
+        let tarball = PathBuf::from(tarball);
+        let source = format!("MS_COG_SVC_SPEECH_SDK_TARBALL={}", tarball.display());
+        require_file(&tarball, &source);
+        verify_checksum(&tarball);
+        verify_tarball_version(&tarball);
+        // always start from scratch so that files of previously used SDK do not linger
+        if sdk_output_dir.exists() {
+            fs::remove_dir_all(&sdk_output_dir).unwrap();
+        }
+        fs::create_dir_all(&sdk_output_dir).unwrap();
+        extract_tarball(&tarball, &sdk_output_dir);
+        require_file(
+            &sdk_output_dir
+                .join("include")
+                .join("c_api")
+                .join("speechapi_c.h"),
+            &source,
+        );
+        require_file(
+            &sdk_output_dir
+                .join("lib")
+                .join(SDK_LIB_ARCH)
+                .join(SDK_CORE_LIBRARY),
+            &source,
+        );
+        return (sdk_output_dir, true);
+    }
 
     let linux_sdk_url = format!(
         "https://csspeechstorage.blob.core.windows.net/drop/{SPEECH_SDK_VERSION}/SpeechSDK-Linux-{SPEECH_SDK_VERSION}.tar.gz");
 
+    let mut renew = env::var("RENEW_SDK").map(|v| v == "1").unwrap_or(false);
+    if !sdk_output_dir.exists() || fs::read_dir(&sdk_output_dir).unwrap().next().is_none() {
+        renew = true;
+        fs::create_dir_all(&sdk_output_dir).unwrap();
+    }
+
+    let sdk_tar_file = out_path.join(format!("SpeechSDK-Linux-{SPEECH_SDK_VERSION}.tar.gz"));
+    if !sdk_tar_file.exists() {
+        download_file(linux_sdk_url.as_str(), sdk_tar_file.to_str().unwrap());
+    }
+
+    if renew {
+        let args = [
+            "--strip",
+            "1",
+            "-xzf",
+            sdk_tar_file.to_str().unwrap(),
+            "-C",
+            sdk_output_dir.to_str().unwrap(),
+        ];
+        Command::new("tar").args(args).status().unwrap();
+    }
+
+    (sdk_output_dir, false)
+}
+
+#[cfg(target_os = "linux")]
+fn build_native(runtime_loading: bool) -> bool {
+    if env_var("DOCS_RS").is_some() {
+        // Skip linking and bindgen when building docs as docs.rs won't have the
+        // dependency present and can't download it.
+        return false;
+    }
+
+    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
+        .map(|v| v == "1")
+        .unwrap_or(false);
+
+    // runtime loaded library needs neither SDK nor linking unless bindings are regenerated
+    if runtime_loading && skip_bindgen {
+        return false;
+    }
+
     // Build scripts should not modify any files outside of the `OUT_DIR` directory,
     // othersize `cargo publish` will fail.
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
@@ -63,38 +448,9 @@ fn main() {
     println!("cargo:rustc-link-lib=dylib=Microsoft.CognitiveServices.Speech.core");
     */
 
-    let mut renew = env::var("RENEW_SDK").map(|v| v == "1").unwrap_or(false);
-    let sdk_output_dir = out_path.join("sdk_output");
-    if !sdk_output_dir.exists() || fs::read_dir(&sdk_output_dir).unwrap().next().is_none() {
-        renew = true;
-        fs::create_dir_all(&sdk_output_dir).unwrap();
-    }
-
-    let sdk_tar_file = out_path.join(format!("SpeechSDK-Linux-{SPEECH_SDK_VERSION}.tar.gz"));
-    if !sdk_tar_file.exists() {
-        download_file(linux_sdk_url.as_str(), sdk_tar_file.to_str().unwrap());
-    }
-
-    if renew {
-        let args = [
-            "--strip",
-            "1",
-            "-xzf",
-            sdk_tar_file.to_str().unwrap(),
-            "-C",
-            sdk_output_dir.to_str().unwrap(),
-        ];
-        Command::new("tar").args(args).status().unwrap();
-    }
+    let (sdk_output_dir, local_sdk) = provision_sdk(&out_path);
 
-    #[cfg(target_arch = "x86")]
-    let lib_path = sdk_output_dir.join("lib").join("x86");
-    #[cfg(target_arch = "x86_64")]
-    let lib_path = sdk_output_dir.join("lib").join("x64");
-    #[cfg(target_arch = "arm")]
-    let lib_path = sdk_output_dir.join("lib").join("arm32");
-    #[cfg(target_arch = "aarch64")]
-    let lib_path = sdk_output_dir.join("lib").join("arm64");
+    let lib_path = sdk_output_dir.join("lib").join(SDK_LIB_ARCH);
 
     let mut inc_arg = String::from("-I");
     inc_arg.push_str(
@@ -105,15 +461,13 @@ fn main() {
             .unwrap(),
     );
 
-    println!("cargo:rustc-link-search=native={}", lib_path.display());
-    println!("cargo:rustc-link-lib=dylib=Microsoft.CognitiveServices.Speech.core");
-
-    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
-        .map(|v| v == "1")
-        .unwrap_or(false);
+    if !runtime_loading {
+        println!("cargo:rustc-link-search=native={}", lib_path.display());
+        println!("cargo:rustc-link-lib=dylib=Microsoft.CognitiveServices.Speech.core");
+    }
 
     if skip_bindgen {
-        return;
+        return false;
     }
 
     // The bindgen::Builder is the main entry point
@@ -141,10 +495,39 @@ fn main() {
         // Unwrap the Result and panic on failure.
         .expect("Unable to generate bindings");
 
+    if local_sdk {
+        // source tree is left untouched for locally provisioned SDK,
+        // src/ffi.rs includes bindings from OUT_DIR instead
+        bindings
+            .write_to_file(out_path.join("bindings.rs"))
+            .expect("Couldn't write bindings!");
+        println!("cargo::rustc-cfg=speech_sdk_out_dir_bindings");
+        return true;
+    }
+
     // Write the bindings to the src/ffi/bindings.rs file.
     bindings
         .write_to_file("src/ffi/bindings.rs")
         .expect("Couldn't write bindings!");
+    false
+}
+
+/// Pre-provisioned SDK (see *provision_sdk*) is supported on Linux only. Fail rather than
+/// silently download SDK when offline build is requested on other platforms.
+#[cfg(not(target_os = "linux"))]
+fn reject_provisioned_sdk() {
+    for name in [
+        "MS_COG_SVC_SPEECH_SDK_DIR",
+        "MS_COG_SVC_SPEECH_SDK_TARBALL",
+        "MS_COG_SVC_SPEECH_SDK_SHA256",
+    ] {
+        if env_var(name).is_some() {
+            panic!(
+                "{name} is set but pre-provisioned Speech SDK is supported on Linux only, \
+                 unset it to download Speech SDK"
+            );
+        }
+    }
 }
 
 #[cfg(any(
@@ -152,11 +535,22 @@ fn main() {
     all(target_os = "macos", target_arch = "arm"),
     all(target_os = "macos", target_arch = "x86_64")
 ))]
-fn main() {
+fn build_native(runtime_loading: bool) -> bool {
+    reject_provisioned_sdk();
+
     if env_var("DOCS_RS").is_some() {
         // Skip linking and bindgen when building docs as docs.rs won't have the
         // dependency present and can't download it.
-        return;
+        return false;
+    }
+
+    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
+        .map(|v| v == "1")
+        .unwrap_or(false);
+
+    // runtime loaded library needs neither SDK nor linking unless bindings are regenerated
+    if runtime_loading && skip_bindgen {
+        return false;
     }
 
     let mac_sdk_url = format!("https://csspeechstorage.blob.core.windows.net/drop/{SPEECH_SDK_VERSION}/MicrosoftCognitiveServicesSpeech-MacOSXCFramework-{SPEECH_SDK_VERSION}.zip");
@@ -189,16 +583,14 @@ fn main() {
         Command::new("unzip").args(args).status().unwrap();
     }
 
-    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
-        .map(|v| v == "1")
-        .unwrap_or(false);
-
     if skip_bindgen {
-        return;
+        return false;
     }
 
-    println!("cargo:rustc-link-search=framework={}/MicrosoftCognitiveServicesSpeech.xcframework/macos-arm64_x86_64", sdk_output_dir.display());
-    println!("cargo:rustc-link-lib=framework=MicrosoftCognitiveServicesSpeech");
+    if !runtime_loading {
+        println!("cargo:rustc-link-search=framework={}/MicrosoftCognitiveServicesSpeech.xcframework/macos-arm64_x86_64", sdk_output_dir.display());
+        println!("cargo:rustc-link-lib=framework=MicrosoftCognitiveServicesSpeech");
+    }
 
     let inc_arg = format!("-I{}/MicrosoftCognitiveServicesSpeech.xcframework/macos-arm64_x86_64/MicrosoftCognitiveServicesSpeech.framework/Headers", sdk_output_dir.display());
 
@@ -221,17 +613,29 @@ fn main() {
     bindings
         .write_to_file("src/ffi/bindings.rs")
         .expect("Couldn't write bindings!");
+    false
 }
 
 #[cfg(target_os = "windows")]
-fn main() {
+fn build_native(runtime_loading: bool) -> bool {
     use std::{fs::File, io::BufReader};
     use zip::ZipArchive;
 
+    reject_provisioned_sdk();
+
     if env_var("DOCS_RS").is_some() {
         // Skip linking and bindgen when building docs as docs.rs won't have the
         // dependency present and can't download it.
-        return;
+        return false;
+    }
+
+    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
+        .map(|v| v == "1")
+        .unwrap_or(false);
+
+    // runtime loaded library needs neither SDK nor linking unless bindings are regenerated
+    if runtime_loading && skip_bindgen {
+        return false;
     }
 
     let nuget_package_url = format!("https://www.nuget.org/api/v2/package/Microsoft.CognitiveServices.Speech/{SPEECH_SDK_VERSION}");
@@ -290,15 +694,13 @@ fn main() {
     let mut inc_arg = String::from("-I");
     inc_arg.push_str(native.join("include").join("c_api").to_str().unwrap());
 
-    println!("cargo:rustc-link-search=native={}", lib_path.display());
-    println!("cargo:rustc-link-lib=dylib=Microsoft.CognitiveServices.Speech.core");
-
-    let skip_bindgen = env::var("MS_COG_SVC_SPEECH_SKIP_BINDGEN")
-        .map(|v| v == "1")
-        .unwrap_or(false);
+    if !runtime_loading {
+        println!("cargo:rustc-link-search=native={}", lib_path.display());
+        println!("cargo:rustc-link-lib=dylib=Microsoft.CognitiveServices.Speech.core");
+    }
 
     if skip_bindgen {
-        return;
+        return false;
     }
 
     // The bindgen::Builder is the main entry point
@@ -319,4 +721,5 @@ fn main() {
     bindings
         .write_to_file("src/ffi/bindings.rs")
         .expect("Couldn't write bindings!");
+    false
 }
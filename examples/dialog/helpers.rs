@@ -27,6 +27,14 @@ pub fn set_callbacks(dialog_svc_connector: &mut DialogServiceConnector) {
         .set_session_stopped_cb(|event| info!(">set_session_stopped_cb {:?}", event))
         .unwrap();
 
+    dialog_svc_connector
+        .set_speech_start_detected_cb(|event| info!(">set_speech_start_detected_cb {:?}", event))
+        .unwrap();
+
+    dialog_svc_connector
+        .set_speech_end_detected_cb(|event| info!(">set_speech_end_detected_cb {:?}", event))
+        .unwrap();
+
     dialog_svc_connector
         .set_recognizing_cb(|event| info!(">set_recognizing_cb {:?}", event.result.text))
         .unwrap();
@@ -1,5 +1,6 @@
 mod helpers;
 mod listen_once_async;
+mod start_continuous_listening_async;
 mod start_keyword_recognition_async;
 
 #[tokio::main]
@@ -10,5 +11,6 @@ async fn main() {
     env_logger::init();
 
     listen_once_async::run_example().await;
+    start_continuous_listening_async::run_example().await;
     start_keyword_recognition_async::run_example().await;
 }
@@ -0,0 +1,37 @@
+use super::helpers;
+use cognitive_services_speech_sdk_rs::audio::{AudioConfig, PushAudioInputStream};
+use log::*;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[allow(dead_code)]
+pub async fn run_example() {
+    info!("---------------------------------------------------");
+    info!("running start_continuous_listening_async example...");
+    info!("---------------------------------------------------");
+
+    let filename = helpers::get_sample_file("hello_rust.wav");
+
+    let input_push_stream = PushAudioInputStream::create_push_stream().unwrap();
+    let audio_config = AudioConfig::from_stream_input(&input_push_stream).unwrap();
+
+    let mut dialog_service_connector =
+        helpers::dialog_service_connector_from_audio_cfg(audio_config);
+    helpers::set_callbacks(&mut dialog_service_connector);
+
+    helpers::push_file_into_stream(&filename, input_push_stream);
+    if let Err(err) = dialog_service_connector
+        .start_continuous_listening_async()
+        .await
+    {
+        error!("start_continuous_listening_async error {:?}", err);
+    }
+
+    sleep(Duration::from_millis(5000)).await;
+
+    if let Err(err) = dialog_service_connector.stop_listening_async().await {
+        error!("stop_listening_async error {:?}", err);
+    }
+
+    info!("example finished!");
+}
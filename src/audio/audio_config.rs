@@ -1,4 +1,4 @@
-use crate::audio::{AudioInputStream, AudioOutputStream};
+use crate::audio::{AudioInputStream, AudioOutputStream, ChunkMetadataTimeline};
 use crate::common::{PropertyCollection, PropertyId};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
@@ -22,6 +22,7 @@ use std::mem::MaybeUninit;
 pub struct AudioConfig {
     pub handle: SmartHandle<SPXAUDIOCONFIGHANDLE>,
     pub properties: PropertyCollection,
+    pub(crate) chunk_metadata: Option<ChunkMetadataTimeline>,
 }
 
 impl AudioConfig {
@@ -38,6 +39,7 @@ impl AudioConfig {
             let result = AudioConfig {
                 handle: SmartHandle::create("AudioConfig", handle, audio_config_release),
                 properties: property_bag,
+                chunk_metadata: None,
             };
             Ok(result)
         }
@@ -52,7 +54,9 @@ impl AudioConfig {
             );
             convert_err(ret, "AudioConfig::from_stream_input error")?;
             info!("from_stream_input ok");
-            AudioConfig::from_handle(handle.assume_init())
+            let mut audio_config = AudioConfig::from_handle(handle.assume_init())?;
+            audio_config.chunk_metadata = stream.chunk_metadata_timeline();
+            Ok(audio_config)
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::audio::ChunkMetadataTimeline;
 use crate::ffi::SPXAUDIOSTREAMHANDLE;
 
 /// Abstraction over audio input push & pull streams.
@@ -5,4 +6,9 @@ use crate::ffi::SPXAUDIOSTREAMHANDLE;
 /// input audio streams by respective functions.
 pub trait AudioInputStream {
     fn get_handle(&self) -> SPXAUDIOSTREAMHANDLE;
+
+    /// Timeline of chunk metadata written into / read from the stream, if tracked.
+    fn chunk_metadata_timeline(&self) -> Option<ChunkMetadataTimeline> {
+        None
+    }
 }
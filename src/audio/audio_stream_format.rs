@@ -1,10 +1,10 @@
-use crate::audio::AudioStreamContainerFormat;
+use crate::audio::{AudioStreamContainerFormat, WaveFormat};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
     audio_stream_format_create_from_compressed_format,
     audio_stream_format_create_from_default_input, audio_stream_format_create_from_default_output,
-    audio_stream_format_create_from_waveformat_pcm, audio_stream_format_release, SmartHandle,
-    SPXAUDIOSTREAMFORMATHANDLE,
+    audio_stream_format_create_from_waveformat, audio_stream_format_create_from_waveformat_pcm,
+    audio_stream_format_release, SmartHandle, SPXAUDIOSTREAMFORMATHANDLE,
 };
 use std::mem::MaybeUninit;
 
@@ -13,14 +13,40 @@ use std::mem::MaybeUninit;
 pub struct AudioStreamFormat {
     /// Smart handle wrapping underlying SPXAUDIOSTREAMFORMATHANDLE
     pub handle: SmartHandle<SPXAUDIOSTREAMFORMATHANDLE>,
+    description: FormatDescription,
 }
 
+/// What the format handle was created from. Native SDK does not expose
+/// getters for format handles, hence the parameters are kept on Rust side.
+#[derive(Debug, Clone, Copy)]
+enum FormatDescription {
+    Wave {
+        samples_per_second: u32,
+        bits_per_sample: u8,
+        channels: u8,
+        wave_format: WaveFormat,
+    },
+    Compressed(AudioStreamContainerFormat),
+}
+
+/// Default input/output format: 16 kHz, 16 bit, mono PCM.
+const DEFAULT_FORMAT: FormatDescription = FormatDescription::Wave {
+    samples_per_second: 16000,
+    bits_per_sample: 16,
+    channels: 1,
+    wave_format: WaveFormat::Pcm,
+};
+
 impl AudioStreamFormat {
     /// # Safety
     /// `handle` must be a valid handle to a live audio stream format.
-    unsafe fn from_handle(handle: SPXAUDIOSTREAMFORMATHANDLE) -> Result<AudioStreamFormat> {
+    unsafe fn from_handle(
+        handle: SPXAUDIOSTREAMFORMATHANDLE,
+        description: FormatDescription,
+    ) -> Result<AudioStreamFormat> {
         Ok(AudioStreamFormat {
             handle: SmartHandle::create("AudioStreamFormat", handle, audio_stream_format_release),
+            description,
         })
     }
 
@@ -32,16 +58,57 @@ impl AudioStreamFormat {
         bits_per_sample: Option<u8>,
         channels: Option<u8>,
     ) -> Result<AudioStreamFormat> {
+        let bits_per_sample = bits_per_sample.unwrap_or(16);
+        let channels = channels.unwrap_or(1);
         unsafe {
             let mut handle: MaybeUninit<SPXAUDIOSTREAMFORMATHANDLE> = MaybeUninit::uninit();
             let ret = audio_stream_format_create_from_waveformat_pcm(
                 handle.as_mut_ptr(),
                 samples_per_second,
-                bits_per_sample.unwrap_or(16),
-                channels.unwrap_or(1),
+                bits_per_sample,
+                channels,
             );
             convert_err(ret, "AudioStreamFormat::get_wave_format_pcm error")?;
-            AudioStreamFormat::from_handle(handle.assume_init())
+            AudioStreamFormat::from_handle(
+                handle.assume_init(),
+                FormatDescription::Wave {
+                    samples_per_second,
+                    bits_per_sample,
+                    channels,
+                    wave_format: WaveFormat::Pcm,
+                },
+            )
+        }
+    }
+
+    /// FromWaveFormat creates an audio stream format object with the specified waveformat characteristics,
+    /// e.g. 8 kHz, 8 bit, mono *WaveFormat::Mulaw* for G.711 telephony audio. Data written into
+    /// *PushAudioInputStream* created with such format are passed to the service without transcoding.
+    pub fn from_waveformat(
+        samples_per_second: u32,
+        bits_per_sample: u8,
+        channels: u8,
+        wave_format: WaveFormat,
+    ) -> Result<AudioStreamFormat> {
+        unsafe {
+            let mut handle: MaybeUninit<SPXAUDIOSTREAMFORMATHANDLE> = MaybeUninit::uninit();
+            let ret = audio_stream_format_create_from_waveformat(
+                handle.as_mut_ptr(),
+                samples_per_second,
+                bits_per_sample,
+                channels,
+                wave_format.into(),
+            );
+            convert_err(ret, "AudioStreamFormat::from_waveformat error")?;
+            AudioStreamFormat::from_handle(
+                handle.assume_init(),
+                FormatDescription::Wave {
+                    samples_per_second,
+                    bits_per_sample,
+                    channels,
+                    wave_format,
+                },
+            )
         }
     }
 
@@ -52,7 +119,7 @@ impl AudioStreamFormat {
             let mut handle: MaybeUninit<SPXAUDIOSTREAMFORMATHANDLE> = MaybeUninit::uninit();
             let ret = audio_stream_format_create_from_default_input(handle.as_mut_ptr());
             convert_err(ret, "AudioStreamFormat::get_default_format error")?;
-            AudioStreamFormat::from_handle(handle.assume_init())
+            AudioStreamFormat::from_handle(handle.assume_init(), DEFAULT_FORMAT)
         }
     }
 
@@ -63,7 +130,7 @@ impl AudioStreamFormat {
             let mut handle: MaybeUninit<SPXAUDIOSTREAMFORMATHANDLE> = MaybeUninit::uninit();
             let ret = audio_stream_format_create_from_default_output(handle.as_mut_ptr());
             convert_err(ret, "AudioStreamFormat::get_default_output_format error")?;
-            AudioStreamFormat::from_handle(handle.assume_init())
+            AudioStreamFormat::from_handle(handle.assume_init(), DEFAULT_FORMAT)
         }
     }
 
@@ -79,7 +146,54 @@ impl AudioStreamFormat {
                 compressed_format.into(),
             );
             convert_err(ret, "AudioStreamFormat::get_compressed_format error")?;
-            AudioStreamFormat::from_handle(handle.assume_init())
+            AudioStreamFormat::from_handle(
+                handle.assume_init(),
+                FormatDescription::Compressed(compressed_format),
+            )
+        }
+    }
+
+    /// Wave format of the stream, None for compressed formats.
+    pub fn wave_format(&self) -> Option<WaveFormat> {
+        match self.description {
+            FormatDescription::Wave { wave_format, .. } => Some(wave_format),
+            FormatDescription::Compressed(_) => None,
+        }
+    }
+
+    /// Sample rate of the stream, None for compressed formats.
+    pub fn samples_per_second(&self) -> Option<u32> {
+        match self.description {
+            FormatDescription::Wave {
+                samples_per_second, ..
+            } => Some(samples_per_second),
+            FormatDescription::Compressed(_) => None,
+        }
+    }
+
+    /// Bits per sample, None for compressed formats.
+    pub fn bits_per_sample(&self) -> Option<u8> {
+        match self.description {
+            FormatDescription::Wave {
+                bits_per_sample, ..
+            } => Some(bits_per_sample),
+            FormatDescription::Compressed(_) => None,
+        }
+    }
+
+    /// Number of channels, None for compressed formats.
+    pub fn channels(&self) -> Option<u8> {
+        match self.description {
+            FormatDescription::Wave { channels, .. } => Some(channels),
+            FormatDescription::Compressed(_) => None,
+        }
+    }
+
+    /// Container format of the stream, None for wave formats.
+    pub fn container_format(&self) -> Option<AudioStreamContainerFormat> {
+        match self.description {
+            FormatDescription::Wave { .. } => None,
+            FormatDescription::Compressed(format) => Some(format),
         }
     }
 }
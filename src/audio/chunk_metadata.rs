@@ -0,0 +1,161 @@
+use crate::audio::AudioStreamFormat;
+use crate::common::PropertyId;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Recognition offsets are in ticks of 100 nanoseconds.
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Maximal number of metadata changes kept by *ChunkMetadataTimeline*.
+/// Oldest entries are discarded first, results of such old audio get no metadata.
+const MAX_ENTRIES: usize = 4096;
+
+/// ChunkMetadata is attached to chunk of audio written into push stream
+/// (see *PushAudioInputStream::write_with_metadata*) or read from pull stream
+/// (see *PullAudioInputStreamCallbacks::chunk_metadata*). Passed to the service as
+/// *DataBufferTimeStamp* and *DataBufferUserID* properties.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ChunkMetadata {
+    /// Time stamp of the chunk, 64-bit value with resolution of 90 kHz.
+    pub timestamp: Option<u64>,
+    /// Id of the speaker of the chunk.
+    pub user_id: Option<String>,
+}
+
+impl ChunkMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.timestamp.is_none() && self.user_id.is_none()
+    }
+
+    /// Returns value of *DataBufferTimeStamp* or *DataBufferUserID* property, None otherwise.
+    pub fn property_value(&self, id: PropertyId) -> Option<String> {
+        match id {
+            PropertyId::DataBufferTimeStamp => self.timestamp.map(|ts| ts.to_string()),
+            PropertyId::DataBufferUserID => self.user_id.clone(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TimelineInner {
+    bytes_per_second: u64,
+    position: u64,
+    /// Start offset (ticks) of each run of chunks with the same metadata.
+    entries: VecDeque<(u64, ChunkMetadata)>,
+}
+
+/// ChunkMetadataTimeline records metadata of chunks passed through input stream
+/// so that recognition results can be mapped onto chunk they originate from.
+/// Shared by the stream, audio config and recognizer.
+#[derive(Debug, Clone)]
+pub struct ChunkMetadataTimeline {
+    inner: Arc<Mutex<TimelineInner>>,
+}
+
+impl ChunkMetadataTimeline {
+    /// Returns None for compressed formats, their offsets cannot be derived from byte count.
+    pub(crate) fn for_format(format: &AudioStreamFormat) -> Option<Self> {
+        let bytes_per_second =
+            u64::from(format.samples_per_second()?) * u64::from(format.bits_per_sample()?) / 8
+                * u64::from(format.channels()?);
+        if bytes_per_second == 0 {
+            return None;
+        }
+        Some(Self::new(bytes_per_second))
+    }
+
+    fn new(bytes_per_second: u64) -> Self {
+        ChunkMetadataTimeline {
+            inner: Arc::new(Mutex::new(TimelineInner {
+                bytes_per_second,
+                position: 0,
+                entries: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Records chunk of *len* bytes, metadata is attached if differs from previous chunk.
+    pub(crate) fn record(&self, len: usize, metadata: Option<&ChunkMetadata>) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let metadata = metadata.cloned().unwrap_or_default();
+        let changed = inner
+            .entries
+            .back()
+            .map_or(!metadata.is_empty(), |(_, last)| *last != metadata);
+        if changed {
+            let offset = inner.position * TICKS_PER_SECOND / inner.bytes_per_second;
+            inner.entries.push_back((offset, metadata));
+            if inner.entries.len() > MAX_ENTRIES {
+                inner.entries.pop_front();
+            }
+        }
+        inner.position += len as u64;
+    }
+
+    /// Returns metadata of chunk containing given offset (ticks of 100 ns from stream start).
+    pub fn lookup(&self, offset: u64) -> Option<ChunkMetadata> {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .entries
+            .iter()
+            .rfind(|(start, _)| *start <= offset)
+            .map(|(_, metadata)| metadata.clone())
+            .filter(|metadata| !metadata.is_empty())
+    }
+
+    /// Discards entries of chunks preceding the one containing given offset.
+    /// Called for final results, which are delivered in order of their offsets,
+    /// partial results of the same phrase may still refer to older chunks.
+    pub(crate) fn discard_before(&self, offset: u64) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(index) = inner
+            .entries
+            .iter()
+            .rposition(|(start, _)| *start <= offset)
+        {
+            inner.entries.drain(..index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(user_id: &str) -> ChunkMetadata {
+        ChunkMetadata {
+            timestamp: None,
+            user_id: Some(user_id.to_owned()),
+        }
+    }
+
+    #[test]
+    fn chunk_metadata_timeline_discard_test() {
+        // one byte is one second
+        let timeline = ChunkMetadataTimeline::new(1);
+        timeline.record(1, Some(&metadata("alice")));
+        timeline.record(1, Some(&metadata("bob")));
+        timeline.record(1, None);
+
+        // lookup does not discard anything
+        assert_eq!(timeline.lookup(15_000_000), Some(metadata("bob")));
+        assert_eq!(timeline.lookup(5_000_000), Some(metadata("alice")));
+        assert_eq!(timeline.lookup(25_000_000), None);
+
+        timeline.discard_before(15_000_000);
+        assert_eq!(timeline.lookup(5_000_000), None);
+        assert_eq!(timeline.lookup(15_000_000), Some(metadata("bob")));
+    }
+
+    #[test]
+    fn chunk_metadata_timeline_capacity_test() {
+        let timeline = ChunkMetadataTimeline::new(1);
+        for i in 0..=MAX_ENTRIES {
+            timeline.record(1, Some(&metadata(&i.to_string())));
+        }
+        let inner = timeline.inner.lock().unwrap();
+        assert_eq!(inner.entries.len(), MAX_ENTRIES);
+        assert_eq!(inner.entries.front().unwrap().1, metadata("1"));
+    }
+}
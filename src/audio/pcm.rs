@@ -0,0 +1,21 @@
+//! Package pcm provides pure-Rust utilities for ingestion of WAV files and raw PCM audio
+//! in formats not supported by native library. Audio is converted into 16-bit mono PCM
+//! (see *AudioStreamFormat::get_wave_format_pcm*) and pumped into *PushAudioInputStream*.
+mod channel_mix;
+#[cfg(feature = "mp3-decoding")]
+mod mp3_decoder;
+mod pcm_converter;
+mod resampler;
+mod sample_format;
+mod wav_header;
+
+// re-export structs directly under pcm module
+pub use self::channel_mix::{deinterleave, ChannelMix};
+#[cfg(feature = "mp3-decoding")]
+pub use self::mp3_decoder::decode_mp3;
+pub use self::pcm_converter::{push_stream_from_wav_file, PcmConverter};
+pub use self::resampler::Resampler;
+pub use self::sample_format::{
+    alaw_to_i16, f32_to_i16, i16_samples_to_bytes, mulaw_to_i16, SampleFormat,
+};
+pub use self::wav_header::WavHeader;
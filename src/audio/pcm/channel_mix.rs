@@ -0,0 +1,47 @@
+/// ChannelMix defines how multichannel audio is reduced to single (mono) channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelMix {
+    /// Averages all channels.
+    #[default]
+    Average,
+    /// Takes only given channel (zero based), e.g. one party of stereo telephony recording.
+    Select(u16),
+}
+
+impl ChannelMix {
+    /// Reduces single frame (one sample per channel) to mono sample.
+    pub fn mix(&self, frame: &[f32]) -> f32 {
+        match self {
+            ChannelMix::Average => frame.iter().sum::<f32>() / frame.len().max(1) as f32,
+            ChannelMix::Select(channel) => frame.get(*channel as usize).copied().unwrap_or(0.0),
+        }
+    }
+
+    /// Reduces interleaved samples to mono. Trailing incomplete frame is ignored.
+    pub fn downmix(&self, samples: &[f32], channels: u16) -> Vec<f32> {
+        if channels <= 1 {
+            return samples.to_vec();
+        }
+        samples
+            .chunks_exact(channels as usize)
+            .map(|frame| self.mix(frame))
+            .collect()
+    }
+}
+
+/// Splits interleaved audio into one buffer per channel, samples are copied as is.
+/// Trailing incomplete frame is ignored.
+pub fn deinterleave(data: &[u8], bytes_per_sample: usize, channels: u16) -> Vec<Vec<u8>> {
+    let channels = usize::from(channels.max(1));
+    let frame_len = bytes_per_sample * channels;
+    let mut split = vec![Vec::with_capacity(data.len() / channels); channels];
+    if frame_len == 0 {
+        return split;
+    }
+    for frame in data.chunks_exact(frame_len) {
+        for (channel, sample) in frame.chunks_exact(bytes_per_sample).enumerate() {
+            split[channel].extend_from_slice(sample);
+        }
+    }
+    split
+}
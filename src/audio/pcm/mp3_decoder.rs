@@ -0,0 +1,63 @@
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use std::io::{Cursor, ErrorKind};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+fn decode_error(error: SymphoniaError) -> Error {
+    Error::new(
+        format!("decode_mp3 error: {}", error),
+        ErrorRootCause::ApiError(SpxError::UnsupportedFormat),
+    )
+}
+
+/// Decodes MP3 stream into interleaved 16-bit PCM.
+/// Corrupted frames are skipped.
+pub fn decode_mp3(data: &[u8]) -> Result<Vec<i16>> {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(data.to_vec())), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension("mp3");
+    let mut reader = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(decode_error)?
+        .format;
+    let track = reader
+        .default_track()
+        .ok_or_else(|| decode_error(SymphoniaError::Unsupported("no audio track")))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(decode_error)?;
+
+    let mut samples = vec![];
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err)) if err.kind() == ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(decode_error(err)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let mut buffer =
+                    SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(err) => return Err(decode_error(err)),
+        }
+    }
+    Ok(samples)
+}
@@ -0,0 +1,144 @@
+use crate::audio::pcm::{
+    f32_to_i16, i16_samples_to_bytes, ChannelMix, Resampler, SampleFormat, WavHeader,
+};
+use crate::audio::{AudioStreamFormat, PushAudioInputStream};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+
+const PUMP_BUFFER_SIZE: usize = 64 * 1024;
+
+/// PcmConverter converts WAV or raw PCM audio of arbitrary sample format,
+/// sample rate and number of channels into 16-bit mono PCM with target sample rate.
+/// Audio can be converted in chunks of any size, incomplete frames are carried over.
+#[derive(Debug, Clone)]
+pub struct PcmConverter {
+    sample_format: SampleFormat,
+    channels: u16,
+    mix: ChannelMix,
+    resampler: Resampler,
+    pending: Vec<u8>,
+}
+
+impl PcmConverter {
+    pub fn new(
+        sample_format: SampleFormat,
+        samples_per_second: u32,
+        channels: u16,
+        target_samples_per_second: u32,
+        mix: ChannelMix,
+    ) -> Result<PcmConverter> {
+        if channels == 0 || samples_per_second == 0 || target_samples_per_second == 0 {
+            return Err(Error::new(
+                "PcmConverter::new error: channels and sample rates must be non-zero".to_owned(),
+                ErrorRootCause::ApiError(SpxError::InvalidArg),
+            ));
+        }
+        if let ChannelMix::Select(channel) = mix {
+            if channel >= channels {
+                return Err(Error::new(
+                    format!(
+                        "PcmConverter::new error: channel {} selected, audio has {} channel(s)",
+                        channel, channels
+                    ),
+                    ErrorRootCause::ApiError(SpxError::OutOfRange),
+                ));
+            }
+        }
+        Ok(PcmConverter {
+            sample_format,
+            channels,
+            mix,
+            resampler: Resampler::new(samples_per_second, target_samples_per_second),
+            pending: vec![],
+        })
+    }
+
+    pub fn from_wav_header(
+        header: &WavHeader,
+        target_samples_per_second: u32,
+        mix: ChannelMix,
+    ) -> Result<PcmConverter> {
+        PcmConverter::new(
+            header.sample_format,
+            header.samples_per_second,
+            header.channels,
+            target_samples_per_second,
+            mix,
+        )
+    }
+
+    pub fn target_samples_per_second(&self) -> u32 {
+        self.resampler.to_samples_per_second()
+    }
+
+    /// Returns format of converted audio, i.e. 16-bit mono PCM with target sample rate.
+    pub fn target_format(&self) -> Result<AudioStreamFormat> {
+        AudioStreamFormat::get_wave_format_pcm(self.target_samples_per_second(), Some(16), Some(1))
+    }
+
+    /// Creates push stream accepting converted audio.
+    pub fn create_push_stream(&self) -> Result<PushAudioInputStream> {
+        PushAudioInputStream::create_push_stream_from_format(self.target_format()?)
+    }
+
+    /// Converts next chunk of audio and returns 16-bit little endian mono samples.
+    pub fn convert(&mut self, data: &[u8]) -> Vec<u8> {
+        let block_align = self.sample_format.bytes_per_sample() * self.channels as usize;
+        self.pending.extend_from_slice(data);
+        let complete = self.pending.len() - self.pending.len() % block_align;
+
+        let samples = self.sample_format.decode_all(&self.pending[..complete]);
+        self.pending.drain(..complete);
+
+        let mono = self.mix.downmix(&samples, self.channels);
+        let resampled = self.resampler.process(&mono);
+        let converted: Vec<i16> = resampled.into_iter().map(f32_to_i16).collect();
+        i16_samples_to_bytes(&converted)
+    }
+
+    /// Reads audio from *reader* until EOF and writes converted audio into *stream*.
+    /// Stream is not closed. Returns number of bytes written into the stream.
+    pub fn pump<R: Read>(
+        &mut self,
+        reader: &mut R,
+        stream: &mut PushAudioInputStream,
+    ) -> Result<u64> {
+        let mut buffer = vec![0u8; PUMP_BUFFER_SIZE];
+        let mut written = 0u64;
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(written),
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            };
+            let converted = self.convert(&buffer[..read]);
+            if !converted.is_empty() {
+                stream.write(&converted)?;
+                written += converted.len() as u64;
+            }
+        }
+    }
+}
+
+/// Reads whole WAV file, converts it into 16-bit mono PCM with target sample rate
+/// and returns closed push stream containing converted audio.
+/// Usable with *AudioConfig::from_stream_input*.
+pub fn push_stream_from_wav_file(
+    file_name: &str,
+    target_samples_per_second: u32,
+    mix: ChannelMix,
+) -> Result<PushAudioInputStream> {
+    let mut reader = BufReader::new(File::open(file_name)?);
+    let header = WavHeader::read_from(&mut reader)?;
+    let mut converter = PcmConverter::from_wav_header(&header, target_samples_per_second, mix)?;
+    let mut stream = converter.create_push_stream()?;
+    if header.data_len == 0 || header.data_len == u32::MAX {
+        converter.pump(&mut reader, &mut stream)?;
+    } else {
+        converter.pump(&mut reader.take(u64::from(header.data_len)), &mut stream)?;
+    }
+    stream.close_stream()?;
+    Ok(stream)
+}
@@ -0,0 +1,130 @@
+use std::f64::consts::PI;
+
+/// Resampler converts mono audio between sample rates using linear interpolation.
+/// When downsampling, audio is low-pass filtered first (windowed sinc FIR) so that
+/// frequencies above the target Nyquist frequency do not alias into speech band.
+/// The filter delays audio by 8 output samples (0.5 ms at 16 kHz). Audio can be
+/// processed in arbitrary chunks, filter and interpolation state is carried over
+/// between *process* calls.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    from_samples_per_second: u32,
+    to_samples_per_second: u32,
+    // input samples per one output sample
+    step: f64,
+    // position of next output sample relative to start of next input chunk,
+    // -1.0..0.0 refers to the interval between previous chunk's last sample and current chunk
+    position: f64,
+    previous: Option<f32>,
+    // anti-aliasing filter, empty unless downsampling
+    taps: Vec<f32>,
+    // last taps.len() - 1 input samples
+    history: Vec<f32>,
+}
+
+/// Designs low-pass filter with Blackman window, *cutoff* is relative to sample rate.
+fn low_pass_taps(cutoff: f64, half_length: usize) -> Vec<f32> {
+    let length = 2 * half_length + 1;
+    let taps: Vec<f64> = (0..length)
+        .map(|n| {
+            let m = n as f64 - half_length as f64;
+            let sinc = if m == 0.0 {
+                2.0 * cutoff
+            } else {
+                (2.0 * PI * cutoff * m).sin() / (PI * m)
+            };
+            let phase = 2.0 * PI * n as f64 / (length - 1) as f64;
+            sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+        })
+        .collect();
+    // unity gain at DC
+    let sum: f64 = taps.iter().sum();
+    taps.into_iter().map(|tap| (tap / sum) as f32).collect()
+}
+
+impl Resampler {
+    pub fn new(from_samples_per_second: u32, to_samples_per_second: u32) -> Self {
+        let step = f64::from(from_samples_per_second) / f64::from(to_samples_per_second.max(1));
+        let taps = if step > 1.0 {
+            // cutoff slightly below target Nyquist frequency to leave room for transition band
+            low_pass_taps(0.45 / step, (8.0 * step).ceil() as usize)
+        } else {
+            vec![]
+        };
+        Resampler {
+            from_samples_per_second,
+            to_samples_per_second,
+            step,
+            position: 0.0,
+            previous: None,
+            taps,
+            history: vec![],
+        }
+    }
+
+    pub fn from_samples_per_second(&self) -> u32 {
+        self.from_samples_per_second
+    }
+
+    pub fn to_samples_per_second(&self) -> u32 {
+        self.to_samples_per_second
+    }
+
+    /// Resamples next chunk of audio.
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.from_samples_per_second == self.to_samples_per_second || input.is_empty() {
+            return input.to_vec();
+        }
+
+        let filtered;
+        let input = if self.taps.is_empty() {
+            input
+        } else {
+            filtered = self.filter(input);
+            &filtered
+        };
+
+        let mut output = Vec::with_capacity((input.len() as f64 / self.step) as usize + 1);
+        loop {
+            let index = self.position.floor();
+            if index + 1.0 >= input.len() as f64 {
+                break;
+            }
+            let fraction = (self.position - index) as f32;
+            let current = if index < 0.0 {
+                self.previous.unwrap_or(input[0])
+            } else {
+                input[index as usize]
+            };
+            let next = input[(index + 1.0) as usize];
+            output.push(current + (next - current) * fraction);
+            self.position += self.step;
+        }
+        self.position -= input.len() as f64;
+        self.previous = input.last().copied();
+        output
+    }
+
+    /// Applies anti-aliasing filter. Before the first chunk the filter is primed
+    /// with its first sample so that the output does not ramp up from silence.
+    fn filter(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.history.is_empty() {
+            self.history = vec![input[0]; self.taps.len() - 1];
+        }
+        let mut extended = std::mem::take(&mut self.history);
+        extended.extend_from_slice(input);
+        let filtered = extended
+            .windows(self.taps.len())
+            .map(|window| {
+                window
+                    .iter()
+                    .zip(&self.taps)
+                    .map(|(sample, tap)| sample * tap)
+                    .sum()
+            })
+            .collect();
+        extended.drain(..input.len());
+        self.history = extended;
+        filtered
+    }
+}
@@ -0,0 +1,144 @@
+/// SampleFormat defines encoding of single audio sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// 8-bit unsigned integer PCM.
+    U8,
+    /// 16-bit signed integer PCM (little endian).
+    I16,
+    /// 24-bit signed integer PCM (little endian, packed in 3 bytes).
+    I24,
+    /// 32-bit signed integer PCM (little endian).
+    I32,
+    /// 32-bit IEEE float (little endian).
+    F32,
+    /// 64-bit IEEE float (little endian).
+    F64,
+    /// 8-bit G.711 μ-law.
+    MuLaw,
+    /// 8-bit G.711 A-law.
+    ALaw,
+}
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_ALAW: u16 = 0x0006;
+const WAVE_FORMAT_MULAW: u16 = 0x0007;
+
+impl SampleFormat {
+    /// Returns sample format for given WAV format tag and bits per sample.
+    /// For WAVE_FORMAT_EXTENSIBLE pass format tag of the sub format.
+    pub fn from_wav_format_tag(format_tag: u16, bits_per_sample: u16) -> Option<Self> {
+        match (format_tag, bits_per_sample) {
+            (WAVE_FORMAT_PCM, 8) => Some(SampleFormat::U8),
+            (WAVE_FORMAT_PCM, 16) => Some(SampleFormat::I16),
+            (WAVE_FORMAT_PCM, 24) => Some(SampleFormat::I24),
+            (WAVE_FORMAT_PCM, 32) => Some(SampleFormat::I32),
+            (WAVE_FORMAT_IEEE_FLOAT, 32) => Some(SampleFormat::F32),
+            (WAVE_FORMAT_IEEE_FLOAT, 64) => Some(SampleFormat::F64),
+            (WAVE_FORMAT_ALAW, 8) => Some(SampleFormat::ALaw),
+            (WAVE_FORMAT_MULAW, 8) => Some(SampleFormat::MuLaw),
+            _ => None,
+        }
+    }
+
+    pub fn wav_format_tag(&self) -> u16 {
+        match self {
+            SampleFormat::U8 | SampleFormat::I16 | SampleFormat::I24 | SampleFormat::I32 => {
+                WAVE_FORMAT_PCM
+            }
+            SampleFormat::F32 | SampleFormat::F64 => WAVE_FORMAT_IEEE_FLOAT,
+            SampleFormat::ALaw => WAVE_FORMAT_ALAW,
+            SampleFormat::MuLaw => WAVE_FORMAT_MULAW,
+        }
+    }
+
+    pub fn bits_per_sample(&self) -> u16 {
+        self.bytes_per_sample() as u16 * 8
+    }
+
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            SampleFormat::U8 | SampleFormat::MuLaw | SampleFormat::ALaw => 1,
+            SampleFormat::I16 => 2,
+            SampleFormat::I24 => 3,
+            SampleFormat::I32 | SampleFormat::F32 => 4,
+            SampleFormat::F64 => 8,
+        }
+    }
+
+    /// Decodes single sample into float in range -1.0..1.0.
+    /// *bytes* must be exactly *bytes_per_sample* long.
+    pub fn decode(&self, bytes: &[u8]) -> f32 {
+        match self {
+            SampleFormat::U8 => (f32::from(bytes[0]) - 128.0) / 128.0,
+            SampleFormat::I16 => f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0,
+            SampleFormat::I24 => {
+                // place 24 bits into upper part of i32 to get sign extension for free
+                let sample = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+                sample as f32 / 8_388_608.0
+            }
+            SampleFormat::I32 => {
+                i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32
+                    / 2_147_483_648.0
+            }
+            SampleFormat::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            SampleFormat::F64 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&bytes[..8]);
+                f64::from_le_bytes(buf) as f32
+            }
+            SampleFormat::MuLaw => f32::from(mulaw_to_i16(bytes[0])) / 32768.0,
+            SampleFormat::ALaw => f32::from(alaw_to_i16(bytes[0])) / 32768.0,
+        }
+    }
+
+    /// Decodes interleaved samples into floats. Trailing incomplete sample is ignored.
+    pub fn decode_all(&self, data: &[u8]) -> Vec<f32> {
+        data.chunks_exact(self.bytes_per_sample())
+            .map(|bytes| self.decode(bytes))
+            .collect()
+    }
+}
+
+/// Decodes G.711 μ-law sample into 16-bit linear PCM.
+pub fn mulaw_to_i16(mulaw: u8) -> i16 {
+    let mulaw = !mulaw;
+    let exponent = (mulaw >> 4) & 0x07;
+    let mantissa = i32::from(mulaw & 0x0F);
+    let magnitude = (((mantissa << 3) + 0x84) << exponent) - 0x84;
+    if mulaw & 0x80 != 0 {
+        -magnitude as i16
+    } else {
+        magnitude as i16
+    }
+}
+
+/// Decodes G.711 A-law sample into 16-bit linear PCM.
+pub fn alaw_to_i16(alaw: u8) -> i16 {
+    let alaw = alaw ^ 0x55;
+    let exponent = (alaw >> 4) & 0x07;
+    let mantissa = i32::from(alaw & 0x0F);
+    let magnitude = if exponent == 0 {
+        (mantissa << 4) + 8
+    } else {
+        ((mantissa << 4) + 0x108) << (exponent - 1)
+    };
+    if alaw & 0x80 != 0 {
+        magnitude as i16
+    } else {
+        -magnitude as i16
+    }
+}
+
+/// Converts float sample into 16-bit PCM, out of range values are clipped.
+pub fn f32_to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * 32767.0).round() as i16
+}
+
+/// Serializes 16-bit samples as little endian bytes.
+pub fn i16_samples_to_bytes(samples: &[i16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect()
+}
@@ -0,0 +1,195 @@
+use crate::audio::pcm::SampleFormat;
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use std::io::{Read, Write};
+
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// WavHeader describes audio stored in WAV (RIFF) file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavHeader {
+    pub sample_format: SampleFormat,
+    pub samples_per_second: u32,
+    pub channels: u16,
+    /// Length of the audio data in bytes. Streamed WAV files
+    /// often contain 0 or 0xFFFFFFFF here, audio should be read until EOF then.
+    pub data_len: u32,
+}
+
+fn invalid_header(message: &str) -> Error {
+    Error::new(
+        format!("WavHeader.read_from error: {}", message),
+        ErrorRootCause::ApiError(SpxError::InvalidHeader),
+    )
+}
+
+fn unsupported_format(format_tag: u16, bits_per_sample: u16) -> Error {
+    Error::new(
+        format!(
+            "WavHeader.read_from error: unsupported format tag {:#x} ({} bits per sample)",
+            format_tag, bits_per_sample
+        ),
+        ErrorRootCause::ApiError(SpxError::UnsupportedFormat),
+    )
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_tag<R: Read>(reader: &mut R) -> Result<[u8; 4]> {
+    let mut tag = [0u8; 4];
+    reader.read_exact(&mut tag)?;
+    Ok(tag)
+}
+
+fn skip<R: Read>(reader: &mut R, len: u64) -> Result<()> {
+    let skipped = std::io::copy(&mut reader.take(len), &mut std::io::sink())?;
+    if skipped != len {
+        return Err(invalid_header("unexpected end of file"));
+    }
+    Ok(())
+}
+
+impl WavHeader {
+    pub fn new(
+        sample_format: SampleFormat,
+        samples_per_second: u32,
+        channels: u16,
+        data_len: u32,
+    ) -> Self {
+        WavHeader {
+            sample_format,
+            samples_per_second,
+            channels,
+            data_len,
+        }
+    }
+
+    /// Number of bytes of one frame, i.e. one sample of each channel.
+    /// None if it does not fit into 16-bit header field.
+    pub fn block_align(&self) -> Option<u16> {
+        self.channels
+            .checked_mul(self.sample_format.bytes_per_sample() as u16)
+    }
+
+    /// Number of bytes per second. None if it does not fit into 32-bit header field.
+    pub fn byte_rate(&self) -> Option<u32> {
+        self.samples_per_second
+            .checked_mul(u32::from(self.block_align()?))
+    }
+
+    /// Parses RIFF header up to the beginning of data chunk.
+    /// When successful *reader* is positioned at the first byte of audio data.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<WavHeader> {
+        if &read_tag(reader)? != b"RIFF" {
+            return Err(invalid_header("RIFF tag not found"));
+        }
+        let _riff_len = read_u32(reader)?;
+        if &read_tag(reader)? != b"WAVE" {
+            return Err(invalid_header("WAVE tag not found"));
+        }
+
+        let mut format: Option<(SampleFormat, u32, u16)> = None;
+        loop {
+            let tag = read_tag(reader)?;
+            let len = read_u32(reader)?;
+            match &tag {
+                b"fmt " => {
+                    if len < 16 {
+                        return Err(invalid_header("fmt chunk too short"));
+                    }
+                    let mut format_tag = read_u16(reader)?;
+                    let channels = read_u16(reader)?;
+                    let samples_per_second = read_u32(reader)?;
+                    let _byte_rate = read_u32(reader)?;
+                    let _block_align = read_u16(reader)?;
+                    let bits_per_sample = read_u16(reader)?;
+                    let mut consumed = 16;
+                    if format_tag == WAVE_FORMAT_EXTENSIBLE && len >= 40 {
+                        let _extension_len = read_u16(reader)?;
+                        let _valid_bits = read_u16(reader)?;
+                        let _channel_mask = read_u32(reader)?;
+                        // first two bytes of sub format GUID carry the actual format tag
+                        format_tag = read_u16(reader)?;
+                        consumed += 10;
+                    }
+                    skip(reader, u64::from(len - consumed) + u64::from(len % 2))?;
+
+                    let sample_format =
+                        SampleFormat::from_wav_format_tag(format_tag, bits_per_sample)
+                            .ok_or_else(|| unsupported_format(format_tag, bits_per_sample))?;
+                    if channels == 0 || samples_per_second == 0 {
+                        return Err(invalid_header("zero channels or sample rate"));
+                    }
+                    format = Some((sample_format, samples_per_second, channels));
+                }
+                b"data" => {
+                    let (sample_format, samples_per_second, channels) =
+                        format.ok_or_else(|| invalid_header("data chunk precedes fmt chunk"))?;
+                    let header = WavHeader {
+                        sample_format,
+                        samples_per_second,
+                        channels,
+                        data_len: len,
+                    };
+                    if header.byte_rate().is_none() {
+                        return Err(invalid_header("block align or byte rate out of range"));
+                    }
+                    return Ok(header);
+                }
+                // LIST, fact, cue etc.
+                _ => skip(reader, u64::from(len) + u64::from(len % 2))?,
+            }
+        }
+    }
+
+    /// Writes canonical RIFF header followed by the header of data chunk.
+    /// Audio data are expected to be written right after.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        // non-PCM formats carry (empty) extension size
+        let fmt_len: u32 = if self.sample_format.wav_format_tag() == 1 {
+            16
+        } else {
+            18
+        };
+        let (block_align, byte_rate) = match (self.block_align(), self.byte_rate()) {
+            (Some(block_align), Some(byte_rate)) => (block_align, byte_rate),
+            _ => {
+                return Err(Error::new(
+                    "WavHeader.write_to error: block align or byte rate out of range".to_owned(),
+                    ErrorRootCause::ApiError(SpxError::InvalidArg),
+                ))
+            }
+        };
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(
+            &(4 + 8 + fmt_len + 8)
+                .saturating_add(self.data_len)
+                .to_le_bytes(),
+        )?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&fmt_len.to_le_bytes())?;
+        writer.write_all(&self.sample_format.wav_format_tag().to_le_bytes())?;
+        writer.write_all(&self.channels.to_le_bytes())?;
+        writer.write_all(&self.samples_per_second.to_le_bytes())?;
+        writer.write_all(&byte_rate.to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        writer.write_all(&self.sample_format.bits_per_sample().to_le_bytes())?;
+        if fmt_len == 18 {
+            writer.write_all(&0u16.to_le_bytes())?;
+        }
+        writer.write_all(b"data")?;
+        writer.write_all(&self.data_len.to_le_bytes())?;
+        Ok(())
+    }
+}
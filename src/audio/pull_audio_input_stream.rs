@@ -1,4 +1,5 @@
-use crate::audio::{AudioInputStream, AudioStreamFormat};
+use crate::audio::{AudioInputStream, AudioStreamFormat, ChunkMetadata, ChunkMetadataTimeline};
+use crate::common::{catch_callback_panic, PropertyId};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
     audio_stream_create_pull_audio_input_stream, audio_stream_release,
@@ -11,6 +12,7 @@ use std::ffi::CString;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_int, c_void};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 /// This trait that must be implemented by callback struct
 /// passed into  pull audio input stream during initialization.
@@ -18,6 +20,8 @@ use std::os::raw::{c_char, c_int, c_void};
 /// When Speech recognizer is ready to process more data it
 /// will call **read** method. <br/>Structs implementing
 /// **PullAudioInputStreamCallbacks** must also implement **Send** trait.<br/>
+/// Panic in any method is caught and reported via **common::on_callback_panic**,
+/// panicking **read** ends the stream (0 bytes read).<br/>
 /// To see how to use see example: **recognizer/continuous_recognition_pull_stream**.
 pub trait PullAudioInputStreamCallbacks: Send {
     /// Reads (pulls) data from this audio stream instance
@@ -39,11 +43,40 @@ pub trait PullAudioInputStreamCallbacks: Send {
     /// **ConversationTranscribingService_DataBufferUserId (11002)**<br/>
     /// For mor details see:
     /// [PropertyId Enum definition](https://docs.microsoft.com/en-us/dotnet/api/microsoft.cognitiveservices.speech.propertyid?view=azure-dotnet).
-    fn get_property(&mut self, id: i32) -> Result<String>;
+    /// Default implementation returns values from **chunk_metadata**.
+    fn get_property(&mut self, id: i32) -> Result<String> {
+        let metadata = self.chunk_metadata();
+        let value = if id == PropertyId::DataBufferTimeStamp.to_i32() {
+            metadata.property_value(PropertyId::DataBufferTimeStamp)
+        } else if id == PropertyId::DataBufferUserID.to_i32() {
+            metadata.property_value(PropertyId::DataBufferUserID)
+        } else {
+            None
+        };
+        Ok(value.unwrap_or_default())
+    }
+
+    /// Returns metadata of the most recently read chunk, called after each **read**
+    /// and by default **get_property**. Metadata is attached to recognition results
+    /// (see **SpeechRecognitionResult::chunk_metadata**).
+    fn chunk_metadata(&mut self) -> ChunkMetadata {
+        ChunkMetadata::default()
+    }
 }
 
+/// Callbacks are locked for the duration of native call so that
+/// they can be replaced while stream is being used.
 struct CallbackBag {
-    callbacks: Option<Box<dyn PullAudioInputStreamCallbacks>>,
+    callbacks: Mutex<Option<Box<dyn PullAudioInputStreamCallbacks>>>,
+    chunk_metadata: Option<ChunkMetadataTimeline>,
+}
+
+impl CallbackBag {
+    fn callbacks(&self) -> MutexGuard<'_, Option<Box<dyn PullAudioInputStreamCallbacks>>> {
+        self.callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 /// PullAudioInputStream represents audio input stream with audio data pulled (read) by Speech Recognizer when needed via *read* method.
@@ -65,6 +98,10 @@ impl AudioInputStream for PullAudioInputStream {
     fn get_handle(&self) -> SPXAUDIOSTREAMHANDLE {
         self.handle.inner()
     }
+
+    fn chunk_metadata_timeline(&self) -> Option<ChunkMetadataTimeline> {
+        self.callback_bag.chunk_metadata.clone()
+    }
 }
 
 impl PullAudioInputStream {
@@ -83,7 +120,10 @@ impl PullAudioInputStream {
                     handle.assume_init(),
                     audio_stream_release,
                 ),
-                callback_bag: Box::new(CallbackBag { callbacks: None }),
+                callback_bag: Box::new(CallbackBag {
+                    callbacks: Mutex::new(None),
+                    chunk_metadata: ChunkMetadataTimeline::for_format(format),
+                }),
             })
         }
     }
@@ -101,7 +141,7 @@ impl PullAudioInputStream {
         callbacks: Box<dyn PullAudioInputStreamCallbacks>,
         register_get_prop_cb: bool,
     ) -> Result<()> {
-        self.callback_bag.callbacks = Some(callbacks);
+        *self.callback_bag.callbacks() = Some(callbacks);
         unsafe {
             let mut ret = pull_audio_input_stream_set_callbacks(
                 self.handle.inner(),
@@ -133,33 +173,42 @@ impl PullAudioInputStream {
     #[allow(non_snake_case)]
     #[allow(unused_variables)]
     unsafe extern "C" fn cb_read(pvContext: *mut c_void, buffer: *mut u8, size: u32) -> c_int {
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(callbacks) = &mut callback_bag.callbacks {
-            let converted_size = usize::try_from(size);
-            if let Err(conv_err) = converted_size {
-                error!(
-                    "PullAudioInputStream::cb_read errror when converting size to usize: {}",
-                    conv_err
-                );
-                0 // return 0 as we did not read anything
+        catch_callback_panic("PullAudioInputStream::cb_read", 0, || {
+            let callback_bag = &*(pvContext as *const CallbackBag);
+            if let Some(callbacks) = callback_bag.callbacks().as_mut() {
+                let converted_size = usize::try_from(size);
+                if let Err(conv_err) = converted_size {
+                    error!(
+                        "PullAudioInputStream::cb_read errror when converting size to usize: {}",
+                        conv_err
+                    );
+                    0 // return 0 as we did not read anything
+                } else {
+                    let slice_buffer =
+                        std::slice::from_raw_parts_mut(buffer, converted_size.unwrap());
+                    let bytes_read = callbacks.read(slice_buffer);
+                    if let Some(chunk_metadata) = &callback_bag.chunk_metadata {
+                        let metadata = callbacks.chunk_metadata();
+                        chunk_metadata.record(bytes_read as usize, Some(&metadata));
+                    }
+                    bytes_read as i32
+                }
             } else {
-                let slice_buffer = std::slice::from_raw_parts_mut(buffer, converted_size.unwrap());
-                let bytes_read = callbacks.read(slice_buffer);
-                bytes_read as i32
+                error!("PullAudioInputStream::cb_read callbacks not defined");
+                0 // return 0 as we did not read anything
             }
-        } else {
-            error!("PullAudioInputStream::cb_read callbacks not defined");
-            0 // return 0 as we did not read anything
-        }
+        })
     }
 
     #[allow(non_snake_case)]
     #[allow(unused_variables)]
     unsafe extern "C" fn cb_close(pvContext: *mut c_void) {
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(callbacks) = &mut callback_bag.callbacks {
-            callbacks.close();
-        }
+        catch_callback_panic("PullAudioInputStream::cb_close", (), || {
+            let callback_bag = &*(pvContext as *const CallbackBag);
+            if let Some(callbacks) = callback_bag.callbacks().as_mut() {
+                callbacks.close();
+            }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -170,44 +219,46 @@ impl PullAudioInputStream {
         value: *mut u8,
         size: u32,
     ) {
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(callbacks) = &mut callback_bag.callbacks {
-            let converted_size = usize::try_from(size);
-            if let Err(conv_err) = converted_size {
-                error!("PullAudioInputStream::cb_get_property errror when converting size to usize: {}", conv_err);
-                return;
-            }
-            let converted_size = converted_size.unwrap();
-
-            match callbacks.get_property(id) {
-                Ok(prop_value) => match CString::new(prop_value) {
-                    Ok(c_prop_value) => {
-                        let c_prop_value_bytes_count = c_prop_value.as_bytes().len();
-                        let bytes_count_to_copy = if c_prop_value_bytes_count < converted_size {
-                            c_prop_value_bytes_count
-                        } else {
-                            converted_size
-                        };
-                        std::ptr::copy_nonoverlapping(
-                            c_prop_value.as_ptr(),
-                            value as *mut c_char,
-                            bytes_count_to_copy,
-                        );
-                    }
-                    Err(cstr_err) => {
+        catch_callback_panic("PullAudioInputStream::cb_get_property", (), || {
+            let callback_bag = &*(pvContext as *const CallbackBag);
+            if let Some(callbacks) = callback_bag.callbacks().as_mut() {
+                let converted_size = usize::try_from(size);
+                if let Err(conv_err) = converted_size {
+                    error!("PullAudioInputStream::cb_get_property errror when converting size to usize: {}", conv_err);
+                    return;
+                }
+                let converted_size = converted_size.unwrap();
+
+                match callbacks.get_property(id) {
+                    Ok(prop_value) => match CString::new(prop_value) {
+                        Ok(c_prop_value) => {
+                            let c_prop_value_bytes_count = c_prop_value.as_bytes().len();
+                            let bytes_count_to_copy = if c_prop_value_bytes_count < converted_size {
+                                c_prop_value_bytes_count
+                            } else {
+                                converted_size
+                            };
+                            std::ptr::copy_nonoverlapping(
+                                c_prop_value.as_ptr(),
+                                value as *mut c_char,
+                                bytes_count_to_copy,
+                            );
+                        }
+                        Err(cstr_err) => {
+                            error!(
+                                "PullAudioInputStream.cb_get_property error(CString::new): {:?}",
+                                cstr_err
+                            );
+                        }
+                    },
+                    Err(get_prop_err) => {
                         error!(
-                            "PullAudioInputStream.cb_get_property error(CString::new): {:?}",
-                            cstr_err
+                            "PullAudioInputStream.cb_get_property error(callbacks.get_property): {:?}",
+                            get_prop_err
                         );
                     }
-                },
-                Err(get_prop_err) => {
-                    error!(
-                        "PullAudioInputStream.cb_get_property error(callbacks.get_property): {:?}",
-                        get_prop_err
-                    );
                 }
             }
-        }
+        })
     }
 }
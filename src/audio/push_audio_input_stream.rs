@@ -1,4 +1,4 @@
-use crate::audio::{AudioInputStream, AudioStreamFormat};
+use crate::audio::{AudioInputStream, AudioStreamFormat, ChunkMetadata, ChunkMetadataTimeline};
 use crate::common::PropertyId;
 use crate::error::{convert_err, Result};
 use crate::ffi::{
@@ -16,12 +16,19 @@ use std::mem::MaybeUninit;
 #[derive(Debug)]
 pub struct PushAudioInputStream {
     pub handle: SmartHandle<SPXAUDIOSTREAMHANDLE>,
+    chunk_metadata: Option<ChunkMetadataTimeline>,
+    // metadata properties were set by write_with_metadata and must be cleared by next write
+    metadata_set: bool,
 }
 
 impl AudioInputStream for PushAudioInputStream {
     fn get_handle(&self) -> SPXAUDIOSTREAMHANDLE {
         self.handle.inner()
     }
+
+    fn chunk_metadata_timeline(&self) -> Option<ChunkMetadataTimeline> {
+        self.chunk_metadata.clone()
+    }
 }
 
 impl PushAudioInputStream {
@@ -45,6 +52,8 @@ impl PushAudioInputStream {
                     handle.assume_init(),
                     audio_stream_release,
                 ),
+                chunk_metadata: ChunkMetadataTimeline::for_format(&format),
+                metadata_set: false,
             })
         }
     }
@@ -70,19 +79,54 @@ impl PushAudioInputStream {
                     handle.assume_init(),
                     audio_stream_release,
                 ),
+                chunk_metadata: ChunkMetadataTimeline::for_format(&default_format),
+                metadata_set: false,
             })
         }
     }
 
     // impl to use static dispatch, see https://joshleeb.com/posts/rust-traits-and-trait-objects/
     pub fn write(&mut self, buffer: impl AsRef<[u8]>) -> Result<()> {
+        if self.metadata_set {
+            self.set_metadata_properties(&ChunkMetadata::default())?;
+        }
+        self.write_chunk(buffer.as_ref(), None)
+    }
+
+    /// Writes audio with *DataBufferTimeStamp* and *DataBufferUserID* properties applied
+    /// to this buffer only. Missing values are cleared so that metadata of previous
+    /// buffer is not carried over, subsequent *write* clears them as well. Metadata
+    /// is attached to recognition results (see *SpeechRecognitionResult::chunk_metadata*).
+    pub fn write_with_metadata(
+        &mut self,
+        buffer: impl AsRef<[u8]>,
+        metadata: &ChunkMetadata,
+    ) -> Result<()> {
+        self.set_metadata_properties(metadata)?;
+        self.write_chunk(buffer.as_ref(), Some(metadata))
+    }
+
+    fn set_metadata_properties(&mut self, metadata: &ChunkMetadata) -> Result<()> {
+        for id in [
+            PropertyId::DataBufferTimeStamp,
+            PropertyId::DataBufferUserID,
+        ] {
+            self.set_property(id, metadata.property_value(id).unwrap_or_default())?;
+        }
+        self.metadata_set = !metadata.is_empty();
+        Ok(())
+    }
+
+    fn write_chunk(&mut self, buf: &[u8], metadata: Option<&ChunkMetadata>) -> Result<()> {
         unsafe {
-            let buf = buffer.as_ref();
             let ptr = buf.as_ptr() as *mut u8;
             let ret = push_audio_input_stream_write(self.handle.inner(), ptr, buf.len() as u32);
             convert_err(ret, "PushAudioInputStream.write error")?;
-            Ok(())
         }
+        if let Some(chunk_metadata) = &self.chunk_metadata {
+            chunk_metadata.record(buf.len(), metadata);
+        }
+        Ok(())
     }
 
     pub fn close_stream(&self) -> Result<()> {
@@ -1,4 +1,5 @@
 use crate::audio::AudioOutputStream;
+use crate::common::catch_callback_panic;
 use crate::error::{convert_err, Result};
 use crate::ffi::{
     audio_stream_create_push_audio_output_stream, audio_stream_release,
@@ -9,6 +10,7 @@ use std::convert::TryFrom;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::os::raw::{c_int, c_void};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 /// This trait that must be implemented by callback struct
 /// passed into  push audio output stream during initialization.
@@ -16,6 +18,7 @@ use std::os::raw::{c_int, c_void};
 /// When Synthetizer has syntehtized data it
 /// will call **write** method. <br/>Structs implementing
 /// **PushAudioOutputStreamCallbacks** must also implement **Send** trait.<br/>
+/// Panic in any method is caught and reported via **common::on_callback_panic**.<br/>
 /// To see how to use see example: **synthesizer/speak_ssml_async**.
 pub trait PushAudioOutputStreamCallbacks: Send {
     /// called by synthetizer when new data are synthetized
@@ -29,8 +32,18 @@ pub trait PushAudioOutputStreamCallbacks: Send {
     fn close(&mut self);
 }
 
+/// Callbacks are locked for the duration of native call so that
+/// they can be replaced while stream is being used.
 struct CallbackBag {
-    callbacks: Option<Box<dyn PushAudioOutputStreamCallbacks>>,
+    callbacks: Mutex<Option<Box<dyn PushAudioOutputStreamCallbacks>>>,
+}
+
+impl CallbackBag {
+    fn callbacks(&self) -> MutexGuard<'_, Option<Box<dyn PushAudioOutputStreamCallbacks>>> {
+        self.callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 /// PushAudioOutputStream represents audio output stream with audio data pushed by Speech Synthetizer via *write* method.
@@ -60,7 +73,9 @@ impl PushAudioOutputStream {
     pub unsafe fn from_handle(handle: SPXAUDIOSTREAMHANDLE) -> Result<Self> {
         Ok(PushAudioOutputStream {
             handle: SmartHandle::create("PushAudioOutputStream", handle, audio_stream_release),
-            callback_bag: Box::new(CallbackBag { callbacks: None }),
+            callback_bag: Box::new(CallbackBag {
+                callbacks: Mutex::new(None),
+            }),
         })
     }
 
@@ -78,7 +93,7 @@ impl PushAudioOutputStream {
         &mut self,
         callbacks: Box<dyn PushAudioOutputStreamCallbacks>,
     ) -> Result<()> {
-        self.callback_bag.callbacks = Some(callbacks);
+        *self.callback_bag.callbacks() = Some(callbacks);
         unsafe {
             let ret = push_audio_output_stream_set_callbacks(
                 self.handle.inner(),
@@ -94,32 +109,37 @@ impl PushAudioOutputStream {
     #[allow(non_snake_case)]
     #[allow(unused_variables)]
     unsafe extern "C" fn cb_write(pvContext: *mut c_void, buffer: *mut u8, size: u32) -> c_int {
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(callbacks) = &mut callback_bag.callbacks {
-            let converted_size = usize::try_from(size);
-            if let Err(conv_err) = converted_size {
-                error!(
-                    "PushAudioOutputStream::cb_write errror when converting size to usize: {}",
-                    conv_err
-                );
-                0 // return 0 as we did not write anything
+        catch_callback_panic("PushAudioOutputStream::cb_write", 0, || {
+            let callback_bag = &*(pvContext as *const CallbackBag);
+            if let Some(callbacks) = callback_bag.callbacks().as_mut() {
+                let converted_size = usize::try_from(size);
+                if let Err(conv_err) = converted_size {
+                    error!(
+                        "PushAudioOutputStream::cb_write errror when converting size to usize: {}",
+                        conv_err
+                    );
+                    0 // return 0 as we did not write anything
+                } else {
+                    let slice_buffer =
+                        std::slice::from_raw_parts_mut(buffer, converted_size.unwrap());
+                    let bytes_written = callbacks.write(slice_buffer);
+                    bytes_written as i32
+                }
             } else {
-                let slice_buffer = std::slice::from_raw_parts_mut(buffer, converted_size.unwrap());
-                let bytes_written = callbacks.write(slice_buffer);
-                bytes_written as i32
+                error!("PushAudioOutputStream::cb_write callbacks not defined");
+                0 // return 0 as we did not write anything
             }
-        } else {
-            error!("PushAudioOutputStream::cb_write callbacks not defined");
-            0 // return 0 as we did not write anything
-        }
+        })
     }
 
     #[allow(non_snake_case)]
     #[allow(unused_variables)]
     unsafe extern "C" fn cb_close(pvContext: *mut c_void) {
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(callbacks) = &mut callback_bag.callbacks {
-            callbacks.close();
-        }
+        catch_callback_panic("PushAudioOutputStream::cb_close", (), || {
+            let callback_bag = &*(pvContext as *const CallbackBag);
+            if let Some(callbacks) = callback_bag.callbacks().as_mut() {
+                callbacks.close();
+            }
+        })
     }
 }
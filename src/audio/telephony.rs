@@ -0,0 +1,15 @@
+//! Package telephony provides ingestion of telephony audio received as RTP carrying
+//! G.711 (PCMU/PCMA, 8 kHz) payload. Packets are reordered in small jitter buffer,
+//! gaps are filled with silence and payload is pushed into *PushAudioInputStream*
+//! without transcoding (see *AudioStreamFormat::from_waveformat*).
+//! Requires *telephony* feature.
+mod g711_codec;
+mod rtp_depacketizer;
+mod rtp_ingest;
+mod rtp_packet;
+
+// re-export structs directly under telephony module
+pub use self::g711_codec::G711Codec;
+pub use self::rtp_depacketizer::{RtpDepacketizer, RtpStats};
+pub use self::rtp_ingest::RtpIngest;
+pub use self::rtp_packet::RtpPacket;
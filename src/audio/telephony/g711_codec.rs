@@ -0,0 +1,60 @@
+use crate::audio::pcm::{alaw_to_i16, mulaw_to_i16};
+use crate::audio::{AudioStreamFormat, WaveFormat};
+use crate::error::Result;
+
+/// Sample rate of G.711 audio and of its RTP clock.
+pub(crate) const G711_SAMPLES_PER_SECOND: u32 = 8000;
+
+/// G711Codec defines G.711 variants carried in RTP with static payload types (RFC 3551).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum G711Codec {
+    /// μ-law, RTP payload type 0.
+    Pcmu,
+    /// A-law, RTP payload type 8.
+    Pcma,
+}
+
+impl G711Codec {
+    pub fn from_payload_type(payload_type: u8) -> Option<Self> {
+        match payload_type {
+            0 => Some(G711Codec::Pcmu),
+            8 => Some(G711Codec::Pcma),
+            _ => None,
+        }
+    }
+
+    pub fn payload_type(&self) -> u8 {
+        match self {
+            G711Codec::Pcmu => 0,
+            G711Codec::Pcma => 8,
+        }
+    }
+
+    pub fn wave_format(&self) -> WaveFormat {
+        match self {
+            G711Codec::Pcmu => WaveFormat::Mulaw,
+            G711Codec::Pcma => WaveFormat::Alaw,
+        }
+    }
+
+    /// Encoded value of silent sample, used to fill gaps in the stream.
+    pub fn silence(&self) -> u8 {
+        match self {
+            G711Codec::Pcmu => 0xFF,
+            G711Codec::Pcma => 0xD5,
+        }
+    }
+
+    /// Returns 8 kHz, 8 bit, mono stream format matching the codec.
+    pub fn stream_format(&self) -> Result<AudioStreamFormat> {
+        AudioStreamFormat::from_waveformat(G711_SAMPLES_PER_SECOND, 8, 1, self.wave_format())
+    }
+
+    /// Decodes payload into 16-bit linear PCM, e.g. for local processing of the audio.
+    pub fn decode(&self, payload: &[u8]) -> Vec<i16> {
+        match self {
+            G711Codec::Pcmu => payload.iter().map(|s| mulaw_to_i16(*s)).collect(),
+            G711Codec::Pcma => payload.iter().map(|s| alaw_to_i16(*s)).collect(),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use super::g711_codec::G711_SAMPLES_PER_SECOND;
+use crate::audio::telephony::{G711Codec, RtpPacket};
+use crate::error::Result;
+use log::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// Default jitter buffer depth, 5 packets of 20 ms.
+const DEFAULT_JITTER_DEPTH: usize = 5;
+/// Gaps longer than this are not filled with silence (e.g. RTP timestamp jump).
+const DEFAULT_MAX_GAP: Duration = Duration::from_secs(1);
+/// Maximal UDP datagram size.
+const MAX_DATAGRAM_SIZE: usize = 65536;
+/// Maximal number of kept timestamp anchors used for offset mapping.
+const MAX_ANCHORS: usize = 64;
+/// Recognition offsets are in ticks of 100 nanoseconds.
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// RtpStats counts packets processed by *RtpDepacketizer*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RtpStats {
+    /// Packets accepted into jitter buffer.
+    pub received: u64,
+    /// Packets arriving after later packets were already released.
+    pub late: u64,
+    pub duplicates: u64,
+    /// Packets never received, derived from sequence numbers.
+    pub lost: u64,
+    /// Packets not parseable as RTP.
+    pub invalid: u64,
+    /// Packets with payload type other than the codec (e.g. DTMF or comfort noise).
+    pub unexpected_payload_type: u64,
+    pub ssrc_changes: u64,
+    /// Number of silent samples inserted into gaps.
+    pub silence_samples: u64,
+}
+
+/// Maps RTP timestamps of given source onto samples written into the stream.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    ssrc: u32,
+    timestamp: u32,
+    sample: u64,
+}
+
+/// RtpDepacketizer turns RTP packets carrying G.711 into continuous audio.
+/// Packets are reordered in jitter buffer of given depth, late and duplicate
+/// packets are dropped and gaps (packet loss, silence suppression) are filled
+/// with silence. When SSRC changes (e.g. call transfer) buffered packets
+/// of previous source are released and the new source continues the stream.
+#[derive(Debug)]
+pub struct RtpDepacketizer {
+    codec: G711Codec,
+    depth: usize,
+    max_gap_samples: u32,
+    buffer: BTreeMap<u64, RtpPacket>,
+    ssrc: Option<u32>,
+    highest_sequence: Option<u64>,
+    next_sequence: Option<u64>,
+    next_timestamp: Option<u32>,
+    samples: u64,
+    anchors: VecDeque<Anchor>,
+    stats: RtpStats,
+}
+
+impl RtpDepacketizer {
+    pub fn new(codec: G711Codec) -> Self {
+        RtpDepacketizer {
+            codec,
+            depth: DEFAULT_JITTER_DEPTH,
+            max_gap_samples: duration_to_samples(DEFAULT_MAX_GAP),
+            buffer: BTreeMap::new(),
+            ssrc: None,
+            highest_sequence: None,
+            next_sequence: None,
+            next_timestamp: None,
+            samples: 0,
+            anchors: VecDeque::new(),
+            stats: RtpStats::default(),
+        }
+    }
+
+    /// Sets number of packets held in jitter buffer before being released.
+    /// Zero releases packets immediately, i.e. only duplicates and late packets are dropped.
+    pub fn with_jitter_depth(mut self, packets: usize) -> Self {
+        self.depth = packets;
+        self
+    }
+
+    /// Sets longest gap filled with silence. Longer gaps are skipped.
+    pub fn with_max_gap(mut self, max_gap: Duration) -> Self {
+        self.max_gap_samples = duration_to_samples(max_gap);
+        self
+    }
+
+    pub fn codec(&self) -> G711Codec {
+        self.codec
+    }
+
+    pub fn stats(&self) -> &RtpStats {
+        &self.stats
+    }
+
+    /// Number of samples released so far, including inserted silence.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Parses raw RTP packet and returns audio released from jitter buffer (possibly empty).
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        match RtpPacket::parse(data) {
+            Ok(packet) => Ok(self.push_packet(packet)),
+            Err(err) => {
+                self.stats.invalid += 1;
+                Err(err)
+            }
+        }
+    }
+
+    /// Adds packet into jitter buffer and returns audio released from jitter buffer (possibly empty).
+    pub fn push_packet(&mut self, packet: RtpPacket) -> Vec<u8> {
+        if packet.payload_type != self.codec.payload_type() {
+            trace!(
+                "RtpDepacketizer: skipping payload type {}",
+                packet.payload_type
+            );
+            self.stats.unexpected_payload_type += 1;
+            return vec![];
+        }
+
+        let mut audio = vec![];
+        if self.ssrc != Some(packet.ssrc) {
+            if let Some(ssrc) = self.ssrc {
+                debug!("RtpDepacketizer: ssrc changed {} -> {}", ssrc, packet.ssrc);
+                self.stats.ssrc_changes += 1;
+                audio = self.flush();
+            }
+            self.ssrc = Some(packet.ssrc);
+        }
+
+        let sequence = self.extend_sequence(packet.sequence_number);
+        if self.next_sequence.is_some_and(|next| sequence < next) {
+            self.stats.late += 1;
+            return audio;
+        }
+        if self.buffer.contains_key(&sequence) {
+            self.stats.duplicates += 1;
+            return audio;
+        }
+        self.stats.received += 1;
+        self.buffer.insert(sequence, packet);
+        while self.buffer.len() > self.depth {
+            self.release_first(&mut audio);
+        }
+        audio
+    }
+
+    /// Releases all buffered packets, e.g. at the end of the call. Sequence and timestamp
+    /// tracking is reset, next packet starts new segment of the stream.
+    pub fn flush(&mut self) -> Vec<u8> {
+        let mut audio = vec![];
+        while !self.buffer.is_empty() {
+            self.release_first(&mut audio);
+        }
+        self.highest_sequence = None;
+        self.next_sequence = None;
+        self.next_timestamp = None;
+        audio
+    }
+
+    /// Receives single datagram from *socket* and returns audio released from jitter buffer
+    /// (possibly empty). Datagrams which are not valid RTP packets are counted and skipped.
+    pub async fn recv_from(&mut self, socket: &UdpSocket) -> Result<Vec<u8>> {
+        let mut datagram = vec![0u8; MAX_DATAGRAM_SIZE];
+        let (len, peer) = socket.recv_from(&mut datagram).await?;
+        match self.push(&datagram[..len]) {
+            Ok(audio) => Ok(audio),
+            Err(err) => {
+                warn!("RtpDepacketizer: invalid packet from {}: {}", peer, err);
+                Ok(vec![])
+            }
+        }
+    }
+
+    /// Maps RTP timestamp of given source onto recognition offset (in ticks of 100 ns
+    /// from the start of the stream), so that recognition results can be correlated with media.
+    /// Returns None for unknown source or timestamp preceding its first released packet.
+    pub fn offset_for_timestamp(&self, ssrc: u32, timestamp: u32) -> Option<u64> {
+        self.anchors
+            .iter()
+            .rev()
+            .filter(|anchor| anchor.ssrc == ssrc)
+            .find_map(|anchor| {
+                let delta = timestamp.wrapping_sub(anchor.timestamp);
+                if delta < 1 << 31 {
+                    Some(samples_to_ticks(anchor.sample + u64::from(delta)))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Unrolls 16-bit sequence number into monotonic one relative to highest seen number.
+    fn extend_sequence(&mut self, sequence_number: u16) -> u64 {
+        let extended = match self.highest_sequence {
+            // leave room for packets preceding the first one
+            None => (1 << 16) + u64::from(sequence_number),
+            Some(highest) => {
+                let delta = sequence_number.wrapping_sub(highest as u16) as i16;
+                (highest as i64 + i64::from(delta)) as u64
+            }
+        };
+        if self
+            .highest_sequence
+            .is_none_or(|highest| extended > highest)
+        {
+            self.highest_sequence = Some(extended);
+        }
+        extended
+    }
+
+    fn release_first(&mut self, audio: &mut Vec<u8>) {
+        let (sequence, packet) = match self.buffer.pop_first() {
+            Some(first) => first,
+            None => return,
+        };
+        if let Some(next) = self.next_sequence {
+            self.stats.lost += sequence - next;
+        }
+
+        match self.next_timestamp {
+            Some(expected) => {
+                let gap = packet.timestamp.wrapping_sub(expected);
+                if gap > self.max_gap_samples {
+                    // timestamp jump or overlap, continue stream from this packet
+                    debug!("RtpDepacketizer: timestamp discontinuity {}", gap as i32);
+                    self.anchor(&packet);
+                } else if gap > 0 {
+                    audio.resize(audio.len() + gap as usize, self.codec.silence());
+                    self.samples += u64::from(gap);
+                    self.stats.silence_samples += u64::from(gap);
+                }
+            }
+            None => self.anchor(&packet),
+        }
+
+        audio.extend_from_slice(&packet.payload);
+        self.samples += packet.payload.len() as u64;
+        self.next_sequence = Some(sequence + 1);
+        self.next_timestamp = Some(packet.timestamp.wrapping_add(packet.payload.len() as u32));
+    }
+
+    fn anchor(&mut self, packet: &RtpPacket) {
+        if self.anchors.len() == MAX_ANCHORS {
+            self.anchors.pop_front();
+        }
+        self.anchors.push_back(Anchor {
+            ssrc: packet.ssrc,
+            timestamp: packet.timestamp,
+            sample: self.samples,
+        });
+    }
+}
+
+/// Converts duration into samples, capped so that overlaps (negative gaps) are never filled.
+fn duration_to_samples(duration: Duration) -> u32 {
+    let samples = duration.as_millis() * u128::from(G711_SAMPLES_PER_SECOND) / 1000;
+    samples.min(i32::MAX as u128) as u32
+}
+
+fn samples_to_ticks(samples: u64) -> u64 {
+    samples * (TICKS_PER_SECOND / u64::from(G711_SAMPLES_PER_SECOND))
+}
@@ -0,0 +1,74 @@
+use crate::audio::telephony::{G711Codec, RtpDepacketizer};
+use crate::audio::{AudioConfig, PushAudioInputStream};
+use crate::error::Result;
+use log::*;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+/// RtpIngest feeds G.711 audio received as RTP into *PushAudioInputStream*
+/// with matching stream format. Use *audio_config* to create recognizer consuming the stream.
+#[derive(Debug)]
+pub struct RtpIngest {
+    depacketizer: RtpDepacketizer,
+    stream: PushAudioInputStream,
+}
+
+impl RtpIngest {
+    pub fn new(codec: G711Codec) -> Result<RtpIngest> {
+        RtpIngest::from_depacketizer(RtpDepacketizer::new(codec))
+    }
+
+    /// Creates ingest using preconfigured depacketizer (jitter buffer depth, max gap).
+    pub fn from_depacketizer(depacketizer: RtpDepacketizer) -> Result<RtpIngest> {
+        let stream = PushAudioInputStream::create_push_stream_from_format(
+            depacketizer.codec().stream_format()?,
+        )?;
+        Ok(RtpIngest {
+            depacketizer,
+            stream,
+        })
+    }
+
+    pub fn audio_config(&self) -> Result<AudioConfig> {
+        AudioConfig::from_stream_input(&self.stream)
+    }
+
+    /// Depacketizer state, e.g. for statistics or mapping RTP timestamps onto recognition offsets.
+    pub fn depacketizer(&self) -> &RtpDepacketizer {
+        &self.depacketizer
+    }
+
+    /// Pushes raw RTP packet. Audio released from jitter buffer is written into the stream.
+    pub fn push(&mut self, data: &[u8]) -> Result<()> {
+        let audio = self.depacketizer.push(data)?;
+        self.write(audio)
+    }
+
+    /// Receives RTP packets from *socket* until no packet arrives for *idle_timeout*
+    /// (end of the call), then flushes jitter buffer and closes the stream.
+    pub async fn receive_udp(&mut self, socket: &UdpSocket, idle_timeout: Duration) -> Result<()> {
+        while let Ok(audio) = timeout(idle_timeout, self.depacketizer.recv_from(socket)).await {
+            self.write(audio?)?;
+        }
+        info!(
+            "RtpIngest: no packet received for {:?}, closing stream",
+            idle_timeout
+        );
+        self.finish()
+    }
+
+    /// Flushes jitter buffer and closes the stream.
+    pub fn finish(&mut self) -> Result<()> {
+        let audio = self.depacketizer.flush();
+        self.write(audio)?;
+        self.stream.close_stream()
+    }
+
+    fn write(&mut self, audio: Vec<u8>) -> Result<()> {
+        if audio.is_empty() {
+            return Ok(());
+        }
+        self.stream.write(audio)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+
+const RTP_VERSION: u8 = 2;
+const RTP_HEADER_LEN: usize = 12;
+
+/// RtpPacket is parsed RTP packet (RFC 3550). CSRC list, header extension
+/// and padding are skipped, only fields needed for audio ingestion are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtpPacket {
+    pub marker: bool,
+    pub payload_type: u8,
+    pub sequence_number: u16,
+    pub timestamp: u32,
+    pub ssrc: u32,
+    pub payload: Vec<u8>,
+}
+
+fn invalid_packet(details: &str) -> Error {
+    Error::new(
+        format!("RtpPacket::parse error: {}", details),
+        ErrorRootCause::ApiError(SpxError::InvalidArg),
+    )
+}
+
+impl RtpPacket {
+    pub fn parse(data: &[u8]) -> Result<RtpPacket> {
+        if data.len() < RTP_HEADER_LEN {
+            return Err(invalid_packet("packet shorter than RTP header"));
+        }
+        if data[0] >> 6 != RTP_VERSION {
+            return Err(invalid_packet("unsupported RTP version"));
+        }
+        let padding = data[0] & 0x20 != 0;
+        let extension = data[0] & 0x10 != 0;
+        let csrc_count = usize::from(data[0] & 0x0F);
+
+        let mut start = RTP_HEADER_LEN + 4 * csrc_count;
+        if extension {
+            if data.len() < start + 4 {
+                return Err(invalid_packet("truncated header extension"));
+            }
+            let words = usize::from(u16::from_be_bytes([data[start + 2], data[start + 3]]));
+            start += 4 + 4 * words;
+        }
+        let mut end = data.len();
+        if padding {
+            end = end.saturating_sub(usize::from(data[data.len() - 1]));
+        }
+        if start > end {
+            return Err(invalid_packet("header or padding exceeds packet length"));
+        }
+
+        Ok(RtpPacket {
+            marker: data[1] & 0x80 != 0,
+            payload_type: data[1] & 0x7F,
+            sequence_number: u16::from_be_bytes([data[2], data[3]]),
+            timestamp: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            ssrc: u32::from_be_bytes([data[8], data[9], data[10], data[11]]),
+            payload: data[start..end].to_vec(),
+        })
+    }
+
+    /// Serializes packet with minimal header (no CSRC, extension or padding).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(RTP_HEADER_LEN + self.payload.len());
+        data.push(RTP_VERSION << 6);
+        data.push(u8::from(self.marker) << 7 | (self.payload_type & 0x7F));
+        data.extend_from_slice(&self.sequence_number.to_be_bytes());
+        data.extend_from_slice(&self.timestamp.to_be_bytes());
+        data.extend_from_slice(&self.ssrc.to_be_bytes());
+        data.extend_from_slice(&self.payload);
+        data
+    }
+}
@@ -0,0 +1,10 @@
+//! Package vad provides pure-Rust voice activity detection placed between audio producer
+//! and *PushAudioInputStream*. It emits local speech start/end hints and can suspend
+//! writes during long silences so that silent audio is not sent to the service.
+//! Audio is expected as 16-bit little endian mono PCM (see *pcm* package for conversion).
+mod vad_gate;
+mod voice_activity_detector;
+
+// re-export structs directly under vad module
+pub use self::vad_gate::{VadEvent, VadGate, VadOutput};
+pub use self::voice_activity_detector::{VadConfig, VoiceActivityDetector};
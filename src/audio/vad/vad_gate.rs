@@ -0,0 +1,225 @@
+use crate::audio::vad::{VadConfig, VoiceActivityDetector};
+use crate::audio::PushAudioInputStream;
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use log::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Recognition offsets are in ticks of 100 nanoseconds.
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// VadEvent is local hint produced by *VadGate*. Offsets are in ticks of 100 ns
+/// of the audio passed to the gate, i.e. including suspended silence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    SpeechStart { offset: u64 },
+    SpeechEnd { offset: u64 },
+}
+
+/// VadOutput is result of processing chunk of audio by *VadGate*.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VadOutput {
+    /// Audio to be written into the stream (empty while writes are suspended).
+    pub audio: Vec<u8>,
+    pub events: Vec<VadEvent>,
+}
+
+/// Maps position in written stream onto position in audio passed to the gate.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    stream_sample: u64,
+    source_sample: u64,
+}
+
+/// VadGate sits between audio producer and *PushAudioInputStream*. It detects speech
+/// in 16-bit mono PCM, emits *VadEvent*s and, if *VadConfig::suspend_after* is set,
+/// stops passing audio during long silences. Leading hangover audio is kept while
+/// suspended and written when speech starts, so the beginning of the phrase is not lost.
+/// Service offsets can be mapped back onto producer timeline via *source_offset*.
+#[derive(Debug, Clone)]
+pub struct VadGate {
+    detector: VoiceActivityDetector,
+    samples_per_second: u32,
+    frame_len: usize,
+    min_speech_frames: usize,
+    trailing_frames: usize,
+    preroll_frames: usize,
+    suspend_after_samples: Option<u64>,
+    pending: Vec<u8>,
+    speech: bool,
+    voiced_run: usize,
+    unvoiced_run: usize,
+    silence_samples: u64,
+    suspended: bool,
+    preroll: VecDeque<Vec<u8>>,
+    source_samples: u64,
+    stream_samples: u64,
+    anchors: Vec<Anchor>,
+}
+
+impl VadGate {
+    pub fn new(samples_per_second: u32, config: VadConfig) -> Result<VadGate> {
+        let frame_len = duration_to_samples(config.frame_duration, samples_per_second) as usize;
+        if frame_len == 0 {
+            return Err(Error::new(
+                "VadGate::new error: sample rate and frame duration must be non-zero".to_owned(),
+                ErrorRootCause::ApiError(SpxError::InvalidArg),
+            ));
+        }
+        let frames = |duration: Duration| {
+            let samples = duration_to_samples(duration, samples_per_second) as usize;
+            samples.div_ceil(frame_len)
+        };
+        let min_speech_frames = frames(config.min_speech).max(1);
+        Ok(VadGate {
+            detector: VoiceActivityDetector::new(&config),
+            samples_per_second,
+            frame_len,
+            min_speech_frames,
+            trailing_frames: frames(config.trailing_hangover).max(1),
+            preroll_frames: frames(config.leading_hangover) + min_speech_frames,
+            suspend_after_samples: config
+                .suspend_after
+                .map(|duration| duration_to_samples(duration, samples_per_second)),
+            pending: vec![],
+            speech: false,
+            voiced_run: 0,
+            unvoiced_run: 0,
+            silence_samples: 0,
+            suspended: false,
+            preroll: VecDeque::new(),
+            source_samples: 0,
+            stream_samples: 0,
+            anchors: vec![],
+        })
+    }
+
+    pub fn is_speech(&self) -> bool {
+        self.speech
+    }
+
+    /// True while audio is not passed into the stream.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Processes next chunk of 16-bit little endian mono PCM. Chunks can be of any size,
+    /// incomplete frames are carried over.
+    pub fn process(&mut self, data: &[u8]) -> VadOutput {
+        let mut output = VadOutput::default();
+        let frame_bytes = self.frame_len * 2;
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(data);
+        let mut frames = pending.chunks_exact(frame_bytes);
+        for frame in &mut frames {
+            self.process_frame(frame, &mut output);
+        }
+        self.pending = frames.remainder().to_vec();
+        output
+    }
+
+    /// Processes next chunk of audio and writes audio passed by the gate into *stream*.
+    pub fn write(
+        &mut self,
+        data: &[u8],
+        stream: &mut PushAudioInputStream,
+    ) -> Result<Vec<VadEvent>> {
+        let output = self.process(data);
+        if !output.audio.is_empty() {
+            stream.write(&output.audio)?;
+        }
+        Ok(output.events)
+    }
+
+    /// Maps offset reported by the service (ticks of 100 ns of written stream)
+    /// onto offset in audio passed to the gate, i.e. adds duration of suspended silence.
+    pub fn source_offset(&self, stream_offset: u64) -> u64 {
+        self.anchors
+            .iter()
+            .rev()
+            .find(|anchor| self.ticks(anchor.stream_sample) <= stream_offset)
+            .map_or(stream_offset, |anchor| {
+                stream_offset + self.ticks(anchor.source_sample - anchor.stream_sample)
+            })
+    }
+
+    fn process_frame(&mut self, frame: &[u8], output: &mut VadOutput) {
+        let samples: Vec<i16> = frame
+            .chunks_exact(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect();
+        let voiced = self.detector.is_speech(&samples);
+        self.source_samples += self.frame_len as u64;
+
+        if self.speech {
+            self.emit(frame, output);
+            if voiced {
+                self.unvoiced_run = 0;
+            } else {
+                self.unvoiced_run += 1;
+                if self.unvoiced_run >= self.trailing_frames {
+                    let silence = (self.unvoiced_run * self.frame_len) as u64;
+                    output.events.push(VadEvent::SpeechEnd {
+                        offset: self.ticks(self.source_samples - silence),
+                    });
+                    self.speech = false;
+                    self.voiced_run = 0;
+                    self.silence_samples = silence;
+                }
+            }
+            return;
+        }
+
+        self.voiced_run = if voiced { self.voiced_run + 1 } else { 0 };
+        if self.suspended {
+            self.preroll.push_back(frame.to_vec());
+            if self.preroll.len() > self.preroll_frames {
+                self.preroll.pop_front();
+            }
+        } else {
+            self.emit(frame, output);
+            self.silence_samples += self.frame_len as u64;
+            if let Some(suspend_after) = self.suspend_after_samples {
+                if self.silence_samples >= suspend_after {
+                    debug!("VadGate: suspending writes");
+                    self.suspended = true;
+                }
+            }
+        }
+
+        if self.voiced_run >= self.min_speech_frames {
+            let speech = (self.voiced_run * self.frame_len) as u64;
+            output.events.push(VadEvent::SpeechStart {
+                offset: self.ticks(self.source_samples - speech),
+            });
+            self.speech = true;
+            self.unvoiced_run = 0;
+            self.silence_samples = 0;
+            if self.suspended {
+                debug!("VadGate: resuming writes");
+                let preroll = (self.preroll.len() * self.frame_len) as u64;
+                self.anchors.push(Anchor {
+                    stream_sample: self.stream_samples,
+                    source_sample: self.source_samples - preroll,
+                });
+                while let Some(frame) = self.preroll.pop_front() {
+                    self.emit(&frame, output);
+                }
+                self.suspended = false;
+            }
+        }
+    }
+
+    fn emit(&mut self, frame: &[u8], output: &mut VadOutput) {
+        output.audio.extend_from_slice(frame);
+        self.stream_samples += self.frame_len as u64;
+    }
+
+    fn ticks(&self, samples: u64) -> u64 {
+        samples * TICKS_PER_SECOND / u64::from(self.samples_per_second)
+    }
+}
+
+fn duration_to_samples(duration: Duration, samples_per_second: u32) -> u64 {
+    (duration.as_micros() * u128::from(samples_per_second) / 1_000_000) as u64
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+/// Rate at which noise floor follows louder non-speech frames, per frame.
+const NOISE_FLOOR_RISE: f32 = 0.01;
+/// Rate at which noise floor follows speech frames, per frame. Much slower so that
+/// steady speech is not taken for noise, persistent loud noise is still adopted
+/// (within tens of seconds).
+const NOISE_FLOOR_RISE_SPEECH: f32 = 0.001;
+
+/// VadConfig holds parameters of *VoiceActivityDetector* and *VadGate*.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadConfig {
+    /// Length of analysed frame, 10-30 ms.
+    pub frame_duration: Duration,
+    /// Frame is speech if its energy exceeds noise floor by this margin.
+    pub threshold_db: f32,
+    /// Frames quieter than this (dBFS) are never speech.
+    pub min_energy_db: f32,
+    /// Frames with higher zero-crossing rate (0-1) are considered noise unless
+    /// they are well above threshold.
+    pub max_zero_crossing_rate: f32,
+    /// Consecutive speech needed to emit speech start.
+    pub min_speech: Duration,
+    /// Audio preceding detected speech start which is kept (and written after suspension).
+    pub leading_hangover: Duration,
+    /// Silence needed to emit speech end.
+    pub trailing_hangover: Duration,
+    /// When set, writes are suspended after this much silence following speech end
+    /// (or since start). Should be long enough for the service to finalize the phrase.
+    pub suspend_after: Option<Duration>,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        VadConfig {
+            frame_duration: Duration::from_millis(20),
+            threshold_db: 9.0,
+            min_energy_db: -50.0,
+            max_zero_crossing_rate: 0.35,
+            min_speech: Duration::from_millis(60),
+            leading_hangover: Duration::from_millis(300),
+            trailing_hangover: Duration::from_millis(600),
+            suspend_after: None,
+        }
+    }
+}
+
+/// VoiceActivityDetector classifies frames of 16-bit PCM as speech or non-speech
+/// using frame energy relative to adaptive noise floor and zero-crossing rate.
+#[derive(Debug, Clone)]
+pub struct VoiceActivityDetector {
+    threshold_db: f32,
+    min_energy_db: f32,
+    max_zero_crossing_rate: f32,
+    noise_floor_db: Option<f32>,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(config: &VadConfig) -> Self {
+        VoiceActivityDetector {
+            threshold_db: config.threshold_db,
+            min_energy_db: config.min_energy_db,
+            max_zero_crossing_rate: config.max_zero_crossing_rate,
+            noise_floor_db: None,
+        }
+    }
+
+    /// Current noise floor estimate in dBFS.
+    pub fn noise_floor_db(&self) -> Option<f32> {
+        self.noise_floor_db
+    }
+
+    /// Classifies single frame and updates noise floor. Noise floor drops immediately
+    /// to quieter frames and rises slowly, so that steady noise is eventually ignored.
+    /// The rise is slowed down further while energy exceeds speech threshold.
+    pub fn is_speech(&mut self, frame: &[i16]) -> bool {
+        if frame.is_empty() {
+            return false;
+        }
+        let energy_db = energy_db(frame);
+        let floor = *self.noise_floor_db.get_or_insert(energy_db);
+        let threshold = (floor + self.threshold_db).max(self.min_energy_db);
+        let speech = energy_db > threshold
+            && (zero_crossing_rate(frame) <= self.max_zero_crossing_rate
+                || energy_db > threshold + self.threshold_db);
+
+        let rise = if energy_db > threshold {
+            NOISE_FLOOR_RISE_SPEECH
+        } else {
+            NOISE_FLOOR_RISE
+        };
+        self.noise_floor_db = Some(if energy_db < floor {
+            energy_db
+        } else {
+            floor + (energy_db - floor) * rise
+        });
+        speech
+    }
+}
+
+/// RMS energy of the frame in dBFS.
+fn energy_db(frame: &[i16]) -> f32 {
+    let sum: f64 = frame
+        .iter()
+        .map(|s| {
+            let s = f64::from(*s) / 32768.0;
+            s * s
+        })
+        .sum();
+    let rms = (sum / frame.len() as f64).sqrt();
+    (20.0 * rms.max(1e-9).log10()) as f32
+}
+
+fn zero_crossing_rate(frame: &[i16]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0) != (pair[1] >= 0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
@@ -0,0 +1,39 @@
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use std::convert::TryFrom;
+
+/// WaveFormat defines supported audio stream wave formats in WAV container,
+/// see *AudioStreamFormat::from_waveformat*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaveFormat {
+    /// PCM (pulse-code modulated) data in integer format.
+    Pcm = 1,
+    /// A-law-encoded format (G.711), 8 bits per sample.
+    Alaw = 6,
+    /// Mu-law-encoded format (G.711), 8 bits per sample.
+    Mulaw = 7,
+    /// G.722-encoded format.
+    G722 = 655,
+}
+
+impl From<WaveFormat> for u32 {
+    fn from(format: WaveFormat) -> Self {
+        format as u32
+    }
+}
+
+impl TryFrom<u32> for WaveFormat {
+    type Error = Error;
+
+    fn try_from(format: u32) -> Result<Self> {
+        match format {
+            1 => Ok(WaveFormat::Pcm),
+            6 => Ok(WaveFormat::Alaw),
+            7 => Ok(WaveFormat::Mulaw),
+            655 => Ok(WaveFormat::G722),
+            _ => Err(Error::new(
+                format!("unsupported wave format {}", format),
+                ErrorRootCause::ApiError(SpxError::UnsupportedFormat),
+            )),
+        }
+    }
+}
@@ -1,27 +1,44 @@
 //! Package common contains the definitions for many of the shared objects and properties in the Speech SDK.
+mod callback_panic;
 mod cancellation_error_code;
 mod cancellation_reason;
+mod gender;
+mod language_id_mode;
+mod language_id_priority;
 mod output_format;
 mod profanity_option;
 mod property_collection;
 mod property_id;
 mod result_reason;
+mod segmentation_strategy;
 mod service_property_channel;
 mod speech_synthesis_boundary_type;
 mod speech_synthesis_output_format;
 mod stream_status;
+mod subscription;
+mod synthesis_audio_format;
 mod synthesis_voice_type;
 
 // re-export structs directly under common module
+pub use self::callback_panic::{clear_callback_panic_hook, on_callback_panic, CallbackPanic};
 pub use self::cancellation_error_code::CancellationErrorCode;
 pub use self::cancellation_reason::CancellationReason;
+pub use self::gender::Gender;
+pub use self::language_id_mode::LanguageIdMode;
+pub use self::language_id_priority::LanguageIdPriority;
 pub use self::output_format::OutputFormat;
 pub use self::profanity_option::ProfanityOption;
 pub use self::property_collection::PropertyCollection;
 pub use self::property_id::PropertyId;
 pub use self::result_reason::ResultReason;
+pub use self::segmentation_strategy::SegmentationStrategy;
 pub use self::service_property_channel::ServicePropertyChannel;
 pub use self::speech_synthesis_boundary_type::SpeechSynthesisBoundaryType;
 pub use self::speech_synthesis_output_format::SpeechSynthesisOutputFormat;
 pub use self::stream_status::StreamStatus;
+pub use self::subscription::Subscription;
+pub use self::synthesis_audio_format::{SynthesisAudioFormat, SynthesisCodec, SynthesisContainer};
 pub use self::synthesis_voice_type::SynthesisVoiceType;
+
+pub(crate) use self::callback_panic::catch_callback_panic;
+pub(crate) use self::subscription::EventHandlers;
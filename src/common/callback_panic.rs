@@ -0,0 +1,69 @@
+use log::*;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, PoisonError, RwLock};
+
+type PanicHook = Arc<dyn Fn(&CallbackPanic) + Send + Sync>;
+
+static PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+
+/// CallbackPanic describes panic caught in user callback invoked by native Speech SDK
+/// (event handler, audio stream read/write...). Panic never unwinds into native code,
+/// callback returns safe value instead (e.g. zero bytes read ending the audio stream).
+#[derive(Debug, Clone)]
+pub struct CallbackPanic {
+    /// Callback which panicked, e.g. *PullAudioInputStream::cb_read*.
+    pub callback: String,
+    /// Panic message if panic payload was a string.
+    pub message: String,
+}
+
+/// Registers hook called whenever panic is caught in user callback, replacing previous hook.
+/// Panics are always logged, hook can be used e.g. to stop recognition or to fail the session.
+/// Hook is called on native SDK thread and must not block.
+pub fn on_callback_panic<F>(f: F)
+where
+    F: Fn(&CallbackPanic) + 'static + Send + Sync,
+{
+    *PANIC_HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(f));
+}
+
+/// Removes hook registered via *on_callback_panic*.
+pub fn clear_callback_panic_hook() {
+    *PANIC_HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+/// Runs *f* invoked from native code. If it panics the panic is reported
+/// and *fallback* is returned to the native caller.
+pub(crate) fn catch_callback_panic<R>(callback: &str, fallback: R, f: impl FnOnce() -> R) -> R {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let panic = CallbackPanic {
+                callback: callback.to_owned(),
+                message: panic_message(&*payload),
+            };
+            error!("{} panicked: {}", panic.callback, panic.message);
+            let hook = PANIC_HOOK
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+            if let Some(hook) = hook {
+                if catch_unwind(AssertUnwindSafe(|| hook(&panic))).is_err() {
+                    error!("callback panic hook panicked");
+                }
+            }
+            fallback
+        }
+    }
+}
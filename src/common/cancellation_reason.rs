@@ -1,5 +1,5 @@
 /// CancellationReason defines the possible reasons a recognition result might be canceled.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CancellationReason {
     /// Indicates that an error occurred during speech recognition.
     Error = 1,
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Gender of a synthesis voice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Gender {
+    /// Unknown indicates gender was not reported by the service.
+    #[default]
+    Unknown,
+    Female,
+    Male,
+    Neutral,
+}
+
+impl Gender {
+    /// Parses gender as reported in voice properties, e.g. *Female*.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "female" => Gender::Female,
+            "male" => Gender::Male,
+            "neutral" => Gender::Neutral,
+            _ => Gender::Unknown,
+        }
+    }
+}
+
+impl From<&str> for Gender {
+    fn from(value: &str) -> Self {
+        Gender::from_name(value)
+    }
+}
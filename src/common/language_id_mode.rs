@@ -0,0 +1,28 @@
+/// LanguageIdMode defines when the spoken language is identified during auto detect recognition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageIdMode {
+    /// AtStart identifies the language once, within the first few seconds of audio (default).
+    AtStart,
+
+    /// Continuous identifies the language continuously, spoken language may change during the session.
+    Continuous,
+}
+
+impl LanguageIdMode {
+    /// Returns value expected by SpeechServiceConnectionLanguageIdMode property.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LanguageIdMode::AtStart => "AtStart",
+            LanguageIdMode::Continuous => "Continuous",
+        }
+    }
+
+    /// Parses value of SpeechServiceConnectionLanguageIdMode property.
+    /// Unknown or empty value resolves to the service default (AtStart).
+    pub fn from_property_value(value: &str) -> Self {
+        match value {
+            "Continuous" => LanguageIdMode::Continuous,
+            _ => LanguageIdMode::AtStart,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+/// LanguageIdPriority defines whether language identification prefers accuracy or latency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageIdPriority {
+    /// Latency prefers fast language identification, possibly less accurate.
+    Latency,
+
+    /// Accuracy prefers accurate language identification, possibly with higher latency.
+    Accuracy,
+}
+
+impl LanguageIdPriority {
+    /// Returns value expected by language id priority properties.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LanguageIdPriority::Latency => "Latency",
+            LanguageIdPriority::Accuracy => "Accuracy",
+        }
+    }
+}
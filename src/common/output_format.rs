@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// OutputFormat Defines output formats
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OutputFormat {
     /// Simple output format
     Simple = 0,
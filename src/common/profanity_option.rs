@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// ProfanityOption defines the profanity option.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProfanityOption {
     /// Masked profanity option.
     Masked = 0,
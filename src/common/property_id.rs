@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// PropertyID defines speech property ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PropertyId {
     /// SpeechServiceConnectionKey is the Cognitive Services Speech Service subscription key. If you are using an
     /// intent recognizer, you need to specify the LUIS endpoint key for your particular LUIS app. Under normal
@@ -498,6 +501,21 @@ pub enum PropertyId {
     /// The default setting is "false". Note that metrics may not be available
     /// from all embedded speech scenarios.
     EmbeddedSpeechEnablePerformanceMetrics = 13300,
+
+    /// The pitch of the synthesized speech.
+    /// Under normal circumstances, you shouldn't have to use this property directly.
+    /// Instead, use SpeechSynthesisRequest::set_pitch.
+    SpeechSynthesisRequestPitch = 14001,
+
+    /// The rate of the synthesized speech.
+    /// Under normal circumstances, you shouldn't have to use this property directly.
+    /// Instead, use SpeechSynthesisRequest::set_rate.
+    SpeechSynthesisRequestRate = 14002,
+
+    /// The volume of the synthesized speech.
+    /// Under normal circumstances, you shouldn't have to use this property directly.
+    /// Instead, use SpeechSynthesisRequest::set_volume.
+    SpeechSynthesisRequestVolume = 14003,
 }
 
 impl PropertyId {
@@ -628,6 +646,10 @@ impl PropertyId {
             PropertyId::KeywordRecognitionModelKey => 13201,
 
             PropertyId::EmbeddedSpeechEnablePerformanceMetrics => 13300,
+
+            PropertyId::SpeechSynthesisRequestPitch => 14001,
+            PropertyId::SpeechSynthesisRequestRate => 14002,
+            PropertyId::SpeechSynthesisRequestVolume => 14003,
         }
     }
 }
@@ -1,5 +1,5 @@
 /// ResultReason specifies the possible reasons a recognition result might be generated.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ResultReason {
     /// NoMatch indicates speech could not be recognized. More details can be found in the NoMatchDetails object.
     NoMatch = 0,
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+/// SegmentationStrategy defines how the service decides that a spoken phrase has ended
+/// and final Recognized result should be produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentationStrategy {
+    /// Default lets the service choose segmentation.
+    #[default]
+    Default,
+
+    /// Silence ends the phrase once *silence_timeout* of silence is detected
+    /// (100 ms - 5 s). Phrases are additionally cut after *max_segment_time* (20 s - 70 s) if set.
+    Silence {
+        silence_timeout: Duration,
+        max_segment_time: Option<Duration>,
+    },
+
+    /// Semantic ends the phrase based on the meaning of recognized text
+    /// rather than on silence only.
+    Semantic,
+}
+
+impl SegmentationStrategy {
+    /// Returns value expected by Speech-SegmentationStrategy property.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentationStrategy::Default => "Default",
+            SegmentationStrategy::Silence { .. } => "Time",
+            SegmentationStrategy::Semantic => "Semantic",
+        }
+    }
+}
@@ -1,5 +1,5 @@
 /// SpeechSynthesisBoundaryType defines the boundary type of speech synthesis boundary event.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpeechSynthesisBoundaryType {
     /// WordBoundary indicates word boundary.
     WordBoundary = 0,
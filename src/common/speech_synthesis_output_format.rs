@@ -1,5 +1,8 @@
+use crate::common::{SynthesisAudioFormat, SynthesisCodec, SynthesisContainer};
+use serde::{Deserialize, Serialize};
+
 /// SpeechSynthesisOutputFormat defines the possible speech synthesis output audio formats.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SpeechSynthesisOutputFormat {
     /// Raw8Khz8BitMonoMULaw stands for raw-8khz-8bit-mono-mulaw
     Raw8Khz8BitMonoMULaw = 1,
@@ -140,3 +143,171 @@ impl From<SpeechSynthesisOutputFormat> for u32 {
         format as u32
     }
 }
+
+const ALL_FORMATS: [SpeechSynthesisOutputFormat; 37] = [
+    SpeechSynthesisOutputFormat::Raw8Khz8BitMonoMULaw,
+    SpeechSynthesisOutputFormat::Riff16Khz16KbpsMonoSiren,
+    SpeechSynthesisOutputFormat::Audio16Khz16KbpsMonoSiren,
+    SpeechSynthesisOutputFormat::Audio16Khz32KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio16Khz128KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio16Khz64KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio24Khz48KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio24Khz96KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio24Khz160KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Raw16Khz16BitMonoTrueSilk,
+    SpeechSynthesisOutputFormat::Riff16Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff8Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff24Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff8Khz8BitMonoMULaw,
+    SpeechSynthesisOutputFormat::Raw16Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Raw24Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Raw8Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Ogg16Khz16BitMonoOpus,
+    SpeechSynthesisOutputFormat::Ogg24Khz16BitMonoOpus,
+    SpeechSynthesisOutputFormat::Raw48Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff48Khz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Audio48Khz96KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Audio48Khz192KBitRateMonoMp3,
+    SpeechSynthesisOutputFormat::Ogg48Khz16BitMonoOpus,
+    SpeechSynthesisOutputFormat::Webm16Khz16BitMonoOpus,
+    SpeechSynthesisOutputFormat::Webm24Khz16BitMonoOpus,
+    SpeechSynthesisOutputFormat::Raw24Khz16BitMonoTrueSilk,
+    SpeechSynthesisOutputFormat::Raw8Khz8BitMonoALaw,
+    SpeechSynthesisOutputFormat::Riff8Khz8BitMonoALaw,
+    SpeechSynthesisOutputFormat::Webm24Khz16Bit24KbpsMonoOpus,
+    SpeechSynthesisOutputFormat::Audio16Khz16Bit32KbpsMonoOpus,
+    SpeechSynthesisOutputFormat::Audio24Khz16Bit48KbpsMonoOpus,
+    SpeechSynthesisOutputFormat::Audio24Khz16Bit24KbpsMonoOpus,
+    SpeechSynthesisOutputFormat::Raw22050Hz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff22050Hz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Raw44100Hz16BitMonoPcm,
+    SpeechSynthesisOutputFormat::Riff44100Hz16BitMonoPcm,
+];
+
+impl SpeechSynthesisOutputFormat {
+    /// Returns name of the format used by the service (e.g. riff-24khz-16bit-mono-pcm),
+    /// i.e. value of *SpeechServiceConnectionSynthOutputFormat* property.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpeechSynthesisOutputFormat::Raw8Khz8BitMonoMULaw => "raw-8khz-8bit-mono-mulaw",
+            SpeechSynthesisOutputFormat::Riff16Khz16KbpsMonoSiren => "riff-16khz-16kbps-mono-siren",
+            SpeechSynthesisOutputFormat::Audio16Khz16KbpsMonoSiren => {
+                "audio-16khz-16kbps-mono-siren"
+            }
+            SpeechSynthesisOutputFormat::Audio16Khz32KBitRateMonoMp3 => {
+                "audio-16khz-32kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio16Khz128KBitRateMonoMp3 => {
+                "audio-16khz-128kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio16Khz64KBitRateMonoMp3 => {
+                "audio-16khz-64kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio24Khz48KBitRateMonoMp3 => {
+                "audio-24khz-48kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio24Khz96KBitRateMonoMp3 => {
+                "audio-24khz-96kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio24Khz160KBitRateMonoMp3 => {
+                "audio-24khz-160kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Raw16Khz16BitMonoTrueSilk => {
+                "raw-16khz-16bit-mono-truesilk"
+            }
+            SpeechSynthesisOutputFormat::Riff16Khz16BitMonoPcm => "riff-16khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff8Khz16BitMonoPcm => "riff-8khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff24Khz16BitMonoPcm => "riff-24khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff8Khz8BitMonoMULaw => "riff-8khz-8bit-mono-mulaw",
+            SpeechSynthesisOutputFormat::Raw16Khz16BitMonoPcm => "raw-16khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Raw24Khz16BitMonoPcm => "raw-24khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Raw8Khz16BitMonoPcm => "raw-8khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Ogg16Khz16BitMonoOpus => "ogg-16khz-16bit-mono-opus",
+            SpeechSynthesisOutputFormat::Ogg24Khz16BitMonoOpus => "ogg-24khz-16bit-mono-opus",
+            SpeechSynthesisOutputFormat::Raw48Khz16BitMonoPcm => "raw-48khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff48Khz16BitMonoPcm => "riff-48khz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Audio48Khz96KBitRateMonoMp3 => {
+                "audio-48khz-96kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Audio48Khz192KBitRateMonoMp3 => {
+                "audio-48khz-192kbitrate-mono-mp3"
+            }
+            SpeechSynthesisOutputFormat::Ogg48Khz16BitMonoOpus => "ogg-48khz-16bit-mono-opus",
+            SpeechSynthesisOutputFormat::Webm16Khz16BitMonoOpus => "webm-16khz-16bit-mono-opus",
+            SpeechSynthesisOutputFormat::Webm24Khz16BitMonoOpus => "webm-24khz-16bit-mono-opus",
+            SpeechSynthesisOutputFormat::Raw24Khz16BitMonoTrueSilk => {
+                "raw-24khz-16bit-mono-truesilk"
+            }
+            SpeechSynthesisOutputFormat::Raw8Khz8BitMonoALaw => "raw-8khz-8bit-mono-alaw",
+            SpeechSynthesisOutputFormat::Riff8Khz8BitMonoALaw => "riff-8khz-8bit-mono-alaw",
+            SpeechSynthesisOutputFormat::Webm24Khz16Bit24KbpsMonoOpus => {
+                "webm-24khz-16bit-24kbps-mono-opus"
+            }
+            SpeechSynthesisOutputFormat::Audio16Khz16Bit32KbpsMonoOpus => {
+                "audio-16khz-16bit-32kbps-mono-opus"
+            }
+            SpeechSynthesisOutputFormat::Audio24Khz16Bit48KbpsMonoOpus => {
+                "audio-24khz-16bit-48kbps-mono-opus"
+            }
+            SpeechSynthesisOutputFormat::Audio24Khz16Bit24KbpsMonoOpus => {
+                "audio-24khz-16bit-24kbps-mono-opus"
+            }
+            SpeechSynthesisOutputFormat::Raw22050Hz16BitMonoPcm => "raw-22050hz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff22050Hz16BitMonoPcm => "riff-22050hz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Raw44100Hz16BitMonoPcm => "raw-44100hz-16bit-mono-pcm",
+            SpeechSynthesisOutputFormat::Riff44100Hz16BitMonoPcm => "riff-44100hz-16bit-mono-pcm",
+        }
+    }
+
+    /// Returns format for given service name, None if name is not known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        ALL_FORMATS
+            .iter()
+            .copied()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns container, codec, sample rate etc. of the format as described by its name.
+    pub fn audio_format(&self) -> SynthesisAudioFormat {
+        let tokens: Vec<&str> = self.name().split('-').collect();
+        let container = match tokens[0] {
+            "riff" => SynthesisContainer::Riff,
+            "ogg" => SynthesisContainer::Ogg,
+            "webm" => SynthesisContainer::Webm,
+            // raw-* and audio-*
+            _ => SynthesisContainer::Raw,
+        };
+        let codec = match tokens[tokens.len() - 1] {
+            "mulaw" => SynthesisCodec::MuLaw,
+            "alaw" => SynthesisCodec::ALaw,
+            "mp3" => SynthesisCodec::Mp3,
+            "opus" => SynthesisCodec::Opus,
+            "siren" => SynthesisCodec::Siren,
+            "truesilk" => SynthesisCodec::TrueSilk,
+            _ => SynthesisCodec::Pcm,
+        };
+        let number = |suffix: &str| {
+            tokens
+                .iter()
+                .filter_map(|token| token.strip_suffix(suffix)?.parse::<u32>().ok())
+                .next()
+        };
+        let samples_per_second = number("khz")
+            .map(|khz| khz * 1000)
+            .or_else(|| number("hz"))
+            .unwrap_or(0);
+        let bits_per_sample = number("bit").map(|bits| bits as u16);
+        let bitrate = number("kbitrate")
+            .or_else(|| number("kbps"))
+            .map(|kbps| kbps * 1000);
+        let channels = if tokens.contains(&"stereo") { 2 } else { 1 };
+        SynthesisAudioFormat::new(
+            container,
+            codec,
+            samples_per_second,
+            bits_per_sample,
+            bitrate,
+            channels,
+        )
+    }
+}
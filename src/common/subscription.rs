@@ -0,0 +1,236 @@
+use crate::common::catch_callback_panic;
+use crate::error::{convert_err, Result};
+use crate::ffi::{AZAC_HANDLE, SPXHR};
+use log::*;
+use std::fmt;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+
+/// Signature shared by all native event trampolines (recognizer, synthesizer and dialog connector).
+pub(crate) type NativeCallback = unsafe extern "C" fn(AZAC_HANDLE, AZAC_HANDLE, *mut c_void);
+
+/// Signature shared by all native *_set_callback functions.
+pub(crate) type NativeSetCallbackFn =
+    unsafe extern "C" fn(AZAC_HANDLE, Option<NativeCallback>, *mut c_void) -> SPXHR;
+
+type Handler<E> = Arc<dyn Fn(&E) + Send + Sync>;
+
+/// Closure registered via legacy set_*_cb method. It is only required to be *Send*,
+/// mutex makes it callable from whichever native thread fires the event.
+type LegacyHandler<E> = Arc<Mutex<Box<dyn Fn(E) + Send>>>;
+
+/// Locks mutex ignoring poisoning. Handler panics are caught while lock is held,
+/// protected data stay consistent in such case.
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Subscription is returned by *on_\** registration methods (e.g. *SpeechRecognizer::on_recognized*).
+/// Dropping the subscription unregisters the handler. Once the last handler
+/// of given event is gone the native callback is removed as well.
+#[must_use = "dropping Subscription immediately unregisters the handler"]
+pub struct Subscription {
+    unsubscribe: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("active", &self.unsubscribe.is_some())
+            .finish()
+    }
+}
+
+impl Subscription {
+    /// Keeps handler registered for the whole lifetime of the
+    /// recognizer/synthesizer/connector it was registered with.
+    pub fn detach(mut self) {
+        self.unsubscribe = None;
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+/// Native side of event registration. Handle is set to None
+/// when owner (recognizer, synthesizer...) is being dropped so that
+/// outstanding subscriptions never touch released native handle.
+struct NativeBinding {
+    handle: Option<AZAC_HANDLE>,
+    context: *mut c_void,
+    // None for handler lists driven purely from Rust side (see *EventHandlers::local*)
+    callback: Option<(NativeSetCallbackFn, NativeCallback)>,
+    attached: bool,
+    // set when single closure was registered via legacy set_*_cb method,
+    // native callback must stay attached in such case
+    pinned: bool,
+}
+
+// raw handle and context pointer are only dereferenced by native code
+// and all access to them is serialized by enclosing mutex
+unsafe impl Send for NativeBinding {}
+
+impl NativeBinding {
+    fn set_native(&mut self, attach: bool, err_msg: &str) -> Result<()> {
+        if let (Some(handle), Some((set_callback, trampoline))) = (self.handle, self.callback) {
+            unsafe {
+                let ret = if attach {
+                    set_callback(handle, Some(trampoline), self.context)
+                } else {
+                    set_callback(handle, None, std::ptr::null_mut())
+                };
+                convert_err(ret, err_msg)?;
+            }
+            self.attached = attach;
+        }
+        Ok(())
+    }
+}
+
+struct HandlerList<E> {
+    // event name reported when handler panics, e.g. SpeechRecognizer::recognized
+    name: &'static str,
+    next_id: AtomicU64,
+    handlers: Mutex<Vec<(u64, Handler<E>)>>,
+    legacy: Mutex<Option<LegacyHandler<E>>>,
+    // lock order: native first, then handlers
+    native: Mutex<NativeBinding>,
+}
+
+impl<E: 'static> HandlerList<E> {
+    fn unsubscribe(&self, id: u64) {
+        let mut native = lock(&self.native);
+        let mut handlers = lock(&self.handlers);
+        handlers.retain(|(handler_id, _)| *handler_id != id);
+        let is_empty = handlers.is_empty();
+        drop(handlers);
+
+        if is_empty && native.attached && !native.pinned {
+            if let Err(err) = native.set_native(false, "Subscription::drop error") {
+                error!("cannot remove native callback {:?}", err);
+            }
+        }
+    }
+}
+
+/// List of handlers registered for one particular event of recognizer,
+/// synthesizer or dialog service connector.
+pub(crate) struct EventHandlers<E> {
+    inner: Arc<HandlerList<E>>,
+}
+
+impl<E: 'static> EventHandlers<E> {
+    /// *name* identifies the event in panic reports (see *on_callback_panic*).
+    pub(crate) fn new(
+        name: &'static str,
+        set_callback: NativeSetCallbackFn,
+        trampoline: NativeCallback,
+    ) -> Self {
+        Self::with_callback(name, Some((set_callback, trampoline)))
+    }
+
+    /// Creates handler list not backed by any native callback.
+    /// Events are emitted explicitly by the owner.
+    pub(crate) fn local(name: &'static str) -> Self {
+        Self::with_callback(name, None)
+    }
+
+    fn with_callback(
+        name: &'static str,
+        callback: Option<(NativeSetCallbackFn, NativeCallback)>,
+    ) -> Self {
+        EventHandlers {
+            inner: Arc::new(HandlerList {
+                name,
+                next_id: AtomicU64::new(0),
+                handlers: Mutex::new(vec![]),
+                legacy: Mutex::new(None),
+                native: Mutex::new(NativeBinding {
+                    handle: None,
+                    context: std::ptr::null_mut(),
+                    callback,
+                    attached: false,
+                    pinned: false,
+                }),
+            }),
+        }
+    }
+
+    /// Binds the list to native handle and context pointer
+    /// (i.e. heap address of the owning callback bag).
+    pub(crate) fn bind(&self, handle: AZAC_HANDLE, context: *mut c_void) {
+        let mut native = lock(&self.inner.native);
+        native.handle = Some(handle);
+        native.context = context;
+    }
+
+    /// Must be called before native handle is released.
+    pub(crate) fn unbind(&self) {
+        lock(&self.inner.native).handle = None;
+    }
+
+    /// Replaces closure registered via legacy set_*_cb method and attaches
+    /// native callback permanently. Safe to call while events are being fired,
+    /// event already in progress finishes with previous closure.
+    pub(crate) fn set_legacy<F>(&self, f: F, err_msg: &str) -> Result<()>
+    where
+        F: Fn(E) + 'static + Send,
+    {
+        let mut native = lock(&self.inner.native);
+        native.set_native(true, err_msg)?;
+        native.pinned = true;
+        *lock(&self.inner.legacy) = Some(Arc::new(Mutex::new(Box::new(f))));
+        Ok(())
+    }
+
+    pub(crate) fn subscribe<F>(&self, f: F, err_msg: &str) -> Result<Subscription>
+    where
+        F: Fn(&E) + 'static + Send + Sync,
+    {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut native = lock(&self.inner.native);
+        if !native.attached {
+            native.set_native(true, err_msg)?;
+        }
+        lock(&self.inner.handlers).push((id, Arc::new(f)));
+        drop(native);
+
+        let weak: Weak<HandlerList<E>> = Arc::downgrade(&self.inner);
+        Ok(Subscription {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(list) = weak.upgrade() {
+                    list.unsubscribe(id);
+                }
+            })),
+        })
+    }
+
+    /// Invokes all registered handlers. Handlers are cloned out of the lock
+    /// so that handler can freely drop its own (or any other) subscription.
+    /// Panicking handler is reported (see *on_callback_panic*) and remaining handlers are still called.
+    pub(crate) fn emit(&self, event: &E) {
+        let handlers: Vec<Handler<E>> = lock(&self.inner.handlers)
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            catch_callback_panic(self.inner.name, (), || handler(event));
+        }
+    }
+
+    /// Invokes all registered handlers followed by legacy closure (if any).
+    /// Used by native trampolines.
+    pub(crate) fn dispatch(&self, event: E) {
+        self.emit(&event);
+        let legacy = lock(&self.inner.legacy).clone();
+        if let Some(legacy) = legacy {
+            catch_callback_panic(self.inner.name, (), || (lock(&legacy))(event));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::audio::pcm::{alaw_to_i16, f32_to_i16, mulaw_to_i16, SampleFormat, WavHeader};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+
+/// SynthesisContainer defines container of synthesized audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SynthesisContainer {
+    /// WAV file, i.e. RIFF header followed by audio data.
+    Riff,
+    /// No container, plain stream of samples or codec frames.
+    Raw,
+    Ogg,
+    Webm,
+}
+
+/// SynthesisCodec defines encoding of synthesized audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SynthesisCodec {
+    /// Linear PCM, little endian.
+    Pcm,
+    /// G.711 μ-law.
+    MuLaw,
+    /// G.711 A-law.
+    ALaw,
+    Mp3,
+    Opus,
+    Siren,
+    TrueSilk,
+}
+
+impl SynthesisCodec {
+    /// True for codecs which need decoder to get PCM samples.
+    pub fn is_compressed(&self) -> bool {
+        !matches!(
+            self,
+            SynthesisCodec::Pcm | SynthesisCodec::MuLaw | SynthesisCodec::ALaw
+        )
+    }
+}
+
+/// SynthesisAudioFormat describes audio produced for given *SpeechSynthesisOutputFormat*
+/// (see *SpeechSynthesisOutputFormat::audio_format* and *SpeechSynthesisResult::audio_format*).
+/// It can also decode such audio into PCM samples (see *SynthesisAudioFormat::decode*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SynthesisAudioFormat {
+    pub container: SynthesisContainer,
+    pub codec: SynthesisCodec,
+    pub samples_per_second: u32,
+    /// Bits per sample as stated by format name, None if not stated (e.g. MP3).
+    pub bits_per_sample: Option<u16>,
+    /// Bits per second, None if not stated by format name (e.g. Opus in Ogg).
+    pub bitrate: Option<u32>,
+    pub channels: u16,
+    pub mime_type: &'static str,
+    /// File extension without leading dot.
+    pub file_extension: &'static str,
+}
+
+impl SynthesisAudioFormat {
+    pub(crate) fn new(
+        container: SynthesisContainer,
+        codec: SynthesisCodec,
+        samples_per_second: u32,
+        bits_per_sample: Option<u16>,
+        bitrate: Option<u32>,
+        channels: u16,
+    ) -> Self {
+        // uncompressed bitrate is derived from sample rate and sample size
+        let bitrate = bitrate.or_else(|| match (codec.is_compressed(), bits_per_sample) {
+            (false, Some(bits)) => Some(samples_per_second * u32::from(bits) * u32::from(channels)),
+            _ => None,
+        });
+        let (mime_type, file_extension) = match (container, codec) {
+            (SynthesisContainer::Riff, _) => ("audio/wav", "wav"),
+            (SynthesisContainer::Ogg, _) => ("audio/ogg", "ogg"),
+            (SynthesisContainer::Webm, _) => ("audio/webm", "webm"),
+            (SynthesisContainer::Raw, SynthesisCodec::Pcm) => ("audio/L16", "pcm"),
+            (SynthesisContainer::Raw, SynthesisCodec::MuLaw) => ("audio/PCMU", "ulaw"),
+            (SynthesisContainer::Raw, SynthesisCodec::ALaw) => ("audio/PCMA", "alaw"),
+            (SynthesisContainer::Raw, SynthesisCodec::Mp3) => ("audio/mpeg", "mp3"),
+            (SynthesisContainer::Raw, SynthesisCodec::Opus) => ("audio/opus", "opus"),
+            (SynthesisContainer::Raw, SynthesisCodec::Siren) => ("audio/siren", "siren"),
+            (SynthesisContainer::Raw, SynthesisCodec::TrueSilk) => ("audio/SILK", "silk"),
+        };
+        SynthesisAudioFormat {
+            container,
+            codec,
+            samples_per_second,
+            bits_per_sample,
+            bitrate,
+            channels,
+            mime_type,
+            file_extension,
+        }
+    }
+
+    /// Describes audio starting with RIFF header, None if *data* does not start with valid header.
+    pub fn from_wav(data: &[u8]) -> Option<Self> {
+        let header = WavHeader::read_from(&mut &data[..]).ok()?;
+        let codec = match header.sample_format {
+            SampleFormat::MuLaw => SynthesisCodec::MuLaw,
+            SampleFormat::ALaw => SynthesisCodec::ALaw,
+            _ => SynthesisCodec::Pcm,
+        };
+        Some(SynthesisAudioFormat::new(
+            SynthesisContainer::Riff,
+            codec,
+            header.samples_per_second,
+            Some(header.sample_format.bits_per_sample()),
+            None,
+            header.channels,
+        ))
+    }
+
+    /// Decodes *data* in this format into 16-bit PCM samples (interleaved if there are more
+    /// channels), RIFF header is stripped. MP3 is decoded only with *mp3-decoding* feature,
+    /// other compressed codecs are not supported.
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<i16>> {
+        match (self.container, self.codec) {
+            (SynthesisContainer::Riff, _) => {
+                let mut data = data;
+                let header = WavHeader::read_from(&mut data)?;
+                // streamed WAV may not carry data length
+                let data_len = header.data_len as usize;
+                if data_len != 0 && data_len < data.len() {
+                    data = &data[..data_len];
+                }
+                Ok(match header.sample_format {
+                    SampleFormat::I16 => pcm16_samples(data),
+                    SampleFormat::MuLaw => data.iter().map(|s| mulaw_to_i16(*s)).collect(),
+                    SampleFormat::ALaw => data.iter().map(|s| alaw_to_i16(*s)).collect(),
+                    sample_format => sample_format
+                        .decode_all(data)
+                        .into_iter()
+                        .map(f32_to_i16)
+                        .collect(),
+                })
+            }
+            (SynthesisContainer::Raw, SynthesisCodec::Pcm) => Ok(pcm16_samples(data)),
+            (SynthesisContainer::Raw, SynthesisCodec::MuLaw) => {
+                Ok(data.iter().map(|s| mulaw_to_i16(*s)).collect())
+            }
+            (SynthesisContainer::Raw, SynthesisCodec::ALaw) => {
+                Ok(data.iter().map(|s| alaw_to_i16(*s)).collect())
+            }
+            #[cfg(feature = "mp3-decoding")]
+            (_, SynthesisCodec::Mp3) => crate::audio::pcm::decode_mp3(data),
+            (_, codec) => Err(Error::new(
+                format!(
+                    "SynthesisAudioFormat.decode error: decoding of {:?} is not supported",
+                    codec
+                ),
+                ErrorRootCause::ApiError(SpxError::UnsupportedFormat),
+            )),
+        }
+    }
+}
+
+fn pcm16_samples(data: &[u8]) -> Vec<i16> {
+    data.chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect()
+}
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// SynthesisVoiceType defines the type of a synthesis voice.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SynthesisVoiceType {
     /// OnlineNeural indicates online neural voice.
     OnlineNeural = 1,
@@ -15,6 +17,20 @@ pub enum SynthesisVoiceType {
 }
 
 impl SynthesisVoiceType {
+    pub fn is_neural(&self) -> bool {
+        matches!(
+            self,
+            SynthesisVoiceType::OnlineNeural | SynthesisVoiceType::OfflineNeural
+        )
+    }
+
+    pub fn is_offline(&self) -> bool {
+        matches!(
+            self,
+            SynthesisVoiceType::OfflineNeural | SynthesisVoiceType::OfflineStandard
+        )
+    }
+
     pub fn from_u32(reason: u32) -> Self {
         match reason {
             1 => SynthesisVoiceType::OnlineNeural,
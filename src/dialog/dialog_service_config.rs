@@ -1,7 +1,7 @@
 use crate::common::{PropertyId, ServicePropertyChannel};
 use crate::error::Result;
 use crate::ffi::SPXSPEECHCONFIGHANDLE;
-use crate::speech::SpeechConfig;
+use crate::speech::{RecognitionTuning, SpeechConfig};
 
 /// DialogServiceConfig defines base configurations for
 /// the dialog service connector and custom commands config objects.
@@ -61,4 +61,14 @@ pub trait DialogServiceConfig {
         self.get_speech_config()
             .get_property(PropertyId::SpeechServiceConnectionRecoLanguage)
     }
+
+    /// Validates and applies typed recognition timeouts, segmentation and response options.
+    fn set_recognition_tuning(&mut self, tuning: &RecognitionTuning) -> Result<()> {
+        self.get_speech_config().set_recognition_tuning(tuning)
+    }
+
+    /// Reads back recognition options previously set on this config.
+    fn get_recognition_tuning(&mut self) -> Result<RecognitionTuning> {
+        self.get_speech_config().get_recognition_tuning()
+    }
 }
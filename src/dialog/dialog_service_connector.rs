@@ -1,5 +1,7 @@
 use crate::audio::AudioConfig;
-use crate::common::{PropertyCollection, PropertyId};
+use crate::common::{
+    catch_callback_panic, EventHandlers, PropertyCollection, PropertyId, Subscription,
+};
 use crate::dialog::{ActivityReceivedEvent, DialogServiceConfig};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
@@ -12,13 +14,16 @@ use crate::ffi::{
     dialog_service_connector_recognizing_set_callback, dialog_service_connector_send_activity,
     dialog_service_connector_session_started_set_callback,
     dialog_service_connector_session_stopped_set_callback,
+    dialog_service_connector_speech_end_detected_set_callback,
+    dialog_service_connector_speech_start_detected_set_callback,
+    dialog_service_connector_start_continuous_listening,
     dialog_service_connector_start_keyword_recognition,
-    dialog_service_connector_stop_keyword_recognition, SmartHandle, SPXEVENTHANDLE, SPXHANDLE,
-    SPXPROPERTYBAGHANDLE, SPXRECOHANDLE, SPXRESULTHANDLE,
+    dialog_service_connector_stop_keyword_recognition, dialog_service_connector_stop_listening,
+    SmartHandle, SPXEVENTHANDLE, SPXHANDLE, SPXPROPERTYBAGHANDLE, SPXRECOHANDLE, SPXRESULTHANDLE,
 };
 use crate::speech::{
-    KeywordRecognitionModel, SessionEvent, SpeechRecognitionCanceledEvent, SpeechRecognitionEvent,
-    SpeechRecognitionResult,
+    KeywordRecognitionModel, RecognitionEvent, SessionEvent, SpeechRecognitionCanceledEvent,
+    SpeechRecognitionEvent, SpeechRecognitionResult,
 };
 use log::*;
 use std::ffi::{CStr, CString};
@@ -31,16 +36,97 @@ pub struct SendActivityOutcome {
     pub interaction_id: String,
 }
 
+/// A separate internal struct to hold all the callback closures for the dialog service connector events.
+/// Boxing it inside DialogServiceConnector ensures the connector itself can be moved freely
+/// while the callbacks remain at a fixed memory address on the heap.
+struct CallbackBag {
+    session_started_handlers: EventHandlers<SessionEvent>,
+    session_stopped_handlers: EventHandlers<SessionEvent>,
+    speech_start_detected_handlers: EventHandlers<RecognitionEvent>,
+    speech_end_detected_handlers: EventHandlers<RecognitionEvent>,
+    canceled_handlers: EventHandlers<SpeechRecognitionCanceledEvent>,
+    recognizing_handlers: EventHandlers<SpeechRecognitionEvent>,
+    recognized_handlers: EventHandlers<SpeechRecognitionEvent>,
+    activity_received_handlers: EventHandlers<ActivityReceivedEvent>,
+}
+
+impl CallbackBag {
+    fn new() -> Box<Self> {
+        Box::new(CallbackBag {
+            session_started_handlers: EventHandlers::new(
+                "DialogServiceConnector::session_started",
+                dialog_service_connector_session_started_set_callback,
+                DialogServiceConnector::cb_session_started,
+            ),
+            session_stopped_handlers: EventHandlers::new(
+                "DialogServiceConnector::session_stopped",
+                dialog_service_connector_session_stopped_set_callback,
+                DialogServiceConnector::cb_session_stopped,
+            ),
+            speech_start_detected_handlers: EventHandlers::new(
+                "DialogServiceConnector::speech_start_detected",
+                dialog_service_connector_speech_start_detected_set_callback,
+                DialogServiceConnector::cb_speech_start_detected,
+            ),
+            speech_end_detected_handlers: EventHandlers::new(
+                "DialogServiceConnector::speech_end_detected",
+                dialog_service_connector_speech_end_detected_set_callback,
+                DialogServiceConnector::cb_speech_end_detected,
+            ),
+            canceled_handlers: EventHandlers::new(
+                "DialogServiceConnector::canceled",
+                dialog_service_connector_canceled_set_callback,
+                DialogServiceConnector::cb_canceled,
+            ),
+            recognizing_handlers: EventHandlers::new(
+                "DialogServiceConnector::recognizing",
+                dialog_service_connector_recognizing_set_callback,
+                DialogServiceConnector::cb_recognizing,
+            ),
+            recognized_handlers: EventHandlers::new(
+                "DialogServiceConnector::recognized",
+                dialog_service_connector_recognized_set_callback,
+                DialogServiceConnector::cb_recognized,
+            ),
+            activity_received_handlers: EventHandlers::new(
+                "DialogServiceConnector::activity_received",
+                dialog_service_connector_activity_received_set_callback,
+                DialogServiceConnector::cb_activity_received,
+            ),
+        })
+    }
+
+    /// Binds all handler lists to native connector handle.
+    /// Bag address is passed as context into native callbacks.
+    fn bind(&self, handle: SPXRECOHANDLE) {
+        let context = self as *const _ as *mut c_void;
+        self.session_started_handlers.bind(handle, context);
+        self.session_stopped_handlers.bind(handle, context);
+        self.speech_start_detected_handlers.bind(handle, context);
+        self.speech_end_detected_handlers.bind(handle, context);
+        self.canceled_handlers.bind(handle, context);
+        self.recognizing_handlers.bind(handle, context);
+        self.recognized_handlers.bind(handle, context);
+        self.activity_received_handlers.bind(handle, context);
+    }
+
+    fn unbind(&self) {
+        self.session_started_handlers.unbind();
+        self.session_stopped_handlers.unbind();
+        self.speech_start_detected_handlers.unbind();
+        self.speech_end_detected_handlers.unbind();
+        self.canceled_handlers.unbind();
+        self.recognizing_handlers.unbind();
+        self.recognized_handlers.unbind();
+        self.activity_received_handlers.unbind();
+    }
+}
+
 /// DialogServiceConnector connects to a speech enabled dialog backend.
 pub struct DialogServiceConnector {
     pub properties: PropertyCollection,
     pub handle: SmartHandle<SPXRECOHANDLE>,
-    session_started_cb: Option<Box<dyn Fn(SessionEvent) + Send>>,
-    session_stopped_cb: Option<Box<dyn Fn(SessionEvent) + Send>>,
-    canceled_cb: Option<Box<dyn Fn(SpeechRecognitionCanceledEvent) + Send>>,
-    recognizing_cb: Option<Box<dyn Fn(SpeechRecognitionEvent) + Send>>,
-    recognized_cb: Option<Box<dyn Fn(SpeechRecognitionEvent) + Send>>,
-    activity_received_cb: Option<Box<dyn Fn(ActivityReceivedEvent) + Send>>,
+    callback_bag: Box<CallbackBag>,
 }
 
 impl fmt::Debug for DialogServiceConnector {
@@ -61,20 +147,17 @@ impl DialogServiceConnector {
             let ret =
                 dialog_service_connector_get_property_bag(handle, prop_bag_handle.as_mut_ptr());
             convert_err(ret, "DialogServiceConnector::from_handle error")?;
-            Ok(DialogServiceConnector {
+            let result = DialogServiceConnector {
                 properties: PropertyCollection::from_handle(prop_bag_handle.assume_init()),
                 handle: SmartHandle::create(
                     "DialogServiceConnector",
                     handle,
                     dialog_service_connector_handle_release,
                 ),
-                session_started_cb: None,
-                session_stopped_cb: None,
-                canceled_cb: None,
-                recognizing_cb: None,
-                recognized_cb: None,
-                activity_received_cb: None,
-            })
+                callback_bag: CallbackBag::new(),
+            };
+            result.callback_bag.bind(handle);
+            Ok(result)
         }
     }
 
@@ -153,6 +236,29 @@ impl DialogServiceConnector {
         }
     }
 
+    /// Starts listening session which continues (recognizing utterance after utterance)
+    /// until *stop_listening_async* is called. Activities from the bot can be received
+    /// meanwhile so that user can barge in during bot's turn.
+    pub async fn start_continuous_listening_async(&self) -> Result<()> {
+        unsafe {
+            let ret = dialog_service_connector_start_continuous_listening(self.handle.inner());
+            convert_err(
+                ret,
+                "DialogServiceConnector.start_continuous_listening_async error",
+            )?;
+            Ok(())
+        }
+    }
+
+    /// Stops listening session started by *start_continuous_listening_async* or *listen_once_async*.
+    pub async fn stop_listening_async(&self) -> Result<()> {
+        unsafe {
+            let ret = dialog_service_connector_stop_listening(self.handle.inner());
+            convert_err(ret, "DialogServiceConnector.stop_listening_async error")?;
+            Ok(())
+        }
+    }
+
     /// StartKeywordRecognitionAsync initiates keyword recognition.
     pub async fn start_keyword_recognition_async(
         &self,
@@ -215,96 +321,155 @@ impl DialogServiceConnector {
     where
         F: Fn(SessionEvent) + 'static + Send,
     {
-        self.session_started_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_session_started_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_session_started),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_session_started_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .session_started_handlers
+            .set_legacy(f, "DialogServiceConnector.set_session_started_cb error")
     }
 
     pub fn set_session_stopped_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SessionEvent) + 'static + Send,
     {
-        self.session_stopped_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_session_stopped_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_session_stopped),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_session_stopped_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .session_stopped_handlers
+            .set_legacy(f, "DialogServiceConnector.set_session_stopped_cb error")
+    }
+
+    pub fn set_speech_start_detected_cb<F>(&mut self, f: F) -> Result<()>
+    where
+        F: Fn(RecognitionEvent) + 'static + Send,
+    {
+        self.callback_bag.speech_start_detected_handlers.set_legacy(
+            f,
+            "DialogServiceConnector.set_speech_start_detected_cb error",
+        )
+    }
+
+    pub fn set_speech_end_detected_cb<F>(&mut self, f: F) -> Result<()>
+    where
+        F: Fn(RecognitionEvent) + 'static + Send,
+    {
+        self.callback_bag
+            .speech_end_detected_handlers
+            .set_legacy(f, "DialogServiceConnector.set_speech_end_detected_cb error")
     }
 
     pub fn set_canceled_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechRecognitionCanceledEvent) + 'static + Send,
     {
-        self.canceled_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_canceled_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_canceled),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_canceled_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .canceled_handlers
+            .set_legacy(f, "DialogServiceConnector.set_canceled_cb error")
     }
 
     pub fn set_recognizing_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechRecognitionEvent) + 'static + Send,
     {
-        self.recognizing_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_recognizing_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_recognizing),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_recognizing_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .recognizing_handlers
+            .set_legacy(f, "DialogServiceConnector.set_recognizing_cb error")
     }
 
     pub fn set_recognized_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechRecognitionEvent) + 'static + Send,
     {
-        self.recognized_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_recognized_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_recognized),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_recognized_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .recognized_handlers
+            .set_legacy(f, "DialogServiceConnector.set_recognized_cb error")
     }
 
     pub fn set_activity_received_cb<F>(&mut self, f: F) -> Result<()>
     where
-        F: Fn(SpeechRecognitionEvent) + 'static + Send,
+        F: Fn(ActivityReceivedEvent) + 'static + Send,
     {
-        self.recognized_cb = Some(Box::new(f));
-        unsafe {
-            let ret = dialog_service_connector_activity_received_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_activity_received),
-                self as *const _ as *mut c_void,
-            );
-            convert_err(ret, "DialogServiceConnector.set_activity_received_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .activity_received_handlers
+            .set_legacy(f, "DialogServiceConnector.set_activity_received_cb error")
+    }
+
+    /// Registers additional handler of session started event. Unlike *set_session_started_cb*
+    /// any number of handlers can be registered, each of them is unregistered
+    /// when returned *Subscription* is dropped.
+    pub fn on_session_started<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .session_started_handlers
+            .subscribe(f, "DialogServiceConnector.on_session_started error")
+    }
+
+    /// Registers additional handler of session stopped event, see *on_session_started*.
+    pub fn on_session_stopped<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .session_stopped_handlers
+            .subscribe(f, "DialogServiceConnector.on_session_stopped error")
+    }
+
+    /// Registers additional handler of speech start detected event, see *on_session_started*.
+    pub fn on_speech_start_detected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&RecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .speech_start_detected_handlers
+            .subscribe(f, "DialogServiceConnector.on_speech_start_detected error")
+    }
+
+    /// Registers additional handler of speech end detected event, see *on_session_started*.
+    pub fn on_speech_end_detected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&RecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .speech_end_detected_handlers
+            .subscribe(f, "DialogServiceConnector.on_speech_end_detected error")
+    }
+
+    /// Registers additional handler of canceled event, see *on_session_started*.
+    pub fn on_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionCanceledEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .canceled_handlers
+            .subscribe(f, "DialogServiceConnector.on_canceled error")
+    }
+
+    /// Registers additional handler of recognizing event, see *on_session_started*.
+    pub fn on_recognizing<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .recognizing_handlers
+            .subscribe(f, "DialogServiceConnector.on_recognizing error")
+    }
+
+    /// Registers additional handler of recognized event, see *on_session_started*.
+    pub fn on_recognized<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .recognized_handlers
+            .subscribe(f, "DialogServiceConnector.on_recognized error")
+    }
+
+    /// Registers additional handler of activity received event, see *on_session_started*.
+    pub fn on_activity_received<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&ActivityReceivedEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .activity_received_handlers
+            .subscribe(f, "DialogServiceConnector.on_activity_received error")
     }
 
     #[allow(non_snake_case)]
@@ -315,20 +480,18 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_session_started called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        trace!("dialog_service_connector {:?}", dialog_service_connector);
-        if let Some(cb) = &dialog_service_connector.session_started_cb {
-            trace!("session_started_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_session_started", (), || {
             match SessionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.session_started_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("DialogServiceConnector::cb_session_started error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -339,19 +502,70 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_session_stopped called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        if let Some(cb) = &dialog_service_connector.session_stopped_cb {
-            trace!("cb_session_stopped defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_session_stopped", (), || {
             match SessionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.session_stopped_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("DialogServiceConnector::cb_session_stopped error {:?}", err);
                 }
             }
-        }
+        })
+    }
+
+    #[allow(non_snake_case)]
+    #[allow(unused_variables)]
+    unsafe extern "C" fn cb_speech_start_detected(
+        hreco: SPXRECOHANDLE,
+        hevent: SPXEVENTHANDLE,
+        pvContext: *mut c_void,
+    ) {
+        trace!("DialogServiceConnector::cb_speech_start_detected called");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic(
+            "DialogServiceConnector::cb_speech_start_detected",
+            (),
+            || match RecognitionEvent::from_handle(hevent) {
+                Ok(event) => {
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.speech_start_detected_handlers.dispatch(event);
+                }
+                Err(err) => {
+                    error!(
+                        "DialogServiceConnector::cb_speech_start_detected error {:?}",
+                        err
+                    );
+                }
+            },
+        )
+    }
+
+    #[allow(non_snake_case)]
+    #[allow(unused_variables)]
+    unsafe extern "C" fn cb_speech_end_detected(
+        hreco: SPXRECOHANDLE,
+        hevent: SPXEVENTHANDLE,
+        pvContext: *mut c_void,
+    ) {
+        trace!("DialogServiceConnector::cb_speech_end_detected called");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_speech_end_detected", (), || {
+            match RecognitionEvent::from_handle(hevent) {
+                Ok(event) => {
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.speech_end_detected_handlers.dispatch(event);
+                }
+                Err(err) => {
+                    error!(
+                        "DialogServiceConnector::cb_speech_end_detected error {:?}",
+                        err
+                    );
+                }
+            }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -362,19 +576,18 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_canceled called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        if let Some(cb) = &dialog_service_connector.canceled_cb {
-            trace!("canceled_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_canceled", (), || {
             match SpeechRecognitionCanceledEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.canceled_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("DialogServiceConnector::cb_canceled error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -385,19 +598,18 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_recognizing called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        if let Some(cb) = &dialog_service_connector.recognizing_cb {
-            trace!("recognizing_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_recognizing", (), || {
             match SpeechRecognitionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.recognizing_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("DialogServiceConnector::cb_recognizing error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -408,19 +620,18 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_recognized called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        if let Some(cb) = &dialog_service_connector.recognized_cb {
-            trace!("recognized_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_recognized", (), || {
             match SpeechRecognitionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.recognized_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("DialogServiceConnector::cb_recognized error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -431,13 +642,12 @@ impl DialogServiceConnector {
         pvContext: *mut c_void,
     ) {
         trace!("DialogServiceConnector::cb_activity_received called");
-        let dialog_service_connector = &mut *(pvContext as *mut DialogServiceConnector);
-        if let Some(cb) = &dialog_service_connector.activity_received_cb {
-            trace!("cb_activity_received defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("DialogServiceConnector::cb_activity_received", (), || {
             match ActivityReceivedEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.activity_received_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -446,6 +656,13 @@ impl DialogServiceConnector {
                     );
                 }
             }
-        }
+        })
+    }
+}
+
+impl Drop for DialogServiceConnector {
+    fn drop(&mut self) {
+        // outstanding subscriptions must not touch native handle once it is released
+        self.callback_bag.unbind();
     }
 }
@@ -1,4 +1,5 @@
 //! Package error contains struct Error used to wrap library errors.
+use crate::common::CancellationErrorCode;
 use crate::ffi::{
     error_get_error_code, error_get_message, error_release, AZAC_HANDLE, SPX_NOERROR,
 };
@@ -8,16 +9,352 @@ use std::result;
 use std::str::Utf8Error;
 use std::string::FromUtf8Error;
 
+/// Typed representation of native SPXERR_\* codes. Variants in the second
+/// group have no native error code, they are produced from *CancellationErrorCode*
+/// of canceled recognition/synthesis so that both kinds of failures
+/// can be handled (e.g. retried) the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpxError {
+    NotImpl,
+    Uninitialized,
+    AlreadyInitialized,
+    UnhandledException,
+    NotFound,
+    InvalidArg,
+    Timeout,
+    AlreadyInProgress,
+    FileOpenFailed,
+    UnexpectedEof,
+    InvalidHeader,
+    AudioIsPumping,
+    UnsupportedFormat,
+    Abort,
+    MicNotAvailable,
+    InvalidState,
+    UuidCreateFailed,
+    SetFormatUnexpectedStateTransition,
+    ProcessAudioInvalidState,
+    StartRecognizingInvalidStateTransition,
+    UnexpectedCreateObjectFailure,
+    MicError,
+    NoAudioInput,
+    UnexpectedUspSiteFailure,
+    UnexpectedUnidecSiteFailure,
+    BufferTooSmall,
+    OutOfMemory,
+    RuntimeError,
+    InvalidUrl,
+    InvalidRegion,
+    SwitchModeNotAllowed,
+    ChangeConnectionStatusNotAllowed,
+    ExplicitConnectionNotSupportedByRecognizer,
+    InvalidHandle,
+    InvalidRecognizer,
+    OutOfRange,
+    ExtensionLibraryNotFound,
+    UnexpectedTtsEngineSiteFailure,
+    UnexpectedAudioOutputFailure,
+    GstreamerInternalError,
+    ContainerFormatNotSupportedError,
+    GstreamerNotFoundError,
+    InvalidLanguage,
+    UnsupportedApiError,
+    RingbufferDataUnavailable,
+    UnexpectedConversationSiteFailure,
+    UnexpectedConversationTranslatorSiteFailure,
+    Canceled,
+    /// Native error code not known to this version of the library.
+    Unknown(usize),
+
+    AuthenticationFailure,
+    BadRequest,
+    TooManyRequests,
+    Forbidden,
+    ConnectionFailure,
+    ServiceTimeout,
+    ServiceError,
+    ServiceUnavailable,
+    /// Unexpected runtime error reported via cancellation (*CancellationErrorCode::RuntimeError*),
+    /// unlike *RuntimeError* it does not originate from native function call.
+    CanceledRuntimeError,
+}
+
+impl SpxError {
+    /// Converts native error code into SpxError.
+    pub fn from_code(code: usize) -> Self {
+        match code {
+            0xfff => SpxError::NotImpl,
+            0x001 => SpxError::Uninitialized,
+            0x002 => SpxError::AlreadyInitialized,
+            0x003 => SpxError::UnhandledException,
+            0x004 => SpxError::NotFound,
+            0x005 => SpxError::InvalidArg,
+            0x006 => SpxError::Timeout,
+            0x007 => SpxError::AlreadyInProgress,
+            0x008 => SpxError::FileOpenFailed,
+            0x009 => SpxError::UnexpectedEof,
+            0x00a => SpxError::InvalidHeader,
+            0x00b => SpxError::AudioIsPumping,
+            0x00c => SpxError::UnsupportedFormat,
+            0x00d => SpxError::Abort,
+            0x00e => SpxError::MicNotAvailable,
+            0x00f => SpxError::InvalidState,
+            0x010 => SpxError::UuidCreateFailed,
+            0x011 => SpxError::SetFormatUnexpectedStateTransition,
+            0x012 => SpxError::ProcessAudioInvalidState,
+            0x013 => SpxError::StartRecognizingInvalidStateTransition,
+            0x014 => SpxError::UnexpectedCreateObjectFailure,
+            0x015 => SpxError::MicError,
+            0x016 => SpxError::NoAudioInput,
+            0x017 => SpxError::UnexpectedUspSiteFailure,
+            0x018 => SpxError::UnexpectedUnidecSiteFailure,
+            0x019 => SpxError::BufferTooSmall,
+            0x01A => SpxError::OutOfMemory,
+            0x01B => SpxError::RuntimeError,
+            0x01C => SpxError::InvalidUrl,
+            0x01D => SpxError::InvalidRegion,
+            0x01E => SpxError::SwitchModeNotAllowed,
+            0x01F => SpxError::ChangeConnectionStatusNotAllowed,
+            0x020 => SpxError::ExplicitConnectionNotSupportedByRecognizer,
+            0x021 => SpxError::InvalidHandle,
+            0x022 => SpxError::InvalidRecognizer,
+            0x023 => SpxError::OutOfRange,
+            0x024 => SpxError::ExtensionLibraryNotFound,
+            0x025 => SpxError::UnexpectedTtsEngineSiteFailure,
+            0x026 => SpxError::UnexpectedAudioOutputFailure,
+            0x027 => SpxError::GstreamerInternalError,
+            0x028 => SpxError::ContainerFormatNotSupportedError,
+            0x029 => SpxError::GstreamerNotFoundError,
+            0x02A => SpxError::InvalidLanguage,
+            0x02B => SpxError::UnsupportedApiError,
+            0x02C => SpxError::RingbufferDataUnavailable,
+            0x030 => SpxError::UnexpectedConversationSiteFailure,
+            0x031 => SpxError::UnexpectedConversationTranslatorSiteFailure,
+            0x032 => SpxError::Canceled,
+            _ => SpxError::Unknown(code),
+        }
+    }
+
+    /// Maps cancellation error code into SpxError.
+    /// Returns None for *CancellationErrorCode::NoError*.
+    pub fn from_cancellation_error_code(code: &CancellationErrorCode) -> Option<Self> {
+        match code {
+            CancellationErrorCode::NoError => None,
+            CancellationErrorCode::AuthenticationFailure => Some(SpxError::AuthenticationFailure),
+            CancellationErrorCode::BadRequest => Some(SpxError::BadRequest),
+            CancellationErrorCode::TooManyRequests => Some(SpxError::TooManyRequests),
+            CancellationErrorCode::Forbidden => Some(SpxError::Forbidden),
+            CancellationErrorCode::ConnectionFailure => Some(SpxError::ConnectionFailure),
+            CancellationErrorCode::ServiceTimeout => Some(SpxError::ServiceTimeout),
+            CancellationErrorCode::ServiceError => Some(SpxError::ServiceError),
+            CancellationErrorCode::ServiceUnavailable => Some(SpxError::ServiceUnavailable),
+            CancellationErrorCode::RuntimeError => Some(SpxError::CanceledRuntimeError),
+        }
+    }
+
+    /// Returns native error code, None for cancellation-only variants.
+    pub fn code(&self) -> Option<usize> {
+        let code = match self {
+            SpxError::NotImpl => 0xfff,
+            SpxError::Uninitialized => 0x001,
+            SpxError::AlreadyInitialized => 0x002,
+            SpxError::UnhandledException => 0x003,
+            SpxError::NotFound => 0x004,
+            SpxError::InvalidArg => 0x005,
+            SpxError::Timeout => 0x006,
+            SpxError::AlreadyInProgress => 0x007,
+            SpxError::FileOpenFailed => 0x008,
+            SpxError::UnexpectedEof => 0x009,
+            SpxError::InvalidHeader => 0x00a,
+            SpxError::AudioIsPumping => 0x00b,
+            SpxError::UnsupportedFormat => 0x00c,
+            SpxError::Abort => 0x00d,
+            SpxError::MicNotAvailable => 0x00e,
+            SpxError::InvalidState => 0x00f,
+            SpxError::UuidCreateFailed => 0x010,
+            SpxError::SetFormatUnexpectedStateTransition => 0x011,
+            SpxError::ProcessAudioInvalidState => 0x012,
+            SpxError::StartRecognizingInvalidStateTransition => 0x013,
+            SpxError::UnexpectedCreateObjectFailure => 0x014,
+            SpxError::MicError => 0x015,
+            SpxError::NoAudioInput => 0x016,
+            SpxError::UnexpectedUspSiteFailure => 0x017,
+            SpxError::UnexpectedUnidecSiteFailure => 0x018,
+            SpxError::BufferTooSmall => 0x019,
+            SpxError::OutOfMemory => 0x01A,
+            SpxError::RuntimeError => 0x01B,
+            SpxError::InvalidUrl => 0x01C,
+            SpxError::InvalidRegion => 0x01D,
+            SpxError::SwitchModeNotAllowed => 0x01E,
+            SpxError::ChangeConnectionStatusNotAllowed => 0x01F,
+            SpxError::ExplicitConnectionNotSupportedByRecognizer => 0x020,
+            SpxError::InvalidHandle => 0x021,
+            SpxError::InvalidRecognizer => 0x022,
+            SpxError::OutOfRange => 0x023,
+            SpxError::ExtensionLibraryNotFound => 0x024,
+            SpxError::UnexpectedTtsEngineSiteFailure => 0x025,
+            SpxError::UnexpectedAudioOutputFailure => 0x026,
+            SpxError::GstreamerInternalError => 0x027,
+            SpxError::ContainerFormatNotSupportedError => 0x028,
+            SpxError::GstreamerNotFoundError => 0x029,
+            SpxError::InvalidLanguage => 0x02A,
+            SpxError::UnsupportedApiError => 0x02B,
+            SpxError::RingbufferDataUnavailable => 0x02C,
+            SpxError::UnexpectedConversationSiteFailure => 0x030,
+            SpxError::UnexpectedConversationTranslatorSiteFailure => 0x031,
+            SpxError::Canceled => 0x032,
+            SpxError::Unknown(code) => *code,
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    /// Returns name of the native constant (e.g. SPXERR_TIMEOUT),
+    /// or name of the cancellation error code for cancellation-only variants.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpxError::NotImpl => "SPXERR_NOT_IMPL",
+            SpxError::Uninitialized => "SPXERR_UNINITIALIZED",
+            SpxError::AlreadyInitialized => "SPXERR_ALREADY_INITIALIZED",
+            SpxError::UnhandledException => "SPXERR_UNHANDLED_EXCEPTION",
+            SpxError::NotFound => "SPXERR_NOT_FOUND",
+            SpxError::InvalidArg => "SPXERR_INVALID_ARG",
+            SpxError::Timeout => "SPXERR_TIMEOUT",
+            SpxError::AlreadyInProgress => "SPXERR_ALREADY_IN_PROGRESS",
+            SpxError::FileOpenFailed => "SPXERR_FILE_OPEN_FAILED",
+            SpxError::UnexpectedEof => "SPXERR_UNEXPECTED_EOF",
+            SpxError::InvalidHeader => "SPXERR_INVALID_HEADER",
+            SpxError::AudioIsPumping => "SPXERR_AUDIO_IS_PUMPING",
+            SpxError::UnsupportedFormat => "SPXERR_UNSUPPORTED_FORMAT",
+            SpxError::Abort => "SPXERR_ABORT",
+            SpxError::MicNotAvailable => "SPXERR_MIC_NOT_AVAILABLE",
+            SpxError::InvalidState => "SPXERR_INVALID_STATE",
+            SpxError::UuidCreateFailed => "SPXERR_UUID_CREATE_FAILED",
+            SpxError::SetFormatUnexpectedStateTransition => {
+                "SPXERR_SETFORMAT_UNEXPECTED_STATE_TRANSITION"
+            }
+            SpxError::ProcessAudioInvalidState => "SPXERR_PROCESS_AUDIO_INVALID_STATE",
+            SpxError::StartRecognizingInvalidStateTransition => {
+                "SPXERR_START_RECOGNIZING_INVALID_STATE_TRANSITION"
+            }
+            SpxError::UnexpectedCreateObjectFailure => "SPXERR_UNEXPECTED_CREATE_OBJECT_FAILURE",
+            SpxError::MicError => "SPXERR_MIC_ERROR",
+            SpxError::NoAudioInput => "SPXERR_NO_AUDIO_INPUT",
+            SpxError::UnexpectedUspSiteFailure => "SPXERR_UNEXPECTED_USP_SITE_FAILURE",
+            SpxError::UnexpectedUnidecSiteFailure => "SPXERR_UNEXPECTED_UNIDEC_SITE_FAILURE",
+            SpxError::BufferTooSmall => "SPXERR_BUFFER_TOO_SMALL",
+            SpxError::OutOfMemory => "SPXERR_OUT_OF_MEMORY",
+            SpxError::RuntimeError => "SPXERR_RUNTIME_ERROR",
+            SpxError::InvalidUrl => "SPXERR_INVALID_URL",
+            SpxError::InvalidRegion => "SPXERR_INVALID_REGION",
+            SpxError::SwitchModeNotAllowed => "SPXERR_SWITCH_MODE_NOT_ALLOWED",
+            SpxError::ChangeConnectionStatusNotAllowed => {
+                "SPXERR_CHANGE_CONNECTION_STATUS_NOT_ALLOWED"
+            }
+            SpxError::ExplicitConnectionNotSupportedByRecognizer => {
+                "SPXERR_EXPLICIT_CONNECTION_NOT_SUPPORTED_BY_RECOGNIZER"
+            }
+            SpxError::InvalidHandle => "SPXERR_INVALID_HANDLE",
+            SpxError::InvalidRecognizer => "SPXERR_INVALID_RECOGNIZER",
+            SpxError::OutOfRange => "SPXERR_OUT_OF_RANGE",
+            SpxError::ExtensionLibraryNotFound => "SPXERR_EXTENSION_LIBRARY_NOT_FOUND",
+            SpxError::UnexpectedTtsEngineSiteFailure => "SPXERR_UNEXPECTED_TTS_ENGINE_SITE_FAILURE",
+            SpxError::UnexpectedAudioOutputFailure => "SPXERR_UNEXPECTED_AUDIO_OUTPUT_FAILURE",
+            SpxError::GstreamerInternalError => "SPXERR_GSTREAMER_INTERNAL_ERROR",
+            SpxError::ContainerFormatNotSupportedError => {
+                "SPXERR_CONTAINER_FORMAT_NOT_SUPPORTED_ERROR"
+            }
+            SpxError::GstreamerNotFoundError => "SPXERR_GSTREAMER_NOT_FOUND_ERROR",
+            SpxError::InvalidLanguage => "SPXERR_INVALID_LANGUAGE",
+            SpxError::UnsupportedApiError => "SPXERR_UNSUPPORTED_API_ERROR",
+            SpxError::RingbufferDataUnavailable => "SPXERR_RINGBUFFER_DATA_UNAVAILABLE",
+            SpxError::UnexpectedConversationSiteFailure => {
+                "SPXERR_UNEXPECTED_CONVERSATION_SITE_FAILURE"
+            }
+            SpxError::UnexpectedConversationTranslatorSiteFailure => {
+                "SPXERR_UNEXPECTED_CONVERSATION_TRANSLATOR_SITE_FAILURE"
+            }
+            SpxError::Canceled => "SPXERR_CANCELED",
+            SpxError::Unknown(_) => "UNKNOWN SPXERR",
+            SpxError::AuthenticationFailure => "AuthenticationFailure",
+            SpxError::BadRequest => "BadRequest",
+            SpxError::TooManyRequests => "TooManyRequests",
+            SpxError::Forbidden => "Forbidden",
+            SpxError::ConnectionFailure => "ConnectionFailure",
+            SpxError::ServiceTimeout => "ServiceTimeout",
+            SpxError::ServiceError => "ServiceError",
+            SpxError::ServiceUnavailable => "ServiceUnavailable",
+            SpxError::CanceledRuntimeError => "RuntimeError",
+        }
+    }
+
+    /// True if the same operation may succeed when retried later
+    /// (timeouts, connection problems, throttling, service unavailability).
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            SpxError::Timeout
+                | SpxError::UnexpectedUspSiteFailure
+                | SpxError::RingbufferDataUnavailable
+                | SpxError::TooManyRequests
+                | SpxError::ConnectionFailure
+                | SpxError::ServiceTimeout
+                | SpxError::ServiceUnavailable
+        )
+    }
+
+    /// True if subscription key or authorization token is invalid, expired,
+    /// or does not match the region being used.
+    pub fn is_auth(&self) -> bool {
+        matches!(self, SpxError::AuthenticationFailure)
+    }
+}
+
+impl std::fmt::Display for SpxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpxError::Unknown(code) => write!(f, "UNKNOWN SPXERR ({:#x})", code),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// Native Speech SDK library or one of its functions could not be resolved
+/// at runtime (feature *runtime-loading*). Reason reported by the loader
+/// is available in *Error.native_message*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibraryLoadError {
+    LibraryNotFound(String),
+    SymbolNotFound(String),
+}
+
+impl std::fmt::Display for LibraryLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryLoadError::LibraryNotFound(library) => {
+                write!(f, "Speech SDK library {} not loaded", library)
+            }
+            LibraryLoadError::SymbolNotFound(symbol) => {
+                write!(f, "Speech SDK function {} not found", symbol)
+            }
+        }
+    }
+}
+
 /// Enumeration of error root causes. Where appropriate
 /// it wraps underlying error.
 #[derive(Debug)]
 pub enum ErrorRootCause {
-    ApiError(usize),
+    ApiError(SpxError),
     FfiNulError(NulError),
     InvalidCString,
     FromUtf8Error(FromUtf8Error),
     Utf8Error(Utf8Error),
     TryFromIntError(TryFromIntError),
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    TomlError(Box<toml::de::Error>),
+    LibraryLoadError(LibraryLoadError),
 }
 
 /// Error struct represents error than can occur
@@ -26,6 +363,10 @@ pub enum ErrorRootCause {
 pub struct Error {
     pub message: String,
     pub caused_by: ErrorRootCause,
+    /// Name of the failed operation (e.g. *SpeechRecognizer.recognize_once_async*).
+    pub operation: Option<String>,
+    /// Error message as reported by native library or cancellation details.
+    pub native_message: Option<String>,
 }
 
 impl std::error::Error for Error {}
@@ -39,72 +380,86 @@ impl std::fmt::Display for Error {
 impl Error {
     /// Creates new error from custom message and underlying root cause.
     pub fn new(message: String, caused_by: ErrorRootCause) -> Self {
-        Error { message, caused_by }
+        Error {
+            message,
+            caused_by,
+            operation: None,
+            native_message: None,
+        }
+    }
+
+    /// Creates new API error for given operation, native message and error code.
+    /// Trailing " error" of *operation* (e.g. *SpeechRecognizer.recognize_once_async error*)
+    /// is kept in the message only.
+    pub fn api_error(operation: &str, native_message: &str, spx_error: SpxError) -> Self {
+        Error {
+            message: operation.to_string() + ": " + native_message,
+            caused_by: ErrorRootCause::ApiError(spx_error),
+            operation: Some(operation_name(operation)),
+            native_message: Some(native_message.to_owned()),
+        }
+    }
+
+    /// Creates new error from cancellation error code and cancellation details.
+    /// Returns None for *CancellationErrorCode::NoError*.
+    pub fn from_cancellation(
+        operation: &str,
+        error_code: &CancellationErrorCode,
+        error_details: &str,
+    ) -> Option<Self> {
+        SpxError::from_cancellation_error_code(error_code)
+            .map(|spx_error| Error::api_error(operation, error_details, spx_error))
+    }
+
+    /// Creates new error for native library or function which could not be loaded.
+    pub fn library_load_error(
+        operation: &str,
+        load_error: LibraryLoadError,
+        details: &str,
+    ) -> Self {
+        Error {
+            message: format!("{}: {}: {}", operation, load_error, details),
+            caused_by: ErrorRootCause::LibraryLoadError(load_error),
+            operation: Some(operation_name(operation)),
+            native_message: Some(details.to_owned()),
+        }
+    }
+
+    /// Returns typed API error if this error originates from native library
+    /// or from canceled recognition/synthesis.
+    pub fn spx_error(&self) -> Option<SpxError> {
+        match self.caused_by {
+            ErrorRootCause::ApiError(spx_error) => Some(spx_error),
+            _ => None,
+        }
+    }
+
+    /// See *SpxError::is_transient*.
+    pub fn is_transient(&self) -> bool {
+        self.spx_error().is_some_and(|e| e.is_transient())
+    }
+
+    /// See *SpxError::is_auth*.
+    pub fn is_auth(&self) -> bool {
+        self.spx_error().is_some_and(|e| e.is_auth())
     }
 
     /// Returns description based on API error code.
     pub fn api_error_desc(error_root_cause: &ErrorRootCause) -> Option<String> {
         match error_root_cause {
-            ErrorRootCause::ApiError(api_code) => {
-                let api_result_desc = match api_code {
-                    0x000 => "SPX_NOERROR",
-                    0xfff => "SPXERR_NOT_IMPL",
-                    0x001 => "SPXERR_UNINITIALIZED",
-                    0x002 => "SPXERR_ALREADY_INITIALIZED",
-                    0x003 => "SPXERR_UNHANDLED_EXCEPTION",
-                    0x004 => "SPXERR_NOT_FOUND",
-                    0x005 => "SPXERR_INVALID_ARG",
-                    0x006 => "SPXERR_TIMEOUT",
-                    0x007 => "SPXERR_ALREADY_IN_PROGRESS",
-                    0x008 => "SPXERR_FILE_OPEN_FAILED",
-                    0x009 => "SPXERR_UNEXPECTED_EOF",
-                    0x00a => "SPXERR_INVALID_HEADER",
-                    0x00b => "SPXERR_AUDIO_IS_PUMPING",
-                    0x00c => "SPXERR_UNSUPPORTED_FORMAT",
-                    0x00d => "SPXERR_ABORT",
-                    0x00e => "SPXERR_MIC_NOT_AVAILABLE",
-                    0x00f => "SPXERR_INVALID_STATE",
-                    0x010 => "SPXERR_UUID_CREATE_FAILED",
-                    0x011 => "SPXERR_SETFORMAT_UNEXPECTED_STATE_TRANSITION",
-                    0x012 => "SPXERR_PROCESS_AUDIO_INVALID_STATE",
-                    0x013 => "SPXERR_START_RECOGNIZING_INVALID_STATE_TRANSITION",
-                    0x014 => "SPXERR_UNEXPECTED_CREATE_OBJECT_FAILURE",
-                    0x015 => "SPXERR_MIC_ERROR",
-                    0x016 => "SPXERR_NO_AUDIO_INPUT",
-                    0x017 => "SPXERR_UNEXPECTED_USP_SITE_FAILURE",
-                    0x018 => "SPXERR_UNEXPECTED_UNIDEC_SITE_FAILURE",
-                    0x019 => "SPXERR_BUFFER_TOO_SMALL",
-                    0x01A => "SPXERR_OUT_OF_MEMORY",
-                    0x01B => "SPXERR_RUNTIME_ERROR",
-                    0x01C => "SPXERR_INVALID_URL",
-                    0x01D => "SPXERR_INVALID_REGION",
-                    0x01E => "SPXERR_SWITCH_MODE_NOT_ALLOWED",
-                    0x01F => "SPXERR_CHANGE_CONNECTION_STATUS_NOT_ALLOWED",
-                    0x020 => "SPXERR_EXPLICIT_CONNECTION_NOT_SUPPORTED_BY_RECOGNIZER",
-                    0x021 => "SPXERR_INVALID_HANDLE",
-                    0x022 => "SPXERR_INVALID_RECOGNIZER",
-                    0x023 => "SPXERR_OUT_OF_RANGE",
-                    0x024 => "SPXERR_EXTENSION_LIBRARY_NOT_FOUND",
-                    0x025 => "SPXERR_UNEXPECTED_TTS_ENGINE_SITE_FAILURE",
-                    0x026 => "SPXERR_UNEXPECTED_AUDIO_OUTPUT_FAILURE",
-                    0x027 => "SPXERR_GSTREAMER_INTERNAL_ERROR",
-                    0x028 => "SPXERR_CONTAINER_FORMAT_NOT_SUPPORTED_ERROR",
-                    0x029 => "SPXERR_GSTREAMER_NOT_FOUND_ERROR",
-                    0x02A => "SPXERR_INVALID_LANGUAGE",
-                    0x02B => "SPXERR_UNSUPPORTED_API_ERROR",
-                    0x02C => "SPXERR_RINGBUFFER_DATA_UNAVAILABLE",
-                    0x030 => "SPXERR_UNEXPECTED_CONVERSATION_SITE_FAILURE",
-                    0x031 => "SPXERR_UNEXPECTED_CONVERSATION_TRANSLATOR_SITE_FAILURE",
-                    0x032 => "SPXERR_CANCELED",
-                    _ => "UNKNOWN SPXERR",
-                };
-                Some(api_result_desc.to_owned())
-            }
+            ErrorRootCause::ApiError(spx_error) => Some(spx_error.name().to_owned()),
             _ => None,
         }
     }
 }
 
+fn operation_name(operation: &str) -> String {
+    operation
+        .strip_suffix(" error")
+        .unwrap_or(operation)
+        .to_owned()
+}
+
 /// Convenience type so that we can use *Result&lt;T&gt;*
 /// instead of *Result<T, E>*.
 pub type Result<T> = result::Result<T, Error>;
@@ -114,6 +469,12 @@ pub type Result<T> = result::Result<T, Error>;
 #[inline(always)]
 pub fn convert_err(hr: usize, err_msg: &str) -> Result<()> {
     if hr != SPX_NOERROR as usize {
+        #[cfg(feature = "runtime-loading")]
+        if hr == crate::runtime::MISSING_SYMBOL_HR {
+            if let Some((load_error, details)) = crate::runtime::take_load_error() {
+                return Err(Error::library_load_error(err_msg, load_error, &details));
+            }
+        }
         unsafe {
             let error_handle = hr as AZAC_HANDLE;
             let code = error_get_error_code(error_handle);
@@ -123,9 +484,10 @@ pub fn convert_err(hr: usize, err_msg: &str) -> Result<()> {
 
             error_release(error_handle);
 
-            Err(Error::new(
-                err_msg.to_string() + ": " + message,
-                ErrorRootCause::ApiError(code),
+            Err(Error::api_error(
+                err_msg,
+                message,
+                SpxError::from_code(code),
             ))
         }
     } else {
@@ -138,6 +500,8 @@ impl From<NulError> for Error {
         Error {
             message: format!("std::ffi::NulError: {}", error),
             caused_by: ErrorRootCause::FfiNulError(error),
+            operation: None,
+            native_message: None,
         }
     }
 }
@@ -147,6 +511,8 @@ impl From<FromUtf8Error> for Error {
         Error {
             message: format!("std::string::FromUtf8Error: {}", error),
             caused_by: ErrorRootCause::FromUtf8Error(error),
+            operation: None,
+            native_message: None,
         }
     }
 }
@@ -156,6 +522,8 @@ impl From<Utf8Error> for Error {
         Error {
             message: format!("std::str::Utf8Error: {}", error),
             caused_by: ErrorRootCause::Utf8Error(error),
+            operation: None,
+            native_message: None,
         }
     }
 }
@@ -165,6 +533,41 @@ impl From<TryFromIntError> for Error {
         Error {
             message: format!("std::num::TryFromIntError: {}", error),
             caused_by: ErrorRootCause::TryFromIntError(error),
+            operation: None,
+            native_message: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error {
+            message: format!("std::io::Error: {}", error),
+            caused_by: ErrorRootCause::IoError(error),
+            operation: None,
+            native_message: None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Error {
+        Error {
+            message: format!("serde_json::Error: {}", error),
+            caused_by: ErrorRootCause::JsonError(error),
+            operation: None,
+            native_message: None,
+        }
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(error: toml::de::Error) -> Error {
+        Error {
+            message: format!("toml::de::Error: {}", error),
+            caused_by: ErrorRootCause::TomlError(Box::new(error)),
+            operation: None,
+            native_message: None,
         }
     }
 }
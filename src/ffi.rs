@@ -1,7 +1,14 @@
 //! Package ffi contains bindings to underlying C API and thin abstraction for managing C handles.
 #![allow(warnings)]
 #![allow(unaligned_references)]
+#[cfg(all(not(feature = "runtime-loading"), not(speech_sdk_out_dir_bindings)))]
 include!("ffi/bindings.rs");
+// bindings generated from locally provisioned SDK, see build.rs
+#[cfg(all(not(feature = "runtime-loading"), speech_sdk_out_dir_bindings))]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+// native functions resolved at runtime, see build.rs and runtime module
+#[cfg(feature = "runtime-loading")]
+include!(concat!(env!("OUT_DIR"), "/bindings_runtime.rs"));
 
 // manual entry as API v1.21.0 is using this types as #define so bindings.rs does not contains them
 pub type SPXPROPERTYBAGHANDLE = AZAC_HANDLE;
@@ -10,6 +10,7 @@ pub mod common;
 pub mod dialog;
 pub mod error;
 pub mod ffi;
+pub mod runtime;
 pub mod speech;
 
 #[cfg(test)]
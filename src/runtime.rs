@@ -0,0 +1,148 @@
+//! Package runtime provides information about native Speech SDK library.
+//!
+//! By default the native library is linked at build time and binaries fail to start
+//! on hosts where it is not installed. With feature *runtime-loading* the library is
+//! loaded when first needed instead: from path set by *set_library_path*, from path
+//! in *MS_COG_SVC_SPEECH_SDK_LIBRARY* environment variable or from the library search path.
+//! If the library (or a function not present in older library versions) is missing,
+//! API calls return *Error* with *ErrorRootCause::LibraryLoadError*.
+#[cfg(feature = "runtime-loading")]
+use crate::error::{Error, LibraryLoadError, Result};
+#[cfg(feature = "runtime-loading")]
+use crate::ffi::AZACHR;
+#[cfg(feature = "runtime-loading")]
+use libloading::Library;
+#[cfg(feature = "runtime-loading")]
+use std::cell::RefCell;
+#[cfg(feature = "runtime-loading")]
+use std::ffi::OsString;
+#[cfg(feature = "runtime-loading")]
+use std::path::Path;
+#[cfg(feature = "runtime-loading")]
+use std::sync::{Mutex, OnceLock};
+
+/// Returns version of Speech SDK this library was built against.
+/// Native C API does not report its own version, with *runtime-loading*
+/// make sure the loaded library is of the same (or newer) version.
+pub fn speech_sdk_version() -> &'static str {
+    env!("MS_COG_SVC_SPEECH_SDK_VERSION")
+}
+
+/// Checks whether native Speech SDK library can be used.
+/// Without *runtime-loading* feature the library is linked and this always returns true.
+#[cfg(not(feature = "runtime-loading"))]
+pub fn speech_sdk_available() -> bool {
+    true
+}
+
+/// Checks whether native Speech SDK library can be used, loading it if not yet loaded.
+#[cfg(feature = "runtime-loading")]
+pub fn speech_sdk_available() -> bool {
+    library().is_ok()
+}
+
+#[cfg(feature = "runtime-loading")]
+const LIBRARY_PATH_ENV: &str = "MS_COG_SVC_SPEECH_SDK_LIBRARY";
+
+#[cfg(all(feature = "runtime-loading", target_os = "linux"))]
+const DEFAULT_LIBRARY: &str = "libMicrosoft.CognitiveServices.Speech.core.so";
+#[cfg(all(feature = "runtime-loading", target_os = "macos"))]
+const DEFAULT_LIBRARY: &str =
+    "MicrosoftCognitiveServicesSpeech.framework/MicrosoftCognitiveServicesSpeech";
+#[cfg(all(feature = "runtime-loading", target_os = "windows"))]
+const DEFAULT_LIBRARY: &str = "Microsoft.CognitiveServices.Speech.core.dll";
+
+/// Value returned by native functions returning error code when library or function is missing.
+#[cfg(feature = "runtime-loading")]
+pub(crate) const MISSING_SYMBOL_HR: AZACHR = AZACHR::MAX;
+
+#[cfg(feature = "runtime-loading")]
+static LIBRARY_PATH: Mutex<Option<OsString>> = Mutex::new(None);
+
+#[cfg(feature = "runtime-loading")]
+static LIBRARY: OnceLock<std::result::Result<Library, LoadFailure>> = OnceLock::new();
+
+/// Load error along with reason reported by the loader.
+#[cfg(feature = "runtime-loading")]
+type LoadFailure = (LibraryLoadError, String);
+
+#[cfg(feature = "runtime-loading")]
+thread_local! {
+    static LOAD_ERROR: RefCell<Option<LoadFailure>> = const { RefCell::new(None) };
+}
+
+/// Sets path of native library. Must be called before the library is used for the first time.
+#[cfg(feature = "runtime-loading")]
+pub fn set_library_path<P: AsRef<Path>>(path: P) -> Result<()> {
+    let mut library_path = LIBRARY_PATH.lock().unwrap();
+    if LIBRARY.get().is_some() {
+        return Err(Error::library_load_error(
+            "runtime::set_library_path error",
+            LibraryLoadError::LibraryNotFound(path.as_ref().display().to_string()),
+            "library path can be set only before first use",
+        ));
+    }
+    *library_path = Some(path.as_ref().as_os_str().to_owned());
+    Ok(())
+}
+
+/// Loads native library (if not yet loaded) and returns error if it cannot be loaded.
+#[cfg(feature = "runtime-loading")]
+pub fn load_speech_sdk() -> Result<()> {
+    library().map(|_| ()).map_err(|(load_error, details)| {
+        Error::library_load_error("runtime::load_speech_sdk error", load_error, &details)
+    })
+}
+
+#[cfg(feature = "runtime-loading")]
+fn library() -> std::result::Result<&'static Library, LoadFailure> {
+    LIBRARY
+        .get_or_init(|| {
+            let path = LIBRARY_PATH
+                .lock()
+                .unwrap()
+                .clone()
+                .or_else(|| std::env::var_os(LIBRARY_PATH_ENV))
+                .unwrap_or_else(|| DEFAULT_LIBRARY.into());
+            // library stays loaded for the lifetime of the process,
+            // resolved function pointers are cached
+            unsafe { Library::new(&path) }.map_err(|err| {
+                (
+                    LibraryLoadError::LibraryNotFound(path.to_string_lossy().into_owned()),
+                    err.to_string(),
+                )
+            })
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Resolves native function, *name* must be nul terminated.
+#[cfg(feature = "runtime-loading")]
+pub(crate) fn native_symbol<T: Copy>(name: &[u8]) -> Option<T> {
+    let library = library().ok()?;
+    unsafe { library.get::<T>(name) }.ok().map(|symbol| *symbol)
+}
+
+/// Records why native function *name* is not available so that
+/// *convert_err* can report it. Called on every call of missing function.
+#[cfg(feature = "runtime-loading")]
+pub(crate) fn missing_symbol(name: &[u8]) {
+    let symbol = String::from_utf8_lossy(name.strip_suffix(b"\0").unwrap_or(name)).into_owned();
+    let failure = match library() {
+        Err(failure) => failure,
+        Ok(_) => (
+            LibraryLoadError::SymbolNotFound(symbol),
+            format!(
+                "function not exported by loaded library, Speech SDK {} or newer is required",
+                speech_sdk_version()
+            ),
+        ),
+    };
+    LOAD_ERROR.with(|error| *error.borrow_mut() = Some(failure));
+}
+
+#[cfg(feature = "runtime-loading")]
+pub(crate) fn take_load_error() -> Option<LoadFailure> {
+    LOAD_ERROR.with(|error| error.borrow_mut().take())
+}
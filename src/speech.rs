@@ -1,19 +1,31 @@
 //! Package speech provides functionality for speech recognizers along with their related configuration and event objects.
+mod audio_chunk;
 mod audio_data_stream;
 mod auto_detect_source_language_config;
+mod auto_detect_source_language_result;
 mod cancellation_details;
+mod connection_event;
 mod embedded_speech_config;
 mod grammar_phrase;
 mod keyword_recognition_model;
+mod multichannel_recognizer;
 mod phrase_list_grammar;
 mod recognition_event;
+mod recognition_telemetry;
+mod recognition_tuning;
+mod reconnect_policy;
+mod reconnected_event;
+mod reconnecting_event;
+mod resilient_recognizer;
 mod session_event;
 mod source_language_config;
+mod source_language_recognizer;
 mod speech_config;
 mod speech_recognition_canceled_event;
 mod speech_recognition_event;
 mod speech_recognition_result;
 mod speech_recognizer;
+mod speech_settings;
 mod speech_synthesis_bookmark_event;
 mod speech_synthesis_event;
 mod speech_synthesis_request;
@@ -21,24 +33,44 @@ mod speech_synthesis_result;
 mod speech_synthesis_viseme_event;
 mod speech_synthesis_word_boundary_event;
 mod speech_synthesizer;
+mod synthesis_metrics;
+mod synthesis_stream;
 mod synthesis_voices_result;
+mod text_chunker;
+mod text_stream_synthesis;
+mod voice_catalog;
+mod voice_descriptor;
 mod voice_info;
 
 // re-export structs directly under speech module
+pub use self::audio_chunk::AudioChunk;
 pub use self::audio_data_stream::AudioDataStream;
 pub use self::auto_detect_source_language_config::AutoDetectSourceLanguageConfig;
+pub use self::auto_detect_source_language_result::AutoDetectSourceLanguageResult;
 pub use self::cancellation_details::CancellationDetails;
+pub use self::connection_event::{ConnectionEvent, ConnectionState, DisconnectReason};
 pub use self::embedded_speech_config::EmbeddedSpeechConfig;
 pub use self::keyword_recognition_model::KeywordRecognitionModel;
+pub use self::multichannel_recognizer::{
+    MultichannelMode, MultichannelRecognizer, TranscriptSegment,
+};
 pub use self::phrase_list_grammar::PhraseListGrammar;
 pub use self::recognition_event::RecognitionEvent;
+pub use self::recognition_telemetry::{MetricsSink, RecognitionMetric};
+pub use self::recognition_tuning::RecognitionTuning;
+pub use self::reconnect_policy::ReconnectPolicy;
+pub use self::reconnected_event::ReconnectedEvent;
+pub use self::reconnecting_event::ReconnectingEvent;
+pub use self::resilient_recognizer::ResilientRecognizer;
 pub use self::session_event::SessionEvent;
 pub use self::source_language_config::SourceLanguageConfig;
+pub use self::source_language_recognizer::SourceLanguageRecognizer;
 pub use self::speech_config::SpeechConfig;
 pub use self::speech_recognition_canceled_event::SpeechRecognitionCanceledEvent;
 pub use self::speech_recognition_event::SpeechRecognitionEvent;
 pub use self::speech_recognition_result::SpeechRecognitionResult;
 pub use self::speech_recognizer::SpeechRecognizer;
+pub use self::speech_settings::{EmbeddedSettings, ProxySettings, SpeechSettings};
 pub use self::speech_synthesis_bookmark_event::SpeechSynthesisBookmarkEvent;
 pub use self::speech_synthesis_event::SpeechSynthesisEvent;
 pub use self::speech_synthesis_request::SpeechSynthesisRequest;
@@ -46,5 +78,11 @@ pub use self::speech_synthesis_result::SpeechSynthesisResult;
 pub use self::speech_synthesis_viseme_event::SpeechSynthesisVisemeEvent;
 pub use self::speech_synthesis_word_boundary_event::SpeechSynthesisWordBoundaryEvent;
 pub use self::speech_synthesizer::SpeechSynthesizer;
+pub use self::synthesis_metrics::SynthesisMetrics;
+pub use self::synthesis_stream::{SynthesisInput, SynthesisStream};
 pub use self::synthesis_voices_result::SynthesisVoicesResult;
+pub use self::text_chunker::{TextChunker, TextChunking};
+pub use self::text_stream_synthesis::{SynthesisCancellation, TextStreamSynthesis};
+pub use self::voice_catalog::{VoiceCatalog, VoiceQuery};
+pub use self::voice_descriptor::VoiceDescriptor;
 pub use self::voice_info::VoiceInfo;
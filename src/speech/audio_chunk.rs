@@ -0,0 +1,103 @@
+use crate::common::SpeechSynthesisBoundaryType;
+use crate::speech::SynthesisMetrics;
+use std::fmt;
+use std::time::Duration;
+
+/// Item of *SynthesisStream*. Audio is interleaved with word boundary, viseme
+/// and bookmark events in the order they were reported by the synthesizer.
+/// Audio offsets of the events are in ticks (100 nanoseconds).
+#[derive(Clone, PartialEq)]
+pub enum AudioChunk {
+    Audio {
+        data: Vec<u8>,
+        /// Offset of the first byte of *data* within synthesized audio, in bytes.
+        byte_offset: u64,
+        /// Offset of the first byte of *data* in ticks, comparable with *audio_offset*
+        /// of the events. None unless the output format is uncompressed and known
+        /// (see *SpeechSynthesizer::output_format*).
+        audio_offset: Option<u64>,
+    },
+    WordBoundary {
+        audio_offset: u64,
+        duration: Duration,
+        text_offset: u32,
+        word_length: u32,
+        boundary_type: SpeechSynthesisBoundaryType,
+        text: String,
+    },
+    Viseme {
+        audio_offset: u64,
+        viseme_id: u32,
+        animation: String,
+    },
+    Bookmark {
+        audio_offset: u64,
+        text: String,
+    },
+    /// Always the last item of successful synthesis.
+    Completed {
+        /// Total length of synthesized audio in bytes.
+        audio_length: u64,
+        audio_duration: Duration,
+        metrics: SynthesisMetrics,
+    },
+}
+
+// audio data is truncated similarly to SpeechSynthesisResult
+impl fmt::Debug for AudioChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioChunk::Audio {
+                data,
+                byte_offset,
+                audio_offset,
+            } => f
+                .debug_struct("Audio")
+                .field("len", &data.len())
+                .field("byte_offset", byte_offset)
+                .field("audio_offset", audio_offset)
+                .finish(),
+            AudioChunk::WordBoundary {
+                audio_offset,
+                duration,
+                text_offset,
+                word_length,
+                boundary_type,
+                text,
+            } => f
+                .debug_struct("WordBoundary")
+                .field("audio_offset", audio_offset)
+                .field("duration", duration)
+                .field("text_offset", text_offset)
+                .field("word_length", word_length)
+                .field("boundary_type", boundary_type)
+                .field("text", text)
+                .finish(),
+            AudioChunk::Viseme {
+                audio_offset,
+                viseme_id,
+                animation,
+            } => f
+                .debug_struct("Viseme")
+                .field("audio_offset", audio_offset)
+                .field("viseme_id", viseme_id)
+                .field("animation", animation)
+                .finish(),
+            AudioChunk::Bookmark { audio_offset, text } => f
+                .debug_struct("Bookmark")
+                .field("audio_offset", audio_offset)
+                .field("text", text)
+                .finish(),
+            AudioChunk::Completed {
+                audio_length,
+                audio_duration,
+                metrics,
+            } => f
+                .debug_struct("Completed")
+                .field("audio_length", audio_length)
+                .field("audio_duration", audio_duration)
+                .field("metrics", metrics)
+                .finish(),
+        }
+    }
+}
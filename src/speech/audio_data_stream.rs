@@ -1,21 +1,28 @@
 use crate::common::{PropertyCollection, StreamStatus};
-use crate::error::{convert_err, Error, ErrorRootCause, Result};
+use crate::error::{convert_err, Error, ErrorRootCause, Result, SpxError};
 use crate::ffi::{
     audio_data_stream_can_read_data, audio_data_stream_can_read_data_from_position,
     audio_data_stream_create_from_file, audio_data_stream_create_from_result,
+    audio_data_stream_detach_input, audio_data_stream_get_available_size,
     audio_data_stream_get_position, audio_data_stream_get_property_bag,
     audio_data_stream_get_status, audio_data_stream_read, audio_data_stream_read_from_position,
     audio_data_stream_save_to_wave_file, audio_data_stream_set_position, audio_stream_release,
     SmartHandle, SPXAUDIOSTREAMHANDLE, SPXPROPERTYBAGHANDLE,
 };
-use crate::speech::SpeechSynthesisResult;
+use crate::speech::{CancellationDetails, SpeechSynthesisResult};
+use std::convert::TryFrom;
 use std::ffi::CString;
+use std::io::{self, Read, Seek, SeekFrom};
 use std::mem::MaybeUninit;
 
 /// AudioDataStream represents audio data retrieved either from file
 /// or result of speech synthesis. Represents convenient option for
 /// manipulating and storing of syntehtized audio data
 /// Added in version 1.17.0
+///
+/// Implements *std::io::Read* and *std::io::Seek* so it can be passed to
+/// *std::io::copy* or audio decoders. Read of canceled stream fails with
+/// *io::Error* wrapping *CancellationDetails* (see *cancellation_details*).
 #[derive(Debug)]
 pub struct AudioDataStream {
     pub handle: SmartHandle<SPXAUDIOSTREAMHANDLE>,
@@ -94,7 +101,7 @@ impl AudioDataStream {
         unsafe {
             #[allow(clippy::len_zero)]
             if buffer.len() == 0 {
-                let rootc = ErrorRootCause::ApiError(0x005);
+                let rootc = ErrorRootCause::ApiError(SpxError::InvalidArg);
                 return Err(Error::new(Error::api_error_desc(&rootc).unwrap(), rootc));
             }
             let mut filled_size: u32 = 0;
@@ -117,7 +124,7 @@ impl AudioDataStream {
         unsafe {
             #[allow(clippy::len_zero)]
             if buffer.len() == 0 {
-                let rootc = ErrorRootCause::ApiError(0x005);
+                let rootc = ErrorRootCause::ApiError(SpxError::InvalidArg);
                 return Err(Error::new(Error::api_error_desc(&rootc).unwrap(), rootc));
             }
             let mut filled_size: u32 = 0;
@@ -160,4 +167,123 @@ impl AudioDataStream {
             Ok(())
         }
     }
+
+    /// Returns position as u64, see *get_offset*.
+    pub fn position(&self) -> Result<u64> {
+        Ok(u64::from(self.get_offset()?))
+    }
+
+    /// Sets position as u64, fails if position exceeds range supported by native stream.
+    pub fn set_position(&self, position: u64) -> Result<()> {
+        self.set_offset(u32::try_from(position)?)
+    }
+
+    /// Returns size of data available in the stream from current position.
+    pub fn get_available_size(&self) -> u32 {
+        unsafe { audio_data_stream_get_available_size(self.handle.inner()) }
+    }
+
+    /// Stops any more data from getting to the stream, e.g. when synthesis
+    /// is no longer needed.
+    pub fn detach_input(&self) -> Result<()> {
+        unsafe {
+            let ret = audio_data_stream_detach_input(self.handle.inner());
+            convert_err(ret, "AudioDataStream.detach_input error")?;
+            Ok(())
+        }
+    }
+
+    /// Returns cancellation details if the stream was canceled, None otherwise.
+    pub fn cancellation_details(&self) -> Result<Option<CancellationDetails>> {
+        if let StreamStatus::StreamStatusCanceled = self.get_status()? {
+            Ok(Some(CancellationDetails::from_audio_data_stream(self)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn io_read(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+        let filled_size = AudioDataStream::read(self, buffer).map_err(to_io_error)?;
+        if filled_size == 0 {
+            if let Some(details) = self.cancellation_details().map_err(to_io_error)? {
+                return Err(io::Error::other(details));
+            }
+        }
+        Ok(filled_size as usize)
+    }
+
+    fn io_seek(&self, pos: SeekFrom) -> io::Result<u64> {
+        let base = match pos {
+            SeekFrom::Start(position) => {
+                self.set_position(position).map_err(to_io_error)?;
+                return Ok(position);
+            }
+            SeekFrom::Current(_) => self.position().map_err(to_io_error)?,
+            SeekFrom::End(_) => {
+                // length is only known once all data arrived
+                match self.get_status().map_err(to_io_error)? {
+                    StreamStatus::StreamStatusAllData => {
+                        self.position().map_err(to_io_error)? + u64::from(self.get_available_size())
+                    }
+                    _ => {
+                        return Err(io::Error::other(
+                            "AudioDataStream.seek error: end of stream is not known yet",
+                        ))
+                    }
+                }
+            }
+        };
+        let delta = match pos {
+            SeekFrom::Current(delta) | SeekFrom::End(delta) => delta,
+            SeekFrom::Start(_) => 0,
+        };
+        let position = if delta >= 0 {
+            base.checked_add(delta as u64)
+        } else {
+            base.checked_sub(delta.unsigned_abs())
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "AudioDataStream.seek error: invalid seek position",
+            )
+        })?;
+        self.set_position(position).map_err(to_io_error)?;
+        Ok(position)
+    }
+}
+
+fn to_io_error(err: Error) -> io::Error {
+    let kind = match err.caused_by {
+        ErrorRootCause::TryFromIntError(_) => io::ErrorKind::InvalidInput,
+        _ => io::ErrorKind::Other,
+    };
+    io::Error::new(kind, err)
+}
+
+impl Read for AudioDataStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.io_read(buf)
+    }
+}
+
+impl Read for &AudioDataStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.io_read(buf)
+    }
+}
+
+impl Seek for AudioDataStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.io_seek(pos)
+    }
+}
+
+impl Seek for &AudioDataStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.io_seek(pos)
+    }
 }
@@ -0,0 +1,37 @@
+use crate::common::{PropertyCollection, PropertyId};
+use crate::error::Result;
+use crate::speech::{SpeechRecognitionResult, SpeechSynthesisResult};
+
+/// AutoDetectSourceLanguageResult contains language detected by recognizer (or synthesizer)
+/// created with AutoDetectSourceLanguageConfig.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoDetectSourceLanguageResult {
+    /// Detected language in BCP-47 format (e.g. en-US). None when language was not detected.
+    pub language: Option<String>,
+}
+
+impl AutoDetectSourceLanguageResult {
+    /// Reads detected language from result properties. Can be used for any result
+    /// exposing property collection (recognition, translation or synthesis result).
+    pub fn from_properties(properties: &PropertyCollection) -> Result<Self> {
+        let language = properties.get_property(
+            PropertyId::SpeechServiceConnectionAutoDetectSourceLanguageResult,
+            "",
+        )?;
+        Ok(AutoDetectSourceLanguageResult {
+            language: if language.is_empty() {
+                None
+            } else {
+                Some(language)
+            },
+        })
+    }
+
+    pub fn from_speech_recognition_result(result: &SpeechRecognitionResult) -> Result<Self> {
+        AutoDetectSourceLanguageResult::from_properties(&result.properties)
+    }
+
+    pub fn from_speech_synthesis_result(result: &SpeechSynthesisResult) -> Result<Self> {
+        AutoDetectSourceLanguageResult::from_properties(&result.properties)
+    }
+}
@@ -1,11 +1,15 @@
 use crate::common::{CancellationErrorCode, CancellationReason, PropertyId};
-use crate::error::{convert_err, Result};
-use crate::ffi::{synth_result_get_canceled_error_code, synth_result_get_reason_canceled};
-use crate::speech::SpeechSynthesisResult;
+use crate::error::{convert_err, Error, Result};
+use crate::ffi::{
+    audio_data_stream_get_canceled_error_code, audio_data_stream_get_reason_canceled,
+    synth_result_get_canceled_error_code, synth_result_get_reason_canceled,
+};
+use crate::speech::{AudioDataStream, SpeechSynthesisResult};
+use std::fmt;
 
 /// CancellationDetails contains detailed information about why a result was canceled.
 /// Added in version 1.17.0
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CancellationDetails {
     pub reason: CancellationReason,
     pub error_code: CancellationErrorCode,
@@ -15,6 +19,14 @@ pub struct CancellationDetails {
 impl CancellationDetails {
     pub fn from_speech_synthesis_result(
         speech_synthesis_result: SpeechSynthesisResult,
+    ) -> Result<Self> {
+        CancellationDetails::from_speech_synthesis_result_ref(&speech_synthesis_result)
+    }
+
+    /// Same as *from_speech_synthesis_result*, result is only borrowed
+    /// (e.g. when handling *SpeechSynthesisEvent*).
+    pub fn from_speech_synthesis_result_ref(
+        speech_synthesis_result: &SpeechSynthesisResult,
     ) -> Result<Self> {
         unsafe {
             let mut reason = 0;
@@ -48,4 +60,59 @@ impl CancellationDetails {
             })
         }
     }
+
+    /// Returns details of canceled *AudioDataStream*, e.g. when synthesis
+    /// failed while the stream was being read.
+    pub fn from_audio_data_stream(audio_data_stream: &AudioDataStream) -> Result<Self> {
+        unsafe {
+            let mut reason = 0;
+            let mut ret = audio_data_stream_get_reason_canceled(
+                audio_data_stream.handle.inner(),
+                &mut reason,
+            );
+            convert_err(
+                ret,
+                "CancellationDetails::from_audio_data_stream(reason) error",
+            )?;
+
+            let mut error_code = 0;
+            ret = audio_data_stream_get_canceled_error_code(
+                audio_data_stream.handle.inner(),
+                &mut error_code,
+            );
+            convert_err(
+                ret,
+                "CancellationDetails::from_audio_data_stream(error_code) error",
+            )?;
+
+            let error_details = audio_data_stream
+                .properties
+                .get_property(PropertyId::CancellationDetailsReasonDetailedText, "")?;
+
+            Ok(CancellationDetails {
+                reason: reason.into(),
+                error_code: error_code.into(),
+                error_details,
+            })
+        }
+    }
+
+    /// Converts cancellation into Error so that it can be handled
+    /// the same way as errors returned by native API.
+    /// Returns None if cancellation was not caused by an error.
+    pub fn to_error(&self) -> Option<Error> {
+        Error::from_cancellation("CancellationDetails", &self.error_code, &self.error_details)
+    }
 }
+
+impl fmt::Display for CancellationDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "canceled: {:?}, {:?}", self.reason, self.error_code)?;
+        if !self.error_details.is_empty() {
+            write!(f, ": {}", self.error_details)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CancellationDetails {}
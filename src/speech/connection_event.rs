@@ -0,0 +1,84 @@
+use crate::common::PropertyCollection;
+use crate::error::{convert_err, Result};
+use crate::ffi::{
+    recognizer_connection_event_get_property_bag, recognizer_event_handle_release,
+    recognizer_session_event_get_session_id, SmartHandle, SPXEVENTHANDLE, SPXPROPERTYBAGHANDLE,
+};
+use std::ffi::CStr;
+use std::fmt;
+use std::mem::MaybeUninit;
+
+/// ConnectionState is state of the connection to the service as reported
+/// by connection events (see *SpeechSynthesizer::connection_state*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    Disconnected,
+    /// Connection was requested (see *SpeechSynthesizer::open_connection*)
+    /// but connected event was not received yet.
+    Connecting,
+    Connected,
+}
+
+/// DisconnectReason tells why connection was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisconnectReason {
+    /// Connection was closed via *close_connection*.
+    Closed,
+    /// Connection was closed by the service or failed, e.g. idle timeout or network error.
+    Remote,
+}
+
+/// ConnectionEvent is passed into connected and disconnected callbacks
+/// (see *SpeechSynthesizer::set_connected_cb* and *SpeechSynthesizer::set_disconnected_cb*).
+pub struct ConnectionEvent {
+    pub session_id: String,
+    /// None for connected event.
+    pub reason: Option<DisconnectReason>,
+    pub properties: PropertyCollection,
+    pub handle: SmartHandle<SPXEVENTHANDLE>,
+}
+
+impl fmt::Debug for ConnectionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionEvent")
+            .field("session_id", &self.session_id)
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+impl ConnectionEvent {
+    /// # Safety
+    /// `handle` must be a valid reference to a live connection event.
+    pub unsafe fn from_handle(
+        handle: SPXEVENTHANDLE,
+        reason: Option<DisconnectReason>,
+    ) -> Result<ConnectionEvent> {
+        unsafe {
+            let mut c_buf = [0; 37];
+            let ret = recognizer_session_event_get_session_id(
+                handle,
+                c_buf.as_mut_ptr(),
+                c_buf.len() as u32,
+            );
+            convert_err(ret, "ConnectionEvent::from_handle(session_id) error")?;
+            let session_id = CStr::from_ptr(c_buf.as_ptr()).to_str()?.to_owned();
+
+            let mut prop_bag_handle: MaybeUninit<SPXPROPERTYBAGHANDLE> = MaybeUninit::uninit();
+            let ret =
+                recognizer_connection_event_get_property_bag(handle, prop_bag_handle.as_mut_ptr());
+            convert_err(ret, "ConnectionEvent::from_handle(properties) error")?;
+
+            Ok(ConnectionEvent {
+                session_id,
+                reason,
+                properties: PropertyCollection::from_handle(prop_bag_handle.assume_init()),
+                handle: SmartHandle::create(
+                    "ConnectionEvent",
+                    handle,
+                    recognizer_event_handle_release,
+                ),
+            })
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::ffi::{
     speech_recognition_model_handle_release, SmartHandle, SPXSPEECHCONFIGHANDLE,
     SPXSPEECHRECOMODELHANDLE,
 };
-use crate::speech::SpeechConfig;
+use crate::speech::{RecognitionTuning, SpeechConfig};
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::mem::MaybeUninit;
@@ -126,6 +126,16 @@ impl EmbeddedSpeechConfig {
         self.config.get_output_format()
     }
 
+    /// Validates and applies typed recognition timeouts, segmentation and response options.
+    pub fn set_recognition_tuning(&mut self, tuning: &RecognitionTuning) -> Result<()> {
+        self.config.set_recognition_tuning(tuning)
+    }
+
+    /// Reads back recognition options previously set on this config.
+    pub fn get_recognition_tuning(&self) -> Result<RecognitionTuning> {
+        self.config.get_recognition_tuning()
+    }
+
     /// Sets the profanity option. This can be used to remove profane words or mask them.
     /// * `profanity`: Sets the profanity option. This can be used to remove profane words or mask them.
     pub fn set_profanity(&mut self, profanity: ProfanityOption) -> Result<()> {
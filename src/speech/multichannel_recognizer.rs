@@ -0,0 +1,297 @@
+use crate::audio::pcm::deinterleave;
+use crate::audio::{AudioConfig, AudioStreamFormat, PushAudioInputStream};
+use crate::common::{EventHandlers, ResultReason, Subscription};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use crate::speech::{
+    SpeechConfig, SpeechRecognitionCanceledEvent, SpeechRecognitionEvent, SpeechRecognizer,
+};
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+const BYTES_PER_SAMPLE: usize = 2;
+
+/// MultichannelMode defines how *MultichannelRecognizer* processes channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultichannelMode {
+    /// Multichannel audio is sent as is to single conversation transcriber
+    /// (see *SpeechRecognizer::from_conversation_transcriber_config*), up to eight channels.
+    Native,
+    /// Every channel is recognized by its own speech recognizer, e.g. agent
+    /// and customer of stereo call recording.
+    FanOut,
+}
+
+/// TranscriptSegment is single recognized phrase of *MultichannelRecognizer* transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptSegment {
+    pub channel: u32,
+    pub result_id: String,
+    pub text: String,
+    /// Offset in ticks of 100 ns from the start of the audio.
+    pub offset: u64,
+    /// Duration in ticks of 100 ns.
+    pub duration: u64,
+}
+
+struct Shared {
+    recognizing_handlers: EventHandlers<SpeechRecognitionEvent>,
+    recognized_handlers: EventHandlers<SpeechRecognitionEvent>,
+    canceled_handlers: EventHandlers<SpeechRecognitionCanceledEvent>,
+    transcript: Mutex<Vec<TranscriptSegment>>,
+}
+
+impl Shared {
+    fn recognized(&self, event: &SpeechRecognitionEvent) {
+        let result = &event.result;
+        if result.reason != ResultReason::RecognizedSpeech || result.text.is_empty() {
+            return;
+        }
+        self.transcript
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(TranscriptSegment {
+                channel: result.channel.unwrap_or(0),
+                result_id: result.result_id.clone(),
+                text: result.text.clone(),
+                offset: result.offset.parse().unwrap_or(0),
+                duration: result.duration.parse().unwrap_or(0),
+            });
+    }
+}
+
+/// MultichannelRecognizer recognizes interleaved 16-bit PCM with several channels pushed
+/// via *write*. Results of all channels are tagged by channel (see
+/// *SpeechRecognitionResult::channel*) and merged into one transcript ordered by offset.
+pub struct MultichannelRecognizer {
+    mode: MultichannelMode,
+    channels: u16,
+    streams: Vec<PushAudioInputStream>,
+    recognizers: Vec<SpeechRecognizer>,
+    pending: Vec<u8>,
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for MultichannelRecognizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultichannelRecognizer")
+            .field("mode", &self.mode)
+            .field("channels", &self.channels)
+            .finish()
+    }
+}
+
+impl MultichannelRecognizer {
+    /// Creates new recognizer. *speech_config_factory* is invoked
+    /// for every created recognizer since SpeechConfig is consumed by the recognizer.
+    pub fn new<F>(
+        speech_config_factory: F,
+        samples_per_second: u32,
+        channels: u16,
+        mode: MultichannelMode,
+    ) -> Result<MultichannelRecognizer>
+    where
+        F: Fn() -> Result<SpeechConfig>,
+    {
+        if channels == 0 || (mode == MultichannelMode::Native && channels > 8) {
+            return Err(Error::new(
+                format!(
+                    "MultichannelRecognizer::new error: {} channel(s) not supported in {:?} mode",
+                    channels, mode
+                ),
+                ErrorRootCause::ApiError(SpxError::InvalidArg),
+            ));
+        }
+        let mut recognizer = MultichannelRecognizer {
+            mode,
+            channels,
+            streams: vec![],
+            recognizers: vec![],
+            pending: vec![],
+            shared: Arc::new(Shared {
+                recognizing_handlers: EventHandlers::local("MultichannelRecognizer::recognizing"),
+                recognized_handlers: EventHandlers::local("MultichannelRecognizer::recognized"),
+                canceled_handlers: EventHandlers::local("MultichannelRecognizer::canceled"),
+                transcript: Mutex::new(vec![]),
+            }),
+        };
+
+        match mode {
+            MultichannelMode::Native => {
+                let format = AudioStreamFormat::get_wave_format_pcm(
+                    samples_per_second,
+                    Some(16),
+                    Some(channels as u8),
+                )?;
+                let stream = PushAudioInputStream::create_push_stream_from_format(format)?;
+                let audio_config = AudioConfig::from_stream_input(&stream)?;
+                let transcriber = SpeechRecognizer::from_conversation_transcriber_config(
+                    speech_config_factory()?,
+                    audio_config,
+                )?;
+                recognizer.add(stream, transcriber, None)?;
+            }
+            MultichannelMode::FanOut => {
+                for channel in 0..u32::from(channels) {
+                    let format =
+                        AudioStreamFormat::get_wave_format_pcm(samples_per_second, None, None)?;
+                    let stream = PushAudioInputStream::create_push_stream_from_format(format)?;
+                    let audio_config = AudioConfig::from_stream_input(&stream)?;
+                    let channel_recognizer =
+                        SpeechRecognizer::from_config(speech_config_factory()?, audio_config)?;
+                    recognizer.add(stream, channel_recognizer, Some(channel))?;
+                }
+            }
+        }
+        Ok(recognizer)
+    }
+
+    /// Registers recognizer of one stream. In fan out mode results are tagged with *channel*,
+    /// conversation transcriber reports channel itself.
+    fn add(
+        &mut self,
+        stream: PushAudioInputStream,
+        mut recognizer: SpeechRecognizer,
+        channel: Option<u32>,
+    ) -> Result<()> {
+        let shared = self.shared.clone();
+        recognizer.set_recognizing_cb(move |mut event: SpeechRecognitionEvent| {
+            event.result.channel = channel.or(event.result.channel);
+            shared.recognizing_handlers.emit(&event);
+        })?;
+        let shared = self.shared.clone();
+        recognizer.set_recognized_cb(move |mut event: SpeechRecognitionEvent| {
+            event.result.channel = channel.or(event.result.channel);
+            shared.recognized(&event);
+            shared.recognized_handlers.emit(&event);
+        })?;
+        let shared = self.shared.clone();
+        recognizer.set_canceled_cb(move |mut event: SpeechRecognitionCanceledEvent| {
+            event.base.result.channel = channel.or(event.base.result.channel);
+            shared.canceled_handlers.emit(&event);
+        })?;
+        self.streams.push(stream);
+        self.recognizers.push(recognizer);
+        Ok(())
+    }
+
+    pub fn mode(&self) -> MultichannelMode {
+        self.mode
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Writes interleaved 16-bit PCM. Buffers can be of any size, incomplete frames are carried over.
+    /// Complete frames are consumed even if writing to some stream fails so that retry does not
+    /// send them again to streams which already received them.
+    pub fn write(&mut self, buffer: impl AsRef<[u8]>) -> Result<()> {
+        let frame_len = BYTES_PER_SAMPLE * usize::from(self.channels);
+        let frames = take_complete_frames(&mut self.pending, buffer.as_ref(), frame_len);
+        if frames.is_empty() {
+            return Ok(());
+        }
+        match self.mode {
+            MultichannelMode::Native => self.streams[0].write(&frames)?,
+            MultichannelMode::FanOut => {
+                let split = deinterleave(&frames, BYTES_PER_SAMPLE, self.channels);
+                for (stream, channel) in self.streams.iter_mut().zip(split) {
+                    stream.write(channel)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Signals end of the audio to all recognizers.
+    pub fn close_stream(&self) -> Result<()> {
+        for stream in &self.streams {
+            stream.close_stream()?;
+        }
+        Ok(())
+    }
+
+    pub async fn start_continuous_recognition_async(&mut self) -> Result<()> {
+        for recognizer in &mut self.recognizers {
+            recognizer.start_continuous_recognition_async().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn stop_continuous_recognition_async(&mut self) -> Result<()> {
+        for recognizer in &mut self.recognizers {
+            recognizer.stop_continuous_recognition_async().await?;
+        }
+        Ok(())
+    }
+
+    /// Returns phrases recognized so far on all channels ordered by offset.
+    pub fn transcript(&self) -> Vec<TranscriptSegment> {
+        let mut transcript = self
+            .shared
+            .transcript
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        transcript.sort_by_key(|segment| (segment.offset, segment.channel));
+        transcript
+    }
+
+    /// Registers handler of recognizing event of any channel.
+    pub fn on_recognizing<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .recognizing_handlers
+            .subscribe(f, "MultichannelRecognizer.on_recognizing error")
+    }
+
+    /// Registers handler of recognized event of any channel.
+    pub fn on_recognized<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .recognized_handlers
+            .subscribe(f, "MultichannelRecognizer.on_recognized error")
+    }
+
+    pub fn on_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionCanceledEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .canceled_handlers
+            .subscribe(f, "MultichannelRecognizer.on_canceled error")
+    }
+}
+
+/// Appends *buffer* to *pending* and takes out all complete frames,
+/// leaving incomplete frame in *pending*.
+fn take_complete_frames(pending: &mut Vec<u8>, buffer: &[u8], frame_len: usize) -> Vec<u8> {
+    pending.extend_from_slice(buffer);
+    let complete = pending.len() - pending.len() % frame_len;
+    pending.drain(..complete).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_complete_frames_test() {
+        let mut pending = vec![];
+        assert!(take_complete_frames(&mut pending, &[1, 2, 3], 4).is_empty());
+        assert_eq!(pending, vec![1, 2, 3]);
+
+        let frames = take_complete_frames(&mut pending, &[4, 5, 6, 7, 8, 9], 4);
+        assert_eq!(frames, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(pending, vec![9]);
+
+        // frames are taken out before they are written, next write starts after them
+        let frames = take_complete_frames(&mut pending, &[10, 11, 12], 4);
+        assert_eq!(frames, vec![9, 10, 11, 12]);
+        assert!(pending.is_empty());
+    }
+}
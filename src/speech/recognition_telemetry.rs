@@ -0,0 +1,499 @@
+use crate::common::{
+    CancellationErrorCode, CancellationReason, PropertyCollection, PropertyId, ResultReason,
+    Subscription,
+};
+use crate::error::Result;
+use crate::speech::{
+    SessionEvent, SpeechRecognitionCanceledEvent, SpeechRecognitionEvent, SpeechRecognizer,
+};
+use log::*;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// RecognitionMetric is single measurement reported to *MetricsSink*.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecognitionMetric {
+    /// Wall time from session start (or from previous final result)
+    /// until first partial result of the phrase.
+    TimeToFirstPartial {
+        session_id: String,
+        elapsed: Duration,
+    },
+    /// Final result of the phrase. *service_latency* is latency reported by the service
+    /// (*SpeechServiceResponseRecognitionLatencyMs*), *elapsed* is wall time from
+    /// session start (or from previous final result).
+    FinalLatency {
+        session_id: String,
+        reason: ResultReason,
+        service_latency: Option<Duration>,
+        elapsed: Duration,
+    },
+    Canceled {
+        session_id: String,
+        reason: CancellationReason,
+        error_code: CancellationErrorCode,
+    },
+    SessionDuration {
+        session_id: String,
+        elapsed: Duration,
+    },
+}
+
+/// MetricsSink receives recognition metrics, e.g. to feed them into Prometheus or StatsD.
+/// Called on native SDK thread, implementation must not block.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metric: &RecognitionMetric);
+}
+
+impl<F> MetricsSink for F
+where
+    F: Fn(&RecognitionMetric) + Send + Sync,
+{
+    fn record(&self, metric: &RecognitionMetric) {
+        self(metric)
+    }
+}
+
+/// Labels attached to session span.
+struct SessionLabels {
+    region: String,
+    language: String,
+    endpoint_id: String,
+}
+
+struct SessionState {
+    session_id: String,
+    started: Instant,
+    phrase_started: Instant,
+    first_partial_seen: bool,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// Part of recognition result telemetry is interested in.
+pub(crate) struct ResultSummary<'a> {
+    pub offset: &'a str,
+    pub duration: &'a str,
+    pub reason: ResultReason,
+    pub service_latency: Option<Duration>,
+}
+
+impl<'a> ResultSummary<'a> {
+    fn from_event(event: &'a SpeechRecognitionEvent) -> Self {
+        ResultSummary {
+            offset: &event.result.offset,
+            duration: &event.result.duration,
+            reason: event.result.reason,
+            service_latency: event
+                .result
+                .properties
+                .get_property(PropertyId::SpeechServiceResponseRecognitionLatencyMs, "")
+                .ok()
+                .and_then(|latency| latency.trim().parse().ok())
+                .map(Duration::from_millis),
+        }
+    }
+}
+
+/// Tracks recognition sessions and reports metrics. Handlers are driven
+/// by plain event data and explicit time so they can be exercised without the service.
+pub(crate) struct Telemetry {
+    labels: SessionLabels,
+    sink: Option<Arc<dyn MetricsSink>>,
+    session: Mutex<Option<SessionState>>,
+}
+
+impl Telemetry {
+    pub(crate) fn new(
+        region: String,
+        language: String,
+        endpoint_id: String,
+        sink: Option<Arc<dyn MetricsSink>>,
+    ) -> Self {
+        Telemetry {
+            labels: SessionLabels {
+                region,
+                language,
+                endpoint_id,
+            },
+            sink,
+            session: Mutex::new(None),
+        }
+    }
+
+    fn record(&self, metric: RecognitionMetric) {
+        if let Some(sink) = &self.sink {
+            sink.record(&metric);
+        }
+    }
+
+    fn new_session(&self, session_id: &str, now: Instant) -> SessionState {
+        SessionState {
+            session_id: session_id.to_owned(),
+            started: now,
+            phrase_started: now,
+            first_partial_seen: false,
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "speech_session",
+                session_id = %session_id,
+                region = %self.labels.region,
+                language = %self.labels.language,
+                endpoint_id = %self.labels.endpoint_id,
+            ),
+        }
+    }
+
+    /// Runs *f* with state of given session. Session is started lazily
+    /// if telemetry was enabled after session_started event.
+    fn with_session<R>(
+        &self,
+        session_id: &str,
+        now: Instant,
+        f: impl FnOnce(&mut SessionState) -> R,
+    ) -> R {
+        let mut session = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        if session.as_ref().map(|s| s.session_id.as_str()) != Some(session_id) {
+            *session = Some(self.new_session(session_id, now));
+        }
+        f(session.as_mut().unwrap())
+    }
+
+    pub(crate) fn session_started(&self, session_id: &str, now: Instant) {
+        debug!(
+            "session {} started, region {}, language {}, endpoint id {}",
+            session_id, self.labels.region, self.labels.language, self.labels.endpoint_id
+        );
+        self.with_session(session_id, now, |_session| {
+            #[cfg(feature = "tracing")]
+            tracing::info!(parent: &_session.span, "session started");
+        });
+    }
+
+    pub(crate) fn session_stopped(&self, session_id: &str, now: Instant) {
+        let session = {
+            let mut session = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+            if session.as_ref().is_some_and(|s| s.session_id == session_id) {
+                session.take()
+            } else {
+                None
+            }
+        };
+        if let Some(session) = session {
+            #[cfg(feature = "tracing")]
+            tracing::info!(parent: &session.span, "session stopped");
+            self.record(RecognitionMetric::SessionDuration {
+                session_id: session.session_id,
+                elapsed: now.saturating_duration_since(session.started),
+            });
+        }
+    }
+
+    pub(crate) fn recognizing(&self, session_id: &str, _result: &ResultSummary, now: Instant) {
+        let first_partial = self.with_session(session_id, now, |session| {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                parent: &session.span,
+                offset = %_result.offset,
+                duration = %_result.duration,
+                reason = ?_result.reason,
+                "recognizing"
+            );
+            if session.first_partial_seen {
+                None
+            } else {
+                session.first_partial_seen = true;
+                Some(now.saturating_duration_since(session.phrase_started))
+            }
+        });
+        if let Some(elapsed) = first_partial {
+            self.record(RecognitionMetric::TimeToFirstPartial {
+                session_id: session_id.to_owned(),
+                elapsed,
+            });
+        }
+    }
+
+    pub(crate) fn recognized(&self, session_id: &str, result: &ResultSummary, now: Instant) {
+        let elapsed = self.with_session(session_id, now, |session| {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                parent: &session.span,
+                offset = %result.offset,
+                duration = %result.duration,
+                reason = ?result.reason,
+                latency_ms = ?result.service_latency.map(|l| l.as_millis()),
+                "recognized"
+            );
+            let elapsed = now.saturating_duration_since(session.phrase_started);
+            session.phrase_started = now;
+            session.first_partial_seen = false;
+            elapsed
+        });
+        debug!(
+            "session {} recognized {:?} (offset {}, duration {}), service latency {:?}, elapsed {:?}",
+            session_id, result.reason, result.offset, result.duration, result.service_latency, elapsed
+        );
+        self.record(RecognitionMetric::FinalLatency {
+            session_id: session_id.to_owned(),
+            reason: result.reason,
+            service_latency: result.service_latency,
+            elapsed,
+        });
+    }
+
+    pub(crate) fn canceled(
+        &self,
+        session_id: &str,
+        reason: CancellationReason,
+        error_code: CancellationErrorCode,
+        _error_details: &str,
+        now: Instant,
+    ) {
+        self.with_session(session_id, now, |_session| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                parent: &_session.span,
+                reason = ?reason,
+                error_code = ?error_code,
+                error_details = %_error_details,
+                "canceled"
+            );
+        });
+        self.record(RecognitionMetric::Canceled {
+            session_id: session_id.to_owned(),
+            reason,
+            error_code,
+        });
+    }
+}
+
+/// Subscribes telemetry handlers to recognizer events. Handlers stay registered
+/// as long as returned subscriptions are kept.
+pub(crate) fn attach(
+    recognizer: &SpeechRecognizer,
+    properties: &PropertyCollection,
+    sink: Option<Arc<dyn MetricsSink>>,
+) -> Result<Vec<Subscription>> {
+    let telemetry = Arc::new(Telemetry::new(
+        properties.get_property(PropertyId::SpeechServiceConnectionRegion, "")?,
+        properties.get_property(PropertyId::SpeechServiceConnectionRecoLanguage, "")?,
+        properties.get_property(PropertyId::SpeechServiceConnectionEndpointId, "")?,
+        sink,
+    ));
+
+    let t = telemetry.clone();
+    let session_started = recognizer.on_session_started(move |event: &SessionEvent| {
+        t.session_started(&event.session_id, Instant::now())
+    })?;
+    let t = telemetry.clone();
+    let session_stopped = recognizer.on_session_stopped(move |event: &SessionEvent| {
+        t.session_stopped(&event.session_id, Instant::now())
+    })?;
+    let t = telemetry.clone();
+    let recognizing = recognizer.on_recognizing(move |event: &SpeechRecognitionEvent| {
+        t.recognizing(
+            &event.base.base.session_id,
+            &ResultSummary::from_event(event),
+            Instant::now(),
+        )
+    })?;
+    let t = telemetry.clone();
+    let recognized = recognizer.on_recognized(move |event: &SpeechRecognitionEvent| {
+        t.recognized(
+            &event.base.base.session_id,
+            &ResultSummary::from_event(event),
+            Instant::now(),
+        )
+    })?;
+    let canceled = recognizer.on_canceled(move |event: &SpeechRecognitionCanceledEvent| {
+        telemetry.canceled(
+            &event.base.base.base.session_id,
+            event.reason,
+            event.error_code,
+            &event.error_details,
+            Instant::now(),
+        )
+    })?;
+    Ok(vec![
+        session_started,
+        session_stopped,
+        recognizing,
+        recognized,
+        canceled,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn telemetry() -> (Telemetry, Arc<Mutex<Vec<RecognitionMetric>>>) {
+        let metrics = Arc::new(Mutex::new(vec![]));
+        let sink_metrics = metrics.clone();
+        let sink: Arc<dyn MetricsSink> = Arc::new(move |metric: &RecognitionMetric| {
+            sink_metrics.lock().unwrap().push(metric.clone())
+        });
+        let telemetry = Telemetry::new(
+            "westeurope".to_owned(),
+            "en-US".to_owned(),
+            "".to_owned(),
+            Some(sink),
+        );
+        (telemetry, metrics)
+    }
+
+    fn result(reason: ResultReason, service_latency: Option<u64>) -> ResultSummary<'static> {
+        ResultSummary {
+            offset: "0",
+            duration: "0",
+            reason,
+            service_latency: service_latency.map(Duration::from_millis),
+        }
+    }
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn telemetry_phrase_latency_test() {
+        let (telemetry, metrics) = telemetry();
+        let t0 = Instant::now();
+        let partial = result(ResultReason::RecognizingSpeech, None);
+        let fin = result(ResultReason::RecognizedSpeech, Some(120));
+
+        telemetry.session_started("s1", t0);
+        telemetry.recognizing("s1", &partial, t0 + ms(300));
+        // only the first partial of the phrase is measured
+        telemetry.recognizing("s1", &partial, t0 + ms(400));
+        telemetry.recognized("s1", &fin, t0 + ms(1000));
+        // next phrase is measured from previous final result
+        telemetry.recognizing("s1", &partial, t0 + ms(1200));
+        telemetry.recognized("s1", &fin, t0 + ms(1500));
+        telemetry.session_stopped("s1", t0 + ms(2000));
+
+        let session_id = || "s1".to_owned();
+        assert_eq!(
+            *metrics.lock().unwrap(),
+            vec![
+                RecognitionMetric::TimeToFirstPartial {
+                    session_id: session_id(),
+                    elapsed: ms(300),
+                },
+                RecognitionMetric::FinalLatency {
+                    session_id: session_id(),
+                    reason: ResultReason::RecognizedSpeech,
+                    service_latency: Some(ms(120)),
+                    elapsed: ms(1000),
+                },
+                RecognitionMetric::TimeToFirstPartial {
+                    session_id: session_id(),
+                    elapsed: ms(200),
+                },
+                RecognitionMetric::FinalLatency {
+                    session_id: session_id(),
+                    reason: ResultReason::RecognizedSpeech,
+                    service_latency: Some(ms(120)),
+                    elapsed: ms(500),
+                },
+                RecognitionMetric::SessionDuration {
+                    session_id: session_id(),
+                    elapsed: ms(2000),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn telemetry_session_switch_test() {
+        let (telemetry, metrics) = telemetry();
+        let t0 = Instant::now();
+        let partial = result(ResultReason::RecognizingSpeech, None);
+
+        // telemetry enabled in the middle of session, session is started lazily
+        telemetry.recognizing("s1", &partial, t0);
+        telemetry.session_started("s2", t0 + ms(100));
+        telemetry.recognizing("s2", &partial, t0 + ms(150));
+        // stop of the replaced session does not affect the current one
+        telemetry.session_stopped("s1", t0 + ms(200));
+        telemetry.recognizing("s2", &partial, t0 + ms(250));
+        telemetry.session_started("s3", t0 + ms(300));
+        telemetry.session_stopped("s3", t0 + ms(700));
+
+        assert_eq!(
+            *metrics.lock().unwrap(),
+            vec![
+                RecognitionMetric::TimeToFirstPartial {
+                    session_id: "s1".to_owned(),
+                    elapsed: ms(0),
+                },
+                RecognitionMetric::TimeToFirstPartial {
+                    session_id: "s2".to_owned(),
+                    elapsed: ms(50),
+                },
+                RecognitionMetric::SessionDuration {
+                    session_id: "s3".to_owned(),
+                    elapsed: ms(400),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn telemetry_canceled_test() {
+        let (telemetry, metrics) = telemetry();
+        let t0 = Instant::now();
+        for error_code in [
+            CancellationErrorCode::ConnectionFailure,
+            CancellationErrorCode::TooManyRequests,
+            CancellationErrorCode::ConnectionFailure,
+        ] {
+            telemetry.canceled("s1", CancellationReason::Error, error_code, "details", t0);
+        }
+        telemetry.canceled(
+            "s1",
+            CancellationReason::EndOfStream,
+            CancellationErrorCode::NoError,
+            "",
+            t0,
+        );
+
+        let metrics = metrics.lock().unwrap();
+        let count = |reason, error_code| {
+            metrics
+                .iter()
+                .filter(|metric| {
+                    **metric
+                        == RecognitionMetric::Canceled {
+                            session_id: "s1".to_owned(),
+                            reason,
+                            error_code,
+                        }
+                })
+                .count()
+        };
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(
+            count(
+                CancellationReason::Error,
+                CancellationErrorCode::ConnectionFailure
+            ),
+            2
+        );
+        assert_eq!(
+            count(
+                CancellationReason::Error,
+                CancellationErrorCode::TooManyRequests
+            ),
+            1
+        );
+        assert_eq!(
+            count(
+                CancellationReason::EndOfStream,
+                CancellationErrorCode::NoError
+            ),
+            1
+        );
+    }
+}
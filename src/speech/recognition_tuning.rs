@@ -0,0 +1,270 @@
+use crate::common::{LanguageIdMode, PropertyId, SegmentationStrategy};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use crate::speech::SpeechConfig;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+const SEGMENTATION_STRATEGY: &str = "Speech-SegmentationStrategy";
+const SEGMENTATION_MAXIMUM_TIME_MS: &str = "Speech-SegmentationMaximumTimeMs";
+const TRUE_TEXT: &str = "TrueText";
+
+const SEGMENTATION_SILENCE_TIMEOUT_RANGE: RangeInclusive<Duration> =
+    Duration::from_millis(100)..=Duration::from_millis(5000);
+const SEGMENTATION_MAXIMUM_TIME_RANGE: RangeInclusive<Duration> =
+    Duration::from_millis(20_000)..=Duration::from_millis(70_000);
+const TIMEOUT_RANGE: RangeInclusive<Duration> =
+    Duration::from_millis(1)..=Duration::from_millis(u32::MAX as u64);
+
+/// RecognitionTuning groups recognition timeouts, segmentation and response options
+/// which are otherwise set as untyped properties of *SpeechConfig*.
+/// Only options which are set (Some) are applied, others keep service defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecognitionTuning {
+    /// Silence at the beginning of audio after which recognition ends with NoMatch result.
+    pub initial_silence_timeout: Option<Duration>,
+    /// Silence at the end of audio after which single shot recognition ends.
+    pub end_silence_timeout: Option<Duration>,
+    pub segmentation: Option<SegmentationStrategy>,
+    /// Number of times a word has to appear in partial results to be returned.
+    /// Higher values give more stable but delayed partial results.
+    pub stable_partial_result_threshold: Option<u32>,
+    /// Includes signal to noise ratio in detailed response.
+    pub request_snr: Option<bool>,
+    /// Enables TrueText post processing (e.g. removal of disfluencies).
+    pub true_text: Option<bool>,
+    pub language_id_mode: Option<LanguageIdMode>,
+}
+
+fn out_of_range(message: String) -> Error {
+    Error::new(
+        format!("RecognitionTuning.validate error: {}", message),
+        ErrorRootCause::ApiError(SpxError::OutOfRange),
+    )
+}
+
+fn check_range(name: &str, value: Duration, range: &RangeInclusive<Duration>) -> Result<()> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(out_of_range(format!(
+            "{} {:?} not in range {:?}..={:?}",
+            name,
+            value,
+            range.start(),
+            range.end()
+        )))
+    }
+}
+
+fn millis(value: Duration) -> String {
+    value.as_millis().to_string()
+}
+
+fn parse_millis(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_millis)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+impl RecognitionTuning {
+    pub fn new() -> Self {
+        RecognitionTuning::default()
+    }
+
+    pub fn initial_silence_timeout(mut self, timeout: Duration) -> Self {
+        self.initial_silence_timeout = Some(timeout);
+        self
+    }
+
+    pub fn end_silence_timeout(mut self, timeout: Duration) -> Self {
+        self.end_silence_timeout = Some(timeout);
+        self
+    }
+
+    pub fn segmentation(mut self, segmentation: SegmentationStrategy) -> Self {
+        self.segmentation = Some(segmentation);
+        self
+    }
+
+    pub fn stable_partial_result_threshold(mut self, threshold: u32) -> Self {
+        self.stable_partial_result_threshold = Some(threshold);
+        self
+    }
+
+    pub fn request_snr(mut self, request_snr: bool) -> Self {
+        self.request_snr = Some(request_snr);
+        self
+    }
+
+    pub fn true_text(mut self, true_text: bool) -> Self {
+        self.true_text = Some(true_text);
+        self
+    }
+
+    pub fn language_id_mode(mut self, mode: LanguageIdMode) -> Self {
+        self.language_id_mode = Some(mode);
+        self
+    }
+
+    /// Checks that all options are within ranges accepted by the service.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(timeout) = self.initial_silence_timeout {
+            check_range("initial_silence_timeout", timeout, &TIMEOUT_RANGE)?;
+        }
+        if let Some(timeout) = self.end_silence_timeout {
+            check_range("end_silence_timeout", timeout, &TIMEOUT_RANGE)?;
+        }
+        if let Some(SegmentationStrategy::Silence {
+            silence_timeout,
+            max_segment_time,
+        }) = self.segmentation
+        {
+            check_range(
+                "segmentation silence_timeout",
+                silence_timeout,
+                &SEGMENTATION_SILENCE_TIMEOUT_RANGE,
+            )?;
+            if let Some(max_segment_time) = max_segment_time {
+                check_range(
+                    "segmentation max_segment_time",
+                    max_segment_time,
+                    &SEGMENTATION_MAXIMUM_TIME_RANGE,
+                )?;
+            }
+        }
+        if self.stable_partial_result_threshold == Some(0) {
+            return Err(out_of_range(
+                "stable_partial_result_threshold must be at least 1".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates options and sets them as properties of *config*.
+    /// Nothing is set if validation fails.
+    pub fn apply_to(&self, config: &mut SpeechConfig) -> Result<()> {
+        self.validate()?;
+
+        if let Some(timeout) = self.initial_silence_timeout {
+            config.set_property(
+                PropertyId::SpeechServiceConnectionInitialSilenceTimeoutMs,
+                millis(timeout),
+            )?;
+        }
+        if let Some(timeout) = self.end_silence_timeout {
+            config.set_property(
+                PropertyId::SpeechServiceConnectionEndSilenceTimeoutMs,
+                millis(timeout),
+            )?;
+        }
+        if let Some(segmentation) = self.segmentation {
+            // timeouts of silence segmentation are cleared for other strategies
+            let (silence_timeout, max_segment_time) = match segmentation {
+                SegmentationStrategy::Silence {
+                    silence_timeout,
+                    max_segment_time,
+                } => (millis(silence_timeout), max_segment_time.map(millis)),
+                _ => (String::new(), None),
+            };
+            config.set_property_by_string(
+                SEGMENTATION_STRATEGY.into(),
+                segmentation.as_str().into(),
+            )?;
+            config.set_property(
+                PropertyId::SpeechSegmentationSilenceTimeoutMs,
+                silence_timeout,
+            )?;
+            config.set_property_by_string(
+                SEGMENTATION_MAXIMUM_TIME_MS.into(),
+                max_segment_time.unwrap_or_default(),
+            )?;
+        }
+        if let Some(threshold) = self.stable_partial_result_threshold {
+            config.set_property(
+                PropertyId::SpeechServiceResponseStablePartialResultThreshold,
+                threshold.to_string(),
+            )?;
+        }
+        if let Some(request_snr) = self.request_snr {
+            config.set_property(
+                PropertyId::SpeechServiceResponseRequestSnr,
+                request_snr.to_string(),
+            )?;
+        }
+        if let Some(true_text) = self.true_text {
+            let option = if true_text { TRUE_TEXT } else { "" };
+            config.set_property(
+                PropertyId::SpeechServiceResponsePostProcessingOption,
+                option.into(),
+            )?;
+        }
+        if let Some(mode) = self.language_id_mode {
+            config.set_language_id_mode(mode)?;
+        }
+        Ok(())
+    }
+
+    /// Reads options back from properties of *config*. Options which are not set
+    /// (or hold value not understood by this library) are returned as None.
+    pub fn from_speech_config(config: &SpeechConfig) -> Result<Self> {
+        let initial_silence_timeout = parse_millis(
+            &config.get_property(PropertyId::SpeechServiceConnectionInitialSilenceTimeoutMs)?,
+        );
+        let end_silence_timeout = parse_millis(
+            &config.get_property(PropertyId::SpeechServiceConnectionEndSilenceTimeoutMs)?,
+        );
+
+        let strategy = config.get_property_by_string(SEGMENTATION_STRATEGY.into())?;
+        let silence_timeout =
+            parse_millis(&config.get_property(PropertyId::SpeechSegmentationSilenceTimeoutMs)?);
+        let max_segment_time =
+            parse_millis(&config.get_property_by_string(SEGMENTATION_MAXIMUM_TIME_MS.into())?);
+        let segmentation = match (strategy.as_str(), silence_timeout) {
+            ("Semantic", _) => Some(SegmentationStrategy::Semantic),
+            ("Time", Some(silence_timeout)) | ("", Some(silence_timeout)) => {
+                Some(SegmentationStrategy::Silence {
+                    silence_timeout,
+                    max_segment_time,
+                })
+            }
+            ("Default", _) => Some(SegmentationStrategy::Default),
+            _ => None,
+        };
+
+        let stable_partial_result_threshold = config
+            .get_property(PropertyId::SpeechServiceResponseStablePartialResultThreshold)?
+            .trim()
+            .parse()
+            .ok();
+        let request_snr =
+            parse_bool(&config.get_property(PropertyId::SpeechServiceResponseRequestSnr)?);
+        let true_text = match config
+            .get_property(PropertyId::SpeechServiceResponsePostProcessingOption)?
+            .as_str()
+        {
+            "" => None,
+            option => Some(option == TRUE_TEXT),
+        };
+        let language_id_mode =
+            match config.get_property(PropertyId::SpeechServiceConnectionLanguageIdMode)? {
+                mode if mode.is_empty() => None,
+                mode => Some(LanguageIdMode::from_property_value(&mode)),
+            };
+
+        Ok(RecognitionTuning {
+            initial_silence_timeout,
+            end_silence_timeout,
+            segmentation,
+            stable_partial_result_threshold,
+            request_snr,
+            true_text,
+            language_id_mode,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+/// ReconnectPolicy configures how *ResilientRecognizer* recovers
+/// from continuous recognition sessions canceled due to transient errors.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Maximal number of consecutive reconnect attempts. None means no limit.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first reconnect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between reconnect attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay is multiplied by after each unsuccessful attempt.
+    pub backoff_multiplier: f64,
+    /// Length of the most recent audio kept in memory and resubmitted to the new session.
+    pub replay_buffer: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            max_attempts: None,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            backoff_multiplier: 2.0,
+            replay_buffer: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Returns delay before given reconnect attempt (starting from 1).
+    /// Delay is clamped to [0, *max_backoff*], e.g. negative multiplier
+    /// yields zero delay for even attempts.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_backoff.as_secs_f64() * self.backoff_multiplier.powi(exponent);
+        if delay.is_nan() || delay >= self.max_backoff.as_secs_f64() {
+            self.max_backoff
+        } else {
+            Duration::from_secs_f64(delay.max(0.0))
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+/// Event passed into *ResilientRecognizer::on_reconnected* handlers
+/// once replacement recognition session is running.
+#[derive(Debug, Clone)]
+pub struct ReconnectedEvent {
+    /// Number of attempts it took to reconnect.
+    pub attempts: u32,
+    /// Offset (in ticks, i.e. 100 nanoseconds) of the first audio byte resubmitted to the new session,
+    /// measured from the beginning of the audio written to *ResilientRecognizer*.
+    pub replay_offset: u64,
+    /// Length of the audio resubmitted from replay buffer.
+    pub replayed: Duration,
+}
@@ -0,0 +1,14 @@
+use crate::error::SpxError;
+use std::time::Duration;
+
+/// Event passed into *ResilientRecognizer::on_reconnecting* handlers when recognition
+/// session was canceled due to transient error and new session is about to be created.
+#[derive(Debug, Clone)]
+pub struct ReconnectingEvent {
+    /// Number of the upcoming reconnect attempt, starting from 1.
+    pub attempt: u32,
+    /// Delay before the new session is created.
+    pub delay: Duration,
+    pub error: SpxError,
+    pub error_details: String,
+}
@@ -0,0 +1,578 @@
+use crate::audio::{AudioConfig, AudioStreamFormat, PushAudioInputStream};
+use crate::common::{EventHandlers, Subscription};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use crate::speech::{
+    ReconnectPolicy, ReconnectedEvent, ReconnectingEvent, SpeechConfig,
+    SpeechRecognitionCanceledEvent, SpeechRecognitionEvent, SpeechRecognitionResult,
+    SpeechRecognizer,
+};
+use log::*;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+type SpeechConfigFactory = Box<dyn Fn() -> Result<SpeechConfig> + Send + Sync>;
+
+struct AudioState {
+    // push stream of the currently running session
+    stream: Option<PushAudioInputStream>,
+    replay: ReplayBuffer,
+    closed: bool,
+}
+
+/// Keeps the most recent PCM audio written by the caller
+/// so that it can be resubmitted to the new session.
+struct ReplayBuffer {
+    data: VecDeque<u8>,
+    capacity: usize,
+    block_align: u64,
+    bytes_per_second: u64,
+    // total number of bytes written by the caller
+    written: u64,
+}
+
+impl ReplayBuffer {
+    fn new(length: Duration, samples_per_second: u32, bits_per_sample: u8, channels: u8) -> Self {
+        let block_align = (u64::from(bits_per_sample) / 8 * u64::from(channels)).max(1);
+        let bytes_per_second = (u64::from(samples_per_second) * block_align).max(1);
+        let capacity = length.as_millis() as u64 * bytes_per_second / 1000;
+        ReplayBuffer {
+            data: VecDeque::new(),
+            capacity: (capacity - capacity % block_align) as usize,
+            block_align,
+            bytes_per_second,
+            written: 0,
+        }
+    }
+
+    fn bytes_to_ticks(&self, bytes: u64) -> u64 {
+        (u128::from(bytes) * u128::from(TICKS_PER_SECOND) / u128::from(self.bytes_per_second))
+            as u64
+    }
+
+    fn ticks_to_bytes(&self, ticks: u64) -> u64 {
+        let bytes = (u128::from(ticks) * u128::from(self.bytes_per_second)
+            / u128::from(TICKS_PER_SECOND)) as u64;
+        bytes - bytes % self.block_align
+    }
+
+    /// Appends audio, discarding the oldest whole blocks exceeding the capacity.
+    fn push(&mut self, buf: &[u8]) {
+        self.data.extend(buf);
+        let overflow = self.data.len().saturating_sub(self.capacity);
+        if overflow > 0 {
+            let block_align = self.block_align as usize;
+            let overflow = (overflow.div_ceil(block_align) * block_align).min(self.data.len());
+            self.data.drain(..overflow);
+        }
+        self.written += buf.len() as u64;
+    }
+
+    /// Offset (in ticks) of the first byte kept, measured from the beginning of the audio.
+    fn start(&self) -> u64 {
+        self.bytes_to_ticks(self.written - self.data.len() as u64)
+    }
+
+    /// Length (in ticks) of the audio kept.
+    fn len(&self) -> u64 {
+        self.bytes_to_ticks(self.data.len() as u64)
+    }
+
+    /// Discards audio preceding given offset (in ticks), e.g. audio already transcribed.
+    fn discard_before(&mut self, offset: u64) {
+        let start = self.written - self.data.len() as u64;
+        let end = self.ticks_to_bytes(offset);
+        if end > start {
+            let discarded = ((end - start) as usize).min(self.data.len());
+            self.data.drain(..discarded);
+        }
+    }
+}
+
+/// Shifts session relative span (offset and duration in ticks) by *base* and clips it
+/// so that it does not overlap audio transcribed up to *last_end*.
+/// Returns None if the span lies entirely within the transcribed audio.
+fn rebase_span(offset: u64, duration: u64, base: u64, last_end: u64) -> Option<(u64, u64)> {
+    let start = base + offset;
+    let end = start + duration;
+    if start >= last_end {
+        Some((start, duration))
+    } else if end > last_end {
+        Some((last_end, end - last_end))
+    } else {
+        None
+    }
+}
+
+struct Shared {
+    speech_config_factory: SpeechConfigFactory,
+    samples_per_second: u32,
+    bits_per_sample: u8,
+    channels: u8,
+    policy: ReconnectPolicy,
+    audio: Mutex<AudioState>,
+    // end (offset + duration in ticks) of the last recognized result passed to handlers
+    last_recognized_end: Mutex<u64>,
+    recognizing_handlers: EventHandlers<SpeechRecognitionEvent>,
+    recognized_handlers: EventHandlers<SpeechRecognitionEvent>,
+    canceled_handlers: EventHandlers<SpeechRecognitionCanceledEvent>,
+    reconnecting_handlers: EventHandlers<ReconnectingEvent>,
+    reconnected_handlers: EventHandlers<ReconnectedEvent>,
+}
+
+impl Shared {
+    /// Shifts result offset by the position at which the session started.
+    /// Results overlapping audio already transcribed by previous session are clipped
+    /// to the end of that audio. Returns false for results lying entirely within it.
+    fn rebase(&self, result: &mut SpeechRecognitionResult, base: u64, is_final: bool) -> bool {
+        let offset = result.offset.parse::<u64>().unwrap_or(0);
+        let duration = result.duration.parse::<u64>().unwrap_or(0);
+        let mut last_end = self.last_recognized_end.lock().unwrap();
+        match rebase_span(offset, duration, base, *last_end) {
+            Some((offset, duration)) => {
+                if is_final {
+                    *last_end = offset + duration;
+                }
+                result.offset = offset.to_string();
+                result.duration = duration.to_string();
+                true
+            }
+            None => {
+                debug!(
+                    "skipping replayed result {} (end {} <= {})",
+                    result.result_id,
+                    base + offset + duration,
+                    *last_end
+                );
+                false
+            }
+        }
+    }
+
+    fn detach_stream(&self) {
+        self.audio.lock().unwrap().stream = None;
+    }
+
+    /// Creates new recognizer fed with replay buffer followed by live audio.
+    /// Returns recognizer and event announcing the replay.
+    fn create_session(
+        self: &Arc<Self>,
+        canceled_tx: mpsc::UnboundedSender<SpeechRecognitionCanceledEvent>,
+    ) -> Result<(SpeechRecognizer, ReconnectedEvent)> {
+        let speech_config = (self.speech_config_factory)()?;
+        let format = AudioStreamFormat::get_wave_format_pcm(
+            self.samples_per_second,
+            Some(self.bits_per_sample),
+            Some(self.channels),
+        )?;
+        let mut stream = PushAudioInputStream::create_push_stream_from_format(format)?;
+        let audio_config = AudioConfig::from_stream_input(&stream)?;
+        let mut recognizer = SpeechRecognizer::from_config(speech_config, audio_config)?;
+
+        // audio lock is held until the stream is installed so that
+        // no byte written meanwhile is missing in the new session
+        let mut audio = self.audio.lock().unwrap();
+        // audio already transcribed is not resubmitted
+        audio
+            .replay
+            .discard_before(*self.last_recognized_end.lock().unwrap());
+        let base = audio.replay.start();
+
+        let shared = self.clone();
+        recognizer.set_recognizing_cb(move |mut event: SpeechRecognitionEvent| {
+            if shared.rebase(&mut event.result, base, false) {
+                shared.recognizing_handlers.emit(&event);
+            }
+        })?;
+        let shared = self.clone();
+        recognizer.set_recognized_cb(move |mut event: SpeechRecognitionEvent| {
+            if shared.rebase(&mut event.result, base, true) {
+                shared.recognized_handlers.emit(&event);
+            }
+        })?;
+        recognizer.set_canceled_cb(move |event: SpeechRecognitionCanceledEvent| {
+            if canceled_tx.send(event).is_err() {
+                warn!("ResilientRecognizer: canceled event received after session ended");
+            }
+        })?;
+
+        let (front, back) = audio.replay.data.as_slices();
+        stream.write(front)?;
+        stream.write(back)?;
+        if audio.closed {
+            stream.close_stream()?;
+        }
+        let replayed = Duration::from_nanos(audio.replay.len() * 100);
+        audio.stream = Some(stream);
+
+        Ok((
+            recognizer,
+            ReconnectedEvent {
+                attempts: 0,
+                replay_offset: base,
+                replayed,
+            },
+        ))
+    }
+
+    async fn supervise(self: Arc<Self>, mut stop_rx: oneshot::Receiver<()>) -> Result<()> {
+        let mut attempt: u32 = 0;
+        loop {
+            let (canceled_tx, mut canceled_rx) = mpsc::unbounded_channel();
+            let session = match self.create_session(canceled_tx) {
+                Ok((mut recognizer, reconnected)) => recognizer
+                    .start_continuous_recognition_async()
+                    .await
+                    .map(|_| (recognizer, reconnected)),
+                Err(err) => Err(err),
+            };
+
+            let (err, canceled) = match session {
+                Ok((mut recognizer, mut reconnected)) => {
+                    if attempt > 0 {
+                        info!(
+                            "ResilientRecognizer reconnected after {} attempt(s)",
+                            attempt
+                        );
+                        reconnected.attempts = attempt;
+                        self.reconnected_handlers.emit(&reconnected);
+                    }
+                    attempt = 0;
+
+                    let canceled = tokio::select! {
+                        _ = &mut stop_rx => {
+                            let res = recognizer.stop_continuous_recognition_async().await;
+                            self.detach_stream();
+                            return res;
+                        }
+                        canceled = canceled_rx.recv() => canceled,
+                    };
+                    if let Err(err) = recognizer.stop_continuous_recognition_async().await {
+                        debug!(
+                            "ResilientRecognizer: stopping canceled session failed {:?}",
+                            err
+                        );
+                    }
+                    self.detach_stream();
+
+                    let canceled = match canceled {
+                        Some(canceled) => canceled,
+                        None => {
+                            return Err(Error::new(
+                                "ResilientRecognizer supervisor error: canceled event channel closed"
+                                    .to_owned(),
+                                ErrorRootCause::ApiError(SpxError::RuntimeError),
+                            ));
+                        }
+                    };
+                    match canceled.to_error() {
+                        // end of stream or canceled by user
+                        None => {
+                            self.canceled_handlers.emit(&canceled);
+                            return Ok(());
+                        }
+                        Some(err) if err.is_transient() => (err, Some(canceled)),
+                        Some(err) => {
+                            self.canceled_handlers.emit(&canceled);
+                            return Err(err);
+                        }
+                    }
+                }
+                Err(err) => {
+                    self.detach_stream();
+                    if !err.is_transient() {
+                        return Err(err);
+                    }
+                    (err, None)
+                }
+            };
+
+            attempt += 1;
+            if let Some(max_attempts) = self.policy.max_attempts {
+                if attempt > max_attempts {
+                    warn!(
+                        "ResilientRecognizer giving up after {} attempt(s)",
+                        max_attempts
+                    );
+                    if let Some(canceled) = canceled {
+                        self.canceled_handlers.emit(&canceled);
+                    }
+                    return Err(err);
+                }
+            }
+
+            let delay = self.policy.backoff(attempt);
+            warn!(
+                "ResilientRecognizer reconnecting in {:?} (attempt {}): {}",
+                delay, attempt, err
+            );
+            self.reconnecting_handlers.emit(&ReconnectingEvent {
+                attempt,
+                delay,
+                error: err.spx_error().unwrap_or(SpxError::RuntimeError),
+                error_details: err.native_message.unwrap_or_default(),
+            });
+            tokio::select! {
+                _ = &mut stop_rx => return Ok(()),
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+    }
+}
+
+/// ResilientRecognizer supervises continuous recognition of PCM audio pushed via *write*.
+/// Whenever the session is canceled due to transient error (see *SpxError::is_transient*),
+/// new session is created according to *ReconnectPolicy* and the most recent audio
+/// kept in replay buffer is resubmitted to it. Offsets of the results are rebased to
+/// the beginning of the audio written so they stay monotonic across reconnects.
+/// Audio already transcribed is not resubmitted and results overlapping it are clipped.
+pub struct ResilientRecognizer {
+    shared: Arc<Shared>,
+    stop_tx: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<Result<()>>>,
+}
+
+impl fmt::Debug for ResilientRecognizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResilientRecognizer")
+            .field("samples_per_second", &self.shared.samples_per_second)
+            .field("bits_per_sample", &self.shared.bits_per_sample)
+            .field("channels", &self.shared.channels)
+            .field("policy", &self.shared.policy)
+            .field("running", &self.task.is_some())
+            .finish()
+    }
+}
+
+impl ResilientRecognizer {
+    /// Creates new recognizer. *speech_config_factory* is invoked
+    /// for every (re)created session since SpeechConfig is consumed by the recognizer.
+    pub fn new<F>(
+        speech_config_factory: F,
+        samples_per_second: u32,
+        bits_per_sample: u8,
+        channels: u8,
+        policy: ReconnectPolicy,
+    ) -> ResilientRecognizer
+    where
+        F: Fn() -> Result<SpeechConfig> + 'static + Send + Sync,
+    {
+        let replay = ReplayBuffer::new(
+            policy.replay_buffer,
+            samples_per_second,
+            bits_per_sample,
+            channels,
+        );
+        let shared = Shared {
+            speech_config_factory: Box::new(speech_config_factory),
+            samples_per_second,
+            bits_per_sample,
+            channels,
+            policy,
+            audio: Mutex::new(AudioState {
+                stream: None,
+                replay,
+                closed: false,
+            }),
+            last_recognized_end: Mutex::new(0),
+            recognizing_handlers: EventHandlers::local("ResilientRecognizer::recognizing"),
+            recognized_handlers: EventHandlers::local("ResilientRecognizer::recognized"),
+            canceled_handlers: EventHandlers::local("ResilientRecognizer::canceled"),
+            reconnecting_handlers: EventHandlers::local("ResilientRecognizer::reconnecting"),
+            reconnected_handlers: EventHandlers::local("ResilientRecognizer::reconnected"),
+        };
+        ResilientRecognizer {
+            shared: Arc::new(shared),
+            stop_tx: None,
+            task: None,
+        }
+    }
+
+    /// Writes PCM audio. Audio is kept in replay buffer even
+    /// while the recognizer is reconnecting.
+    pub fn write(&self, buffer: impl AsRef<[u8]>) -> Result<()> {
+        let buf = buffer.as_ref();
+        let mut audio = self.shared.audio.lock().unwrap();
+        if audio.closed {
+            return Err(Error::new(
+                "ResilientRecognizer.write error: stream already closed".to_owned(),
+                ErrorRootCause::ApiError(SpxError::InvalidState),
+            ));
+        }
+
+        audio.replay.push(buf);
+
+        if let Some(stream) = audio.stream.as_mut() {
+            if let Err(err) = stream.write(buf) {
+                // session is most likely being canceled, data is kept in replay buffer
+                debug!("ResilientRecognizer.write error {:?}", err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Signals end of the audio. Recognition finishes once
+    /// all the audio is processed, see *wait*.
+    pub fn close_stream(&self) -> Result<()> {
+        let mut audio = self.shared.audio.lock().unwrap();
+        audio.closed = true;
+        if let Some(stream) = audio.stream.as_ref() {
+            stream.close_stream()?;
+        }
+        Ok(())
+    }
+
+    /// Starts supervised continuous recognition. Must be called within tokio runtime.
+    pub async fn start_continuous_recognition_async(&mut self) -> Result<()> {
+        if self.task.is_some() {
+            return Err(Error::new(
+                "ResilientRecognizer.start_continuous_recognition_async error: already started"
+                    .to_owned(),
+                ErrorRootCause::ApiError(SpxError::AlreadyInProgress),
+            ));
+        }
+        let (stop_tx, stop_rx) = oneshot::channel();
+        self.stop_tx = Some(stop_tx);
+        self.task = Some(tokio::spawn(self.shared.clone().supervise(stop_rx)));
+        Ok(())
+    }
+
+    /// Stops the recognition and returns the error which terminated it, if any.
+    pub async fn stop_continuous_recognition_async(&mut self) -> Result<()> {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            // supervisor might have finished already
+            let _ = stop_tx.send(());
+        }
+        self.wait().await
+    }
+
+    /// Waits until the recognition ends, i.e. until the end of the stream is reached
+    /// (see *close_stream*) or non-transient error occurs.
+    pub async fn wait(&mut self) -> Result<()> {
+        match self.task.take() {
+            Some(task) => task.await.unwrap_or_else(|err| {
+                Err(Error::new(
+                    format!("ResilientRecognizer supervisor error: {}", err),
+                    ErrorRootCause::ApiError(SpxError::RuntimeError),
+                ))
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers handler of recognizing event. Result offset is rebased, see *ResilientRecognizer*.
+    pub fn on_recognizing<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .recognizing_handlers
+            .subscribe(f, "ResilientRecognizer.on_recognizing error")
+    }
+
+    /// Registers handler of recognized event. Result offset is rebased, see *ResilientRecognizer*.
+    pub fn on_recognized<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .recognized_handlers
+            .subscribe(f, "ResilientRecognizer.on_recognized error")
+    }
+
+    /// Registers handler of cancellations the recognizer did not recover from
+    /// (end of stream, non-transient errors or exhausted reconnect attempts).
+    pub fn on_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionCanceledEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .canceled_handlers
+            .subscribe(f, "ResilientRecognizer.on_canceled error")
+    }
+
+    pub fn on_reconnecting<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&ReconnectingEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .reconnecting_handlers
+            .subscribe(f, "ResilientRecognizer.on_reconnecting error")
+    }
+
+    pub fn on_reconnected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&ReconnectedEvent) + 'static + Send + Sync,
+    {
+        self.shared
+            .reconnected_handlers
+            .subscribe(f, "ResilientRecognizer.on_reconnected error")
+    }
+}
+
+impl Drop for ResilientRecognizer {
+    fn drop(&mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 16kHz 16-bit mono, i.e. 32 bytes per millisecond
+    fn replay_buffer(length: Duration) -> ReplayBuffer {
+        ReplayBuffer::new(length, 16000, 16, 1)
+    }
+
+    #[test]
+    fn rebase_span_test() {
+        // result after the transcribed audio is only shifted
+        assert_eq!(rebase_span(100, 50, 1000, 1000), Some((1100, 50)));
+        // result straddling the transcribed audio is clipped
+        assert_eq!(rebase_span(100, 50, 1000, 1120), Some((1120, 30)));
+        // result within the transcribed audio is dropped
+        assert_eq!(rebase_span(100, 50, 1000, 1150), None);
+        assert_eq!(rebase_span(0, 0, 1000, 1001), None);
+    }
+
+    #[test]
+    fn replay_buffer_capacity_test() {
+        let mut replay = replay_buffer(Duration::from_millis(10));
+        assert_eq!(replay.capacity, 320);
+        replay.push(&[0; 200]);
+        assert_eq!((replay.start(), replay.len()), (0, 62_500));
+        // overflow is discarded in whole blocks
+        replay.push(&[1; 201]);
+        assert_eq!(replay.written, 401);
+        assert_eq!(replay.data.len(), 319);
+        assert_eq!(replay.start(), 25_625);
+        assert_eq!(replay.data.front(), Some(&0));
+    }
+
+    #[test]
+    fn replay_buffer_discard_test() {
+        let mut replay = replay_buffer(Duration::from_secs(1));
+        replay.push(&[0; 3200]);
+        // 50ms are transcribed, discarded part is rounded down to whole blocks
+        replay.discard_before(500_003);
+        assert_eq!(replay.data.len(), 1600);
+        assert_eq!(replay.start(), 500_000);
+        // discarding already discarded audio is no-op
+        replay.discard_before(100_000);
+        assert_eq!(replay.data.len(), 1600);
+        // offset past the written audio empties the buffer
+        replay.discard_before(10_000_000);
+        assert_eq!(replay.data.len(), 0);
+        assert_eq!(replay.start(), 1_000_000);
+        replay.push(&[0; 32]);
+        assert_eq!((replay.start(), replay.len()), (1_000_000, 10_000));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::audio::AudioConfig;
+use crate::common::Subscription;
+use crate::error::{convert_err, Result};
+use crate::ffi::{
+    recognizer_create_source_language_recognizer_from_auto_detect_source_lang_config, SPXRECOHANDLE,
+};
+use crate::speech::{
+    AutoDetectSourceLanguageConfig, AutoDetectSourceLanguageResult, SessionEvent, SpeechConfig,
+    SpeechRecognitionCanceledEvent, SpeechRecognitionEvent, SpeechRecognitionResult,
+    SpeechRecognizer,
+};
+use std::fmt;
+use std::mem::MaybeUninit;
+
+/// SourceLanguageRecognizer only identifies the spoken language, no speech-to-text
+/// transcription is performed. Detected language can be obtained from recognition results
+/// via *AutoDetectSourceLanguageResult*.
+pub struct SourceLanguageRecognizer {
+    // native source language recognizer shares recognizer C API
+    inner: SpeechRecognizer,
+}
+
+impl fmt::Debug for SourceLanguageRecognizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceLanguageRecognizer")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl SourceLanguageRecognizer {
+    pub fn from_auto_detect_source_lang_config(
+        speech_config: SpeechConfig,
+        audio_config: AudioConfig,
+        lang_config: AutoDetectSourceLanguageConfig,
+    ) -> Result<SourceLanguageRecognizer> {
+        unsafe {
+            let mut handle: MaybeUninit<SPXRECOHANDLE> = MaybeUninit::uninit();
+            convert_err(
+                recognizer_create_source_language_recognizer_from_auto_detect_source_lang_config(
+                    handle.as_mut_ptr(),
+                    speech_config.handle.inner(),
+                    lang_config.handle.inner(),
+                    audio_config.handle.inner(),
+                ),
+                "SourceLanguageRecognizer.from_auto_detect_source_lang_config error",
+            )?;
+            Ok(SourceLanguageRecognizer {
+                inner: SpeechRecognizer::from_handle(handle.assume_init(), &audio_config)?,
+            })
+        }
+    }
+
+    /// Identifies the language of a single utterance.
+    pub async fn recognize_once_async(&mut self) -> Result<SpeechRecognitionResult> {
+        self.inner.recognize_once_async().await
+    }
+
+    /// Processes *text* as if it was recognized from audio,
+    /// see *SpeechRecognizer::recognize_text_once_async*.
+    pub async fn recognize_text_once_async(
+        &mut self,
+        text: &str,
+    ) -> Result<SpeechRecognitionResult> {
+        self.inner.recognize_text_once_async(text).await
+    }
+
+    /// Identifies the language of a single utterance and returns just the detected language.
+    pub async fn detect_language_once_async(&mut self) -> Result<AutoDetectSourceLanguageResult> {
+        let result = self.inner.recognize_once_async().await?;
+        AutoDetectSourceLanguageResult::from_speech_recognition_result(&result)
+    }
+
+    /// Asynchronously initiates continuous language identification.
+    pub async fn start_continuous_recognition_async(&mut self) -> Result<()> {
+        self.inner.start_continuous_recognition_async().await
+    }
+
+    /// Asynchronously terminates ongoing continuous language identification.
+    pub async fn stop_continuous_recognition_async(&mut self) -> Result<()> {
+        self.inner.stop_continuous_recognition_async().await
+    }
+
+    pub fn on_session_started<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.inner.on_session_started(f)
+    }
+
+    pub fn on_session_stopped<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.inner.on_session_stopped(f)
+    }
+
+    /// Registers handler of recognized event. Event result carries detected language,
+    /// see *AutoDetectSourceLanguageResult::from_speech_recognition_result*.
+    pub fn on_recognized<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.inner.on_recognized(f)
+    }
+
+    pub fn on_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionCanceledEvent) + 'static + Send + Sync,
+    {
+        self.inner.on_canceled(f)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::common::{
-    OutputFormat, ProfanityOption, PropertyCollection, PropertyId, ServicePropertyChannel,
-    SpeechSynthesisOutputFormat,
+    LanguageIdMode, LanguageIdPriority, OutputFormat, ProfanityOption, PropertyCollection,
+    PropertyId, ServicePropertyChannel, SpeechSynthesisOutputFormat,
 };
 use crate::error::{convert_err, Result};
 use crate::ffi::{
@@ -10,7 +10,7 @@ use crate::ffi::{
     speech_config_set_service_property, SmartHandle, SPXHANDLE, SPXPROPERTYBAGHANDLE,
     SPXSPEECHCONFIGHANDLE,
 };
-use crate::speech::EmbeddedSpeechConfig;
+use crate::speech::{EmbeddedSpeechConfig, RecognitionTuning};
 use std::ffi::CString;
 use std::mem::MaybeUninit;
 
@@ -344,6 +344,43 @@ impl SpeechConfig {
         self.set_property(PropertyId::SpeechServiceConnectionRecoLanguage, reco_lang)
     }
 
+    /// Gets language identification mode used together with AutoDetectSourceLanguageConfig.
+    pub fn get_language_id_mode(&self) -> Result<LanguageIdMode> {
+        let mode = self.get_property(PropertyId::SpeechServiceConnectionLanguageIdMode)?;
+        Ok(LanguageIdMode::from_property_value(&mode))
+    }
+
+    /// Sets language identification mode (at-start or continuous) used together with AutoDetectSourceLanguageConfig.
+    pub fn set_language_id_mode(&mut self, mode: LanguageIdMode) -> Result<()> {
+        self.set_property(
+            PropertyId::SpeechServiceConnectionLanguageIdMode,
+            mode.as_str().into(),
+        )
+    }
+
+    /// Sets whether language identification should prefer accuracy or latency.
+    /// Priority is applied to both at-start and continuous language identification.
+    pub fn set_language_id_priority(&mut self, priority: LanguageIdPriority) -> Result<()> {
+        self.set_property_by_string(
+            "SpeechServiceConnection_SingleLanguageIdPriority".into(),
+            priority.as_str().into(),
+        )?;
+        self.set_property_by_string(
+            "SpeechServiceConnection_ContinuousLanguageIdPriority".into(),
+            priority.as_str().into(),
+        )
+    }
+
+    /// Validates and applies typed recognition timeouts, segmentation and response options.
+    pub fn set_recognition_tuning(&mut self, tuning: &RecognitionTuning) -> Result<()> {
+        tuning.apply_to(self)
+    }
+
+    /// Reads back recognition options previously set on this config.
+    pub fn get_recognition_tuning(&self) -> Result<RecognitionTuning> {
+        RecognitionTuning::from_speech_config(self)
+    }
+
     pub fn get_output_format(&self) -> Result<OutputFormat> {
         let output_format =
             self.get_property(PropertyId::SpeechServiceResponseRequestDetailedResultTrueFalse)?;
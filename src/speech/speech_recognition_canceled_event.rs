@@ -1,5 +1,5 @@
 use crate::common::{CancellationErrorCode, CancellationReason, PropertyId};
-use crate::error::{convert_err, Result};
+use crate::error::{convert_err, Error, Result};
 use crate::ffi::{result_get_canceled_error_code, result_get_reason_canceled, SPXEVENTHANDLE};
 use crate::speech::SpeechRecognitionEvent;
 use log::*;
@@ -56,4 +56,15 @@ impl SpeechRecognitionCanceledEvent {
             })
         }
     }
+
+    /// Converts cancellation into Error so that it can be handled
+    /// the same way as errors returned by native API.
+    /// Returns None if cancellation was not caused by an error.
+    pub fn to_error(&self) -> Option<Error> {
+        Error::from_cancellation(
+            "SpeechRecognitionCanceledEvent",
+            &self.error_code,
+            &self.error_details,
+        )
+    }
 }
@@ -1,7 +1,8 @@
+use crate::audio::ChunkMetadata;
 use crate::common::{PropertyCollection, ResultReason};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
-    recognizer_result_handle_release, result_get_duration, result_get_offset,
+    recognizer_result_handle_release, result_get_channel, result_get_duration, result_get_offset,
     result_get_property_bag, result_get_reason, result_get_result_id, result_get_text, SmartHandle,
     SPXPROPERTYBAGHANDLE, SPXRESULTHANDLE,
 };
@@ -18,6 +19,12 @@ pub struct SpeechRecognitionResult {
     pub duration: String, //TBD: change to duration
     pub offset: String,   // TBD: change to duration
     pub properties: PropertyCollection,
+    /// Metadata of input stream chunk containing start of the result,
+    /// see *PushAudioInputStream::write_with_metadata*.
+    pub chunk_metadata: Option<ChunkMetadata>,
+    /// Audio channel the result originates from (multichannel conversation transcription,
+    /// see *MultichannelRecognizer*). None if not reported by the service.
+    pub channel: Option<u32>,
 }
 
 impl fmt::Debug for SpeechRecognitionResult {
@@ -28,6 +35,8 @@ impl fmt::Debug for SpeechRecognitionResult {
             .field("text", &self.text)
             .field("duration", &self.duration)
             .field("offset", &self.offset)
+            .field("chunk_metadata", &self.chunk_metadata)
+            .field("channel", &self.channel)
             .finish()
     }
 }
@@ -74,6 +83,14 @@ impl SpeechRecognitionResult {
                 "SpeechRecognitionResult::from_handle(result_get_offset) error",
             )?;
 
+            let mut channel: u32 = 0;
+            let channel = convert_err(
+                result_get_channel(handle, &mut channel),
+                "SpeechRecognitionResult::from_handle(result_get_channel) error",
+            )
+            .ok()
+            .map(|_| channel);
+
             let mut properties_handle: MaybeUninit<SPXPROPERTYBAGHANDLE> = MaybeUninit::uninit();
             ret = result_get_property_bag(handle, properties_handle.as_mut_ptr());
             convert_err(
@@ -94,6 +111,8 @@ impl SpeechRecognitionResult {
                 duration: (duration).to_string(),
                 offset: (offset).to_string(),
                 properties,
+                chunk_metadata: None,
+                channel,
             })
         }
     }
@@ -1,12 +1,16 @@
-use crate::audio::AudioConfig;
-use crate::common::{PropertyCollection, PropertyId};
+use crate::audio::{AudioConfig, ChunkMetadataTimeline};
+use crate::common::{
+    catch_callback_panic, EventHandlers, PropertyCollection, PropertyId, Subscription,
+};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
     recognizer_async_handle_release, recognizer_canceled_set_callback,
+    recognizer_create_conversation_transcriber_from_config,
     recognizer_create_speech_recognizer_from_auto_detect_source_lang_config,
     recognizer_create_speech_recognizer_from_config,
     recognizer_create_speech_recognizer_from_source_lang_config, recognizer_get_property_bag,
-    recognizer_handle_release, recognizer_recognize_once, recognizer_recognized_set_callback,
+    recognizer_handle_release, recognizer_recognize_once, recognizer_recognize_once_async_wait_for,
+    recognizer_recognize_text_once_async, recognizer_recognized_set_callback,
     recognizer_recognizing_set_callback, recognizer_session_started_set_callback,
     recognizer_session_stopped_set_callback, recognizer_speech_end_detected_set_callback,
     recognizer_speech_start_detected_set_callback, recognizer_start_continuous_recognition_async,
@@ -19,29 +23,110 @@ use crate::ffi::{
     SmartHandle, SPXASYNCHANDLE, SPXEVENTHANDLE, SPXPROPERTYBAGHANDLE, SPXRECOHANDLE,
     SPXRESULTHANDLE,
 };
+use crate::speech::recognition_telemetry;
 use crate::speech::{
-    AutoDetectSourceLanguageConfig, EmbeddedSpeechConfig, KeywordRecognitionModel,
+    AutoDetectSourceLanguageConfig, EmbeddedSpeechConfig, KeywordRecognitionModel, MetricsSink,
     RecognitionEvent, SessionEvent, SourceLanguageConfig, SpeechConfig,
     SpeechRecognitionCanceledEvent, SpeechRecognitionEvent, SpeechRecognitionResult,
 };
 use log::*;
 use std::boxed::Box;
+use std::ffi::CString;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::os::raw::c_void;
+use std::sync::Arc;
 
 /// A separate internal struct to hold all the callback closures for the speech recognizer events.
 /// By creating a separate struct, and then boxing this struct inside our SpeechRecognizer,
 /// we can ensure the SpeechRecognizer itself can be moved freely by end users,
 /// and the callbacks will remain at a fixed memory address on the heap.
 struct CallbackBag {
-    session_started_cb: Option<Box<dyn Fn(SessionEvent) + Send>>,
-    session_stopped_cb: Option<Box<dyn Fn(SessionEvent) + Send>>,
-    speech_start_detected_cb: Option<Box<dyn Fn(RecognitionEvent) + Send>>,
-    speech_end_detected_cb: Option<Box<dyn Fn(RecognitionEvent) + Send>>,
-    canceled_cb: Option<Box<dyn Fn(SpeechRecognitionCanceledEvent) + Send>>,
-    recognizing_cb: Option<Box<dyn Fn(SpeechRecognitionEvent) + Send>>,
-    recognized_cb: Option<Box<dyn Fn(SpeechRecognitionEvent) + Send>>,
+    session_started_handlers: EventHandlers<SessionEvent>,
+    session_stopped_handlers: EventHandlers<SessionEvent>,
+    speech_start_detected_handlers: EventHandlers<RecognitionEvent>,
+    speech_end_detected_handlers: EventHandlers<RecognitionEvent>,
+    canceled_handlers: EventHandlers<SpeechRecognitionCanceledEvent>,
+    recognizing_handlers: EventHandlers<SpeechRecognitionEvent>,
+    recognized_handlers: EventHandlers<SpeechRecognitionEvent>,
+    chunk_metadata: Option<ChunkMetadataTimeline>,
+}
+
+impl CallbackBag {
+    fn new(chunk_metadata: Option<ChunkMetadataTimeline>) -> Box<Self> {
+        Box::new(CallbackBag {
+            session_started_handlers: EventHandlers::new(
+                "SpeechRecognizer::session_started",
+                recognizer_session_started_set_callback,
+                SpeechRecognizer::cb_session_started,
+            ),
+            session_stopped_handlers: EventHandlers::new(
+                "SpeechRecognizer::session_stopped",
+                recognizer_session_stopped_set_callback,
+                SpeechRecognizer::cb_session_stopped,
+            ),
+            speech_start_detected_handlers: EventHandlers::new(
+                "SpeechRecognizer::speech_start_detected",
+                recognizer_speech_start_detected_set_callback,
+                SpeechRecognizer::cb_speech_start_detected,
+            ),
+            speech_end_detected_handlers: EventHandlers::new(
+                "SpeechRecognizer::speech_end_detected",
+                recognizer_speech_end_detected_set_callback,
+                SpeechRecognizer::cb_speech_end_detected,
+            ),
+            canceled_handlers: EventHandlers::new(
+                "SpeechRecognizer::canceled",
+                recognizer_canceled_set_callback,
+                SpeechRecognizer::cb_canceled,
+            ),
+            recognizing_handlers: EventHandlers::new(
+                "SpeechRecognizer::recognizing",
+                recognizer_recognizing_set_callback,
+                SpeechRecognizer::cb_recognizing,
+            ),
+            recognized_handlers: EventHandlers::new(
+                "SpeechRecognizer::recognized",
+                recognizer_recognized_set_callback,
+                SpeechRecognizer::cb_recognized,
+            ),
+            chunk_metadata,
+        })
+    }
+
+    /// Attaches metadata of input stream chunk the result originates from.
+    /// Final results release metadata of older chunks.
+    fn attach_chunk_metadata(&self, result: &mut SpeechRecognitionResult, is_final: bool) {
+        if let (Some(chunk_metadata), Ok(offset)) = (&self.chunk_metadata, result.offset.parse()) {
+            result.chunk_metadata = chunk_metadata.lookup(offset);
+            if is_final {
+                chunk_metadata.discard_before(offset);
+            }
+        }
+    }
+
+    /// Binds all handler lists to native recognizer handle.
+    /// Bag address is passed as context into native callbacks.
+    fn bind(&self, handle: SPXRECOHANDLE) {
+        let context = self as *const _ as *mut c_void;
+        self.session_started_handlers.bind(handle, context);
+        self.session_stopped_handlers.bind(handle, context);
+        self.speech_start_detected_handlers.bind(handle, context);
+        self.speech_end_detected_handlers.bind(handle, context);
+        self.canceled_handlers.bind(handle, context);
+        self.recognizing_handlers.bind(handle, context);
+        self.recognized_handlers.bind(handle, context);
+    }
+
+    fn unbind(&self) {
+        self.session_started_handlers.unbind();
+        self.session_stopped_handlers.unbind();
+        self.speech_start_detected_handlers.unbind();
+        self.speech_end_detected_handlers.unbind();
+        self.canceled_handlers.unbind();
+        self.recognizing_handlers.unbind();
+        self.recognized_handlers.unbind();
+    }
 }
 
 /// SpeechRecognizer struct holds functionality for speech-to-text recognition.
@@ -52,9 +137,16 @@ pub struct SpeechRecognizer {
     handle_async_stop_continuous: Option<SmartHandle<SPXASYNCHANDLE>>,
     handle_async_start_keyword: Option<SmartHandle<SPXASYNCHANDLE>>,
     handle_async_stop_keyword: Option<SmartHandle<SPXASYNCHANDLE>>,
+    // handlers registered by enable_telemetry
+    telemetry: Vec<Subscription>,
     callback_bag: Box<CallbackBag>,
 }
 
+// Methods taking &self only read properties and subscribe handlers, native
+// recognizer guards its property bag internally and handler lists are behind Mutex.
+// Async operation handles and telemetry subscriptions are replaced only via &mut self.
+unsafe impl Sync for SpeechRecognizer {}
+
 impl fmt::Debug for SpeechRecognizer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SpeechRecognizer")
@@ -67,7 +159,10 @@ impl fmt::Debug for SpeechRecognizer {
 impl SpeechRecognizer {
     /// # Safety
     /// `handle` must be a valid handle to a live speech recognizer.
-    unsafe fn from_handle(handle: SPXRECOHANDLE) -> Result<SpeechRecognizer> {
+    pub(crate) unsafe fn from_handle(
+        handle: SPXRECOHANDLE,
+        audio_config: &AudioConfig,
+    ) -> Result<SpeechRecognizer> {
         unsafe {
             let mut prop_bag_handle: MaybeUninit<SPXPROPERTYBAGHANDLE> = MaybeUninit::uninit();
             let ret = recognizer_get_property_bag(handle, prop_bag_handle.as_mut_ptr());
@@ -82,19 +177,13 @@ impl SpeechRecognizer {
                 handle_async_stop_continuous: None,
                 handle_async_start_keyword: None,
                 handle_async_stop_keyword: None,
+                telemetry: vec![],
                 // Here we return a boxed instance of the CallbackBag,
                 // ensure that the pointer we provide to the C library
                 // points to a stable, heap-allocated location that holds the callbacks.
-                callback_bag: Box::new(CallbackBag {
-                    session_started_cb: None,
-                    session_stopped_cb: None,
-                    speech_start_detected_cb: None,
-                    speech_end_detected_cb: None,
-                    canceled_cb: None,
-                    recognizing_cb: None,
-                    recognized_cb: None,
-                }),
+                callback_bag: CallbackBag::new(audio_config.chunk_metadata.clone()),
             };
+            result.callback_bag.bind(handle);
             Ok(result)
         }
     }
@@ -113,7 +202,28 @@ impl SpeechRecognizer {
                 ),
                 "SpeechRecognizer.from_config error",
             )?;
-            SpeechRecognizer::from_handle(handle.assume_init())
+            SpeechRecognizer::from_handle(handle.assume_init(), &audio_config)
+        }
+    }
+
+    /// Creates conversation transcriber, i.e. recognizer which accepts multichannel audio
+    /// (up to eight channels) and reports channel of the results (see *SpeechRecognitionResult::channel*).
+    /// Events and continuous recognition are the same as for speech recognizer.
+    pub fn from_conversation_transcriber_config(
+        speech_config: SpeechConfig,
+        audio_config: AudioConfig,
+    ) -> Result<SpeechRecognizer> {
+        unsafe {
+            let mut handle: MaybeUninit<SPXRECOHANDLE> = MaybeUninit::uninit();
+            convert_err(
+                recognizer_create_conversation_transcriber_from_config(
+                    handle.as_mut_ptr(),
+                    speech_config.handle.inner(),
+                    audio_config.handle.inner(),
+                ),
+                "SpeechRecognizer.from_conversation_transcriber_config error",
+            )?;
+            SpeechRecognizer::from_handle(handle.assume_init(), &audio_config)
         }
     }
 
@@ -140,7 +250,7 @@ impl SpeechRecognizer {
                 ),
                 "SpeechRecognizer.from_auto_detect_source_lang_config error",
             )?;
-            SpeechRecognizer::from_handle(handle.assume_init())
+            SpeechRecognizer::from_handle(handle.assume_init(), &audio_config)
         }
     }
 
@@ -160,7 +270,7 @@ impl SpeechRecognizer {
                 ),
                 "SpeechRecognizer.from_source_lang_config error",
             )?;
-            SpeechRecognizer::from_handle(handle.assume_init())
+            SpeechRecognizer::from_handle(handle.assume_init(), &audio_config)
         }
     }
 
@@ -177,64 +287,36 @@ impl SpeechRecognizer {
     where
         F: Fn(SessionEvent) + 'static + Send,
     {
-        self.callback_bag.session_started_cb = Some(Box::new(f));
-        unsafe {
-            let ret = recognizer_session_started_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_session_started),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_session_started_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .session_started_handlers
+            .set_legacy(f, "SpeechRecognizer.set_session_started_cb error")
     }
 
     pub fn set_session_stopped_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SessionEvent) + 'static + Send,
     {
-        self.callback_bag.session_stopped_cb = Some(Box::new(f));
-        unsafe {
-            let ret = recognizer_session_stopped_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_session_stopped),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_session_stopped_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .session_stopped_handlers
+            .set_legacy(f, "SpeechRecognizer.set_session_stopped_cb error")
     }
 
     pub fn set_speech_start_detected_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(RecognitionEvent) + 'static + Send,
     {
-        self.callback_bag.speech_start_detected_cb = Some(Box::new(f));
-        unsafe {
-            let ret = recognizer_speech_start_detected_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_speech_start_detected),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_speech_start_detected_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .speech_start_detected_handlers
+            .set_legacy(f, "SpeechRecognizer.set_speech_start_detected_cb error")
     }
 
     pub fn set_speech_end_detected_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(RecognitionEvent) + 'static + Send,
     {
-        self.callback_bag.speech_end_detected_cb = Some(Box::new(f));
-        unsafe {
-            let ret = recognizer_speech_end_detected_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_speech_end_detected),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_speech_end_detected_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .speech_end_detected_handlers
+            .set_legacy(f, "SpeechRecognizer.set_speech_end_detected_cb error")
     }
 
     /// Canceled signals for events containing canceled recognition results
@@ -244,52 +326,109 @@ impl SpeechRecognizer {
     where
         F: Fn(SpeechRecognitionCanceledEvent) + 'static + Send,
     {
-        self.callback_bag.canceled_cb = Some(Box::new(f));
-        unsafe {
-            let ret = recognizer_canceled_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_canceled),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_canceled_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .canceled_handlers
+            .set_legacy(f, "SpeechRecognizer.set_canceled_cb error")
     }
 
     pub fn set_recognizing_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechRecognitionEvent) + 'static + Send,
     {
-        self.callback_bag.recognizing_cb = Some(Box::new(f));
-        unsafe {
-            trace!("calling recognizer_recognizing_set_callback");
-            let ret = recognizer_recognizing_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_recognizing),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_recognizing_cb error")?;
-            trace!("called recognizer_recognizing_set_callback");
-            Ok(())
-        }
+        self.callback_bag
+            .recognizing_handlers
+            .set_legacy(f, "SpeechRecognizer.set_recognizing_cb error")
     }
 
     pub fn set_recognized_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechRecognitionEvent) + 'static + Send,
     {
-        self.callback_bag.recognized_cb = Some(Box::new(f));
-        unsafe {
-            trace!("calling recognizer_recognized_set_callback");
-            let ret = recognizer_recognized_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_recognized),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechRecognizer.set_recognized_cb error")?;
-            trace!("called recognizer_recognized_set_callback");
-            Ok(())
-        }
+        self.callback_bag
+            .recognized_handlers
+            .set_legacy(f, "SpeechRecognizer.set_recognized_cb error")
+    }
+
+    /// Opens tracing span for each recognition session (with feature *tracing*) and reports
+    /// latencies and cancellations to *sink*. Telemetry stays enabled for the lifetime of the recognizer,
+    /// calling this method again replaces previously enabled telemetry.
+    pub fn enable_telemetry(&mut self, sink: Option<Arc<dyn MetricsSink>>) -> Result<()> {
+        // previous handlers are unregistered first so that nothing is reported twice
+        self.telemetry.clear();
+        self.telemetry = recognition_telemetry::attach(self, &self.properties, sink)?;
+        Ok(())
+    }
+
+    /// Registers additional handler of session started event. Unlike *set_session_started_cb*
+    /// any number of handlers can be registered, each of them is unregistered
+    /// when returned *Subscription* is dropped.
+    pub fn on_session_started<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .session_started_handlers
+            .subscribe(f, "SpeechRecognizer.on_session_started error")
+    }
+
+    /// Registers additional handler of session stopped event, see *on_session_started*.
+    pub fn on_session_stopped<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SessionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .session_stopped_handlers
+            .subscribe(f, "SpeechRecognizer.on_session_stopped error")
+    }
+
+    /// Registers additional handler of speech start detected event, see *on_session_started*.
+    pub fn on_speech_start_detected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&RecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .speech_start_detected_handlers
+            .subscribe(f, "SpeechRecognizer.on_speech_start_detected error")
+    }
+
+    /// Registers additional handler of speech end detected event, see *on_session_started*.
+    pub fn on_speech_end_detected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&RecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .speech_end_detected_handlers
+            .subscribe(f, "SpeechRecognizer.on_speech_end_detected error")
+    }
+
+    /// Registers additional handler of canceled event, see *on_session_started*.
+    pub fn on_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionCanceledEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .canceled_handlers
+            .subscribe(f, "SpeechRecognizer.on_canceled error")
+    }
+
+    /// Registers additional handler of recognizing event, see *on_session_started*.
+    pub fn on_recognizing<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .recognizing_handlers
+            .subscribe(f, "SpeechRecognizer.on_recognizing error")
+    }
+
+    /// Registers additional handler of recognized event, see *on_session_started*.
+    pub fn on_recognized<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechRecognitionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .recognized_handlers
+            .subscribe(f, "SpeechRecognizer.on_recognized error")
     }
 
     #[allow(non_snake_case)]
@@ -300,19 +439,18 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_session_started called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.session_started_cb {
-            trace!("session_started_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_session_started", (), || {
             match SessionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.session_started_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_session_started error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -323,19 +461,18 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_session_stopped called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.session_stopped_cb {
-            trace!("cb_session_stopped defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_session_stopped", (), || {
             match SessionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.session_stopped_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_session_stopped error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -346,19 +483,18 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_speech_start_detected called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.speech_start_detected_cb {
-            trace!("speech_start_detected_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_speech_start_detected", (), || {
             match RecognitionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.speech_start_detected_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_speech_start_detected error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -369,19 +505,18 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_speech_end_detected called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.speech_end_detected_cb {
-            trace!("speech_end_detected_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_speech_end_detected", (), || {
             match RecognitionEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.speech_end_detected_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_speech_end_detected error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -392,19 +527,18 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_canceled called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.canceled_cb {
-            trace!("canceled_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_canceled", (), || {
             match SpeechRecognitionCanceledEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.canceled_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_canceled error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -415,19 +549,19 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_recognizing called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.recognizing_cb {
-            trace!("recognizing_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_recognizing", (), || {
             match SpeechRecognitionEvent::from_handle(hevent) {
-                Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                Ok(mut event) => {
+                    callback_bag.attach_chunk_metadata(&mut event.result, false);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.recognizing_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_recognizing error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -438,19 +572,19 @@ impl SpeechRecognizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechRecognizer::cb_recognized called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.recognized_cb {
-            trace!("recognized_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechRecognizer::cb_recognized", (), || {
             match SpeechRecognitionEvent::from_handle(hevent) {
-                Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                Ok(mut event) => {
+                    callback_bag.attach_chunk_metadata(&mut event.result, true);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.recognized_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!("SpeechRecognizer::cb_recognized error {:?}", err);
                 }
             }
-        }
+        })
     }
 
     /// Starts speech recognition, and returns after a single utterance is recognized.
@@ -464,6 +598,44 @@ impl SpeechRecognizer {
             let mut handle_result: MaybeUninit<SPXRESULTHANDLE> = MaybeUninit::uninit();
             let ret = recognizer_recognize_once(self.handle.inner(), handle_result.as_mut_ptr());
             convert_err(ret, "SpeechRecognizer.recognize_once_async error")?;
+            let mut result = SpeechRecognitionResult::from_handle(handle_result.assume_init())?;
+            self.callback_bag.attach_chunk_metadata(&mut result, true);
+            Ok(result)
+        }
+    }
+
+    /// Processes *text* as if it was recognized from audio, without any audio
+    /// being sent to the service. Result is of the same type as of *recognize_once_async*,
+    /// e.g. for testing of downstream logic.
+    pub async fn recognize_text_once_async(
+        &mut self,
+        text: &str,
+    ) -> Result<SpeechRecognitionResult> {
+        unsafe {
+            let c_text = CString::new(text)?;
+            let mut handle_async: MaybeUninit<SPXASYNCHANDLE> = MaybeUninit::uninit();
+            let ret = recognizer_recognize_text_once_async(
+                self.handle.inner(),
+                c_text.as_ptr(),
+                handle_async.as_mut_ptr(),
+            );
+            convert_err(ret, "SpeechRecognizer.recognize_text_once_async error")?;
+            let handle_async = SmartHandle::create(
+                "handle_async_recognize_text_once",
+                handle_async.assume_init(),
+                recognizer_async_handle_release,
+            );
+
+            let mut handle_result: MaybeUninit<SPXRESULTHANDLE> = MaybeUninit::uninit();
+            let ret = recognizer_recognize_once_async_wait_for(
+                handle_async.inner(),
+                u32::MAX,
+                handle_result.as_mut_ptr(),
+            );
+            convert_err(
+                ret,
+                "SpeechRecognizer.recognize_text_once_async(wait_for) error",
+            )?;
             SpeechRecognitionResult::from_handle(handle_result.assume_init())
         }
     }
@@ -615,3 +787,10 @@ impl SpeechRecognizer {
             .set_property(PropertyId::SpeechServiceAuthorizationToken, token)
     }
 }
+
+impl Drop for SpeechRecognizer {
+    fn drop(&mut self) {
+        // outstanding subscriptions must not touch native handle once it is released
+        self.callback_bag.unbind();
+    }
+}
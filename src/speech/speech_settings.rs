@@ -0,0 +1,504 @@
+use crate::common::{OutputFormat, ProfanityOption, PropertyId, SpeechSynthesisOutputFormat};
+use crate::dialog::{BotFrameworkConfig, CustomCommandsConfig};
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use crate::speech::{EmbeddedSpeechConfig, SpeechConfig};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+const REDACTED: &str = "***";
+
+/// Properties holding secrets, their values are redacted in *Debug* output.
+const SECRET_PROPERTIES: [PropertyId; 5] = [
+    PropertyId::SpeechServiceConnectionKey,
+    PropertyId::SpeechServiceAuthorizationToken,
+    PropertyId::SpeechServiceConnectionProxyPassword,
+    PropertyId::SpeechServiceConnectionRecoModelKey,
+    PropertyId::SpeechServiceConnectionSynthModelKey,
+];
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// Settings of embedded (offline) speech, see *EmbeddedSpeechConfig*.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddedSettings {
+    /// Folders searched for offline models.
+    pub model_paths: Vec<PathBuf>,
+    pub recognition_model: Option<String>,
+    pub recognition_model_key: Option<String>,
+    pub synthesis_voice: Option<String>,
+    pub synthesis_model_key: Option<String>,
+}
+
+/// SpeechSettings is serializable description of speech configuration which can be loaded
+/// from TOML/JSON files and environment variables and then turned into *SpeechConfig*,
+/// *EmbeddedSpeechConfig*, *BotFrameworkConfig* or *CustomCommandsConfig*.
+/// Only settings which are set (Some) are applied. Secrets are redacted in *Debug* output.
+///
+/// Example TOML file:
+/// ```toml
+/// region = "westeurope"
+/// recognition_language = "en-US"
+/// voice = "en-US-JennyNeural"
+/// synthesis_output_format = "Riff16Khz16BitMonoPcm"
+///
+/// [properties]
+/// SpeechServiceConnectionInitialSilenceTimeoutMs = "5000"
+/// ```
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechSettings {
+    pub subscription_key: Option<String>,
+    pub auth_token: Option<String>,
+    /// Service endpoint URL, takes precedence over *host* and *region*.
+    pub endpoint: Option<String>,
+    /// Service host URL, takes precedence over *region*.
+    pub host: Option<String>,
+    pub region: Option<String>,
+    pub proxy: Option<ProxySettings>,
+    pub recognition_language: Option<String>,
+    pub synthesis_language: Option<String>,
+    pub voice: Option<String>,
+    pub output_format: Option<OutputFormat>,
+    pub synthesis_output_format: Option<SpeechSynthesisOutputFormat>,
+    pub profanity: Option<ProfanityOption>,
+    /// Bot id used by *to_bot_framework_config*.
+    pub bot_id: Option<String>,
+    /// Application id used by *to_custom_commands_config*.
+    pub application_id: Option<String>,
+    pub embedded: Option<EmbeddedSettings>,
+    pub properties: BTreeMap<PropertyId, String>,
+    /// Properties set by name, e.g. properties not covered by *PropertyId*.
+    pub string_properties: BTreeMap<String, String>,
+}
+
+fn redact(value: &Option<String>) -> Option<&str> {
+    value.as_ref().map(|_| REDACTED)
+}
+
+/// True if property set by string name is one of *SECRET_PROPERTIES*. Both native names
+/// (e.g. *SpeechServiceConnection_Key*) and *PropertyId* variant names match, case-insensitively.
+fn is_secret_name(name: &str) -> bool {
+    let normalized = name.replace('_', "").to_lowercase();
+    SECRET_PROPERTIES
+        .iter()
+        .any(|id| format!("{:?}", id).to_lowercase() == normalized)
+}
+
+impl fmt::Debug for ProxySettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxySettings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &redact(&self.password))
+            .finish()
+    }
+}
+
+impl fmt::Debug for EmbeddedSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbeddedSettings")
+            .field("model_paths", &self.model_paths)
+            .field("recognition_model", &self.recognition_model)
+            .field(
+                "recognition_model_key",
+                &redact(&self.recognition_model_key),
+            )
+            .field("synthesis_voice", &self.synthesis_voice)
+            .field("synthesis_model_key", &redact(&self.synthesis_model_key))
+            .finish()
+    }
+}
+
+impl fmt::Debug for SpeechSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let properties: BTreeMap<&PropertyId, &str> = self
+            .properties
+            .iter()
+            .map(|(id, value)| {
+                if SECRET_PROPERTIES.contains(id) {
+                    (id, REDACTED)
+                } else {
+                    (id, value.as_str())
+                }
+            })
+            .collect();
+        let string_properties: BTreeMap<&str, &str> = self
+            .string_properties
+            .iter()
+            .map(|(name, value)| {
+                if is_secret_name(name) {
+                    (name.as_str(), REDACTED)
+                } else {
+                    (name.as_str(), value.as_str())
+                }
+            })
+            .collect();
+        f.debug_struct("SpeechSettings")
+            .field("subscription_key", &redact(&self.subscription_key))
+            .field("auth_token", &redact(&self.auth_token))
+            .field("endpoint", &self.endpoint)
+            .field("host", &self.host)
+            .field("region", &self.region)
+            .field("proxy", &self.proxy)
+            .field("recognition_language", &self.recognition_language)
+            .field("synthesis_language", &self.synthesis_language)
+            .field("voice", &self.voice)
+            .field("output_format", &self.output_format)
+            .field("synthesis_output_format", &self.synthesis_output_format)
+            .field("profanity", &self.profanity)
+            .field("bot_id", &self.bot_id)
+            .field("application_id", &self.application_id)
+            .field("embedded", &self.embedded)
+            .field("properties", &properties)
+            .field("string_properties", &string_properties)
+            .finish()
+    }
+}
+
+fn invalid_settings(message: &str) -> Error {
+    Error::new(
+        format!("SpeechSettings error: {}", message),
+        ErrorRootCause::ApiError(SpxError::InvalidArg),
+    )
+}
+
+/// Parses enum from its variant name, e.g. *Detailed*.
+fn parse_enum<T: DeserializeOwned>(name: &str, value: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_owned()))
+        .map_err(|_| invalid_settings(&format!("invalid value {} of {}", value, name)))
+}
+
+impl ProxySettings {
+    fn merge(self, other: ProxySettings) -> Self {
+        ProxySettings {
+            host: other.host.or(self.host),
+            port: other.port.or(self.port),
+            username: other.username.or(self.username),
+            password: other.password.or(self.password),
+        }
+    }
+}
+
+impl EmbeddedSettings {
+    fn merge(self, other: EmbeddedSettings) -> Self {
+        EmbeddedSettings {
+            model_paths: if other.model_paths.is_empty() {
+                self.model_paths
+            } else {
+                other.model_paths
+            },
+            recognition_model: other.recognition_model.or(self.recognition_model),
+            recognition_model_key: other.recognition_model_key.or(self.recognition_model_key),
+            synthesis_voice: other.synthesis_voice.or(self.synthesis_voice),
+            synthesis_model_key: other.synthesis_model_key.or(self.synthesis_model_key),
+        }
+    }
+}
+
+fn merge_section<T>(base: Option<T>, other: Option<T>, merge: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (base, other) {
+        (Some(base), Some(other)) => Some(merge(base, other)),
+        (base, other) => other.or(base),
+    }
+}
+
+impl SpeechSettings {
+    /// Loads settings from TOML (*.toml*) or JSON (*.json*) file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(toml::from_str(&content)?),
+            Some("json") => Ok(serde_json::from_str(&content)?),
+            _ => Err(invalid_settings(&format!(
+                "unsupported settings file {}, expected .toml or .json",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Loads settings from environment variables starting with *prefix*, see *from_vars*.
+    pub fn from_env(prefix: &str) -> Result<Self> {
+        SpeechSettings::from_vars(prefix, std::env::vars())
+    }
+
+    /// Loads settings from *vars* starting with *prefix*. Names are upper case field names,
+    /// e.g. with prefix *SPEECH_*: *SPEECH_SUBSCRIPTION_KEY*, *SPEECH_REGION*, *SPEECH_PROXY_HOST*,
+    /// *SPEECH_EMBEDDED_MODEL_PATHS* (separated by path separator), *SPEECH_OUTPUT_FORMAT=Detailed*.
+    /// *SPEECH_PROPERTY_&lt;name&gt;* sets property, *name* is either *PropertyId* variant
+    /// or property string name. Unknown variables are ignored.
+    pub fn from_vars<I>(prefix: &str, vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut settings = SpeechSettings::default();
+        for (name, value) in vars {
+            let name = match name.strip_prefix(prefix) {
+                Some(name) => name,
+                None => continue,
+            };
+            if let Some(property) = name.strip_prefix("PROPERTY_") {
+                match parse_enum::<PropertyId>(name, property) {
+                    Ok(id) => settings.properties.insert(id, value),
+                    Err(_) => settings
+                        .string_properties
+                        .insert(property.to_owned(), value),
+                };
+                continue;
+            }
+            if let Some(proxy_field) = name.strip_prefix("PROXY_") {
+                let proxy = settings.proxy.get_or_insert_with(ProxySettings::default);
+                match proxy_field {
+                    "HOST" => proxy.host = Some(value),
+                    "PORT" => {
+                        proxy.port = Some(value.trim().parse().map_err(|_| {
+                            invalid_settings(&format!("invalid value {} of {}", value, name))
+                        })?)
+                    }
+                    "USERNAME" => proxy.username = Some(value),
+                    "PASSWORD" => proxy.password = Some(value),
+                    _ => {}
+                }
+                continue;
+            }
+            if let Some(embedded_field) = name.strip_prefix("EMBEDDED_") {
+                let embedded = settings
+                    .embedded
+                    .get_or_insert_with(EmbeddedSettings::default);
+                match embedded_field {
+                    "MODEL_PATHS" => embedded.model_paths = std::env::split_paths(&value).collect(),
+                    "RECOGNITION_MODEL" => embedded.recognition_model = Some(value),
+                    "RECOGNITION_MODEL_KEY" => embedded.recognition_model_key = Some(value),
+                    "SYNTHESIS_VOICE" => embedded.synthesis_voice = Some(value),
+                    "SYNTHESIS_MODEL_KEY" => embedded.synthesis_model_key = Some(value),
+                    _ => {}
+                }
+                continue;
+            }
+            match name {
+                "SUBSCRIPTION_KEY" => settings.subscription_key = Some(value),
+                "AUTH_TOKEN" => settings.auth_token = Some(value),
+                "ENDPOINT" => settings.endpoint = Some(value),
+                "HOST" => settings.host = Some(value),
+                "REGION" => settings.region = Some(value),
+                "RECOGNITION_LANGUAGE" => settings.recognition_language = Some(value),
+                "SYNTHESIS_LANGUAGE" => settings.synthesis_language = Some(value),
+                "VOICE" => settings.voice = Some(value),
+                "OUTPUT_FORMAT" => settings.output_format = Some(parse_enum(name, &value)?),
+                "SYNTHESIS_OUTPUT_FORMAT" => {
+                    settings.synthesis_output_format = Some(parse_enum(name, &value)?)
+                }
+                "PROFANITY" => settings.profanity = Some(parse_enum(name, &value)?),
+                "BOT_ID" => settings.bot_id = Some(value),
+                "APPLICATION_ID" => settings.application_id = Some(value),
+                _ => {}
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Loads layered settings: each existing file (in given order) overrides previous ones
+    /// and environment variables with *env_prefix* override all files.
+    /// Files which do not exist are skipped.
+    pub fn load<P: AsRef<Path>>(files: &[P], env_prefix: &str) -> Result<Self> {
+        let mut settings = SpeechSettings::default();
+        for file in files {
+            if file.as_ref().exists() {
+                settings = settings.merge(SpeechSettings::from_file(file)?);
+            }
+        }
+        Ok(settings.merge(SpeechSettings::from_env(env_prefix)?))
+    }
+
+    /// Returns settings where values set in *other* override values of *self*.
+    /// Proxy and embedded sections are merged field by field, properties are merged by key.
+    pub fn merge(mut self, other: SpeechSettings) -> Self {
+        self.properties.extend(other.properties);
+        self.string_properties.extend(other.string_properties);
+        SpeechSettings {
+            subscription_key: other.subscription_key.or(self.subscription_key),
+            auth_token: other.auth_token.or(self.auth_token),
+            endpoint: other.endpoint.or(self.endpoint),
+            host: other.host.or(self.host),
+            region: other.region.or(self.region),
+            proxy: merge_section(self.proxy, other.proxy, ProxySettings::merge),
+            recognition_language: other.recognition_language.or(self.recognition_language),
+            synthesis_language: other.synthesis_language.or(self.synthesis_language),
+            voice: other.voice.or(self.voice),
+            output_format: other.output_format.or(self.output_format),
+            synthesis_output_format: other
+                .synthesis_output_format
+                .or(self.synthesis_output_format),
+            profanity: other.profanity.or(self.profanity),
+            bot_id: other.bot_id.or(self.bot_id),
+            application_id: other.application_id.or(self.application_id),
+            embedded: merge_section(self.embedded, other.embedded, EmbeddedSettings::merge),
+            properties: self.properties,
+            string_properties: self.string_properties,
+        }
+    }
+
+    /// Region together with subscription key or authorization token required by dialog configs.
+    fn region_credentials(&self) -> Result<(&str, std::result::Result<&str, &str>)> {
+        let region = self
+            .region
+            .as_deref()
+            .ok_or_else(|| invalid_settings("region is required"))?;
+        match (&self.subscription_key, &self.auth_token) {
+            (Some(key), _) => Ok((region, Ok(key))),
+            (None, Some(token)) => Ok((region, Err(token))),
+            (None, None) => Err(invalid_settings(
+                "subscription_key or auth_token is required",
+            )),
+        }
+    }
+
+    /// Creates *SpeechConfig* from endpoint, host or region (in this order of precedence)
+    /// and applies remaining settings.
+    pub fn to_speech_config(&self) -> Result<SpeechConfig> {
+        let key = self.subscription_key.as_deref();
+        let mut config = match (&self.endpoint, &self.host, &self.region) {
+            (Some(endpoint), _, _) => match key {
+                Some(key) => SpeechConfig::from_endpoint_with_subscription(endpoint.as_str(), key)?,
+                None => SpeechConfig::from_endpoint(endpoint.as_str())?,
+            },
+            (None, Some(host), _) => match key {
+                Some(key) => SpeechConfig::from_host_with_subscription(host.as_str(), key)?,
+                None => SpeechConfig::from_host(host.as_str())?,
+            },
+            (None, None, Some(_)) => match self.region_credentials()? {
+                (region, Ok(key)) => SpeechConfig::from_subscription(key, region)?,
+                (region, Err(token)) => SpeechConfig::from_auth_token(token, region)?,
+            },
+            (None, None, None) => {
+                return Err(invalid_settings("endpoint, host or region is required"))
+            }
+        };
+        // token with endpoint or host is set separately
+        if let (Some(token), None) = (&self.auth_token, key) {
+            if self.endpoint.is_some() || self.host.is_some() {
+                config.set_auth_token(token.clone())?;
+            }
+        }
+        self.apply_to(&mut config)?;
+        Ok(config)
+    }
+
+    /// Creates *EmbeddedSpeechConfig* from *embedded* section and applies remaining settings.
+    pub fn to_embedded_speech_config(&self) -> Result<EmbeddedSpeechConfig> {
+        let embedded = self
+            .embedded
+            .as_ref()
+            .ok_or_else(|| invalid_settings("embedded section is required"))?;
+        let mut config = EmbeddedSpeechConfig::from_paths(embedded.model_paths.clone())?;
+        if let Some(model) = &embedded.recognition_model {
+            config.config.set_property(
+                PropertyId::SpeechServiceConnectionRecoModelName,
+                model.clone(),
+            )?;
+            config.config.set_property(
+                PropertyId::SpeechServiceConnectionRecoModelKey,
+                embedded.recognition_model_key.clone().unwrap_or_default(),
+            )?;
+        }
+        if let Some(voice) = &embedded.synthesis_voice {
+            config.set_speech_synthesis_voice(
+                voice.as_str(),
+                embedded.synthesis_model_key.as_deref().unwrap_or_default(),
+            )?;
+        }
+        self.apply_to(&mut config.config)?;
+        Ok(config)
+    }
+
+    /// Creates *BotFrameworkConfig* (with *bot_id* if set) and applies remaining settings.
+    pub fn to_bot_framework_config(&self) -> Result<BotFrameworkConfig> {
+        let mut config = match (self.region_credentials()?, &self.bot_id) {
+            ((region, Ok(key)), None) => BotFrameworkConfig::from_subscription(key, region)?,
+            ((region, Ok(key)), Some(bot_id)) => {
+                BotFrameworkConfig::from_subscription_and_bot_id(key, region, bot_id.as_str())?
+            }
+            ((region, Err(token)), None) => BotFrameworkConfig::from_auth_token(token, region)?,
+            ((region, Err(token)), Some(bot_id)) => {
+                BotFrameworkConfig::from_auth_token_and_bot_id(token, region, bot_id.as_str())?
+            }
+        };
+        self.apply_to(&mut config.config)?;
+        Ok(config)
+    }
+
+    /// Creates *CustomCommandsConfig* (*application_id* is required) and applies remaining settings.
+    pub fn to_custom_commands_config(&self) -> Result<CustomCommandsConfig> {
+        let application_id = self
+            .application_id
+            .as_deref()
+            .ok_or_else(|| invalid_settings("application_id is required"))?;
+        let mut config = match self.region_credentials()? {
+            (region, Ok(key)) => {
+                CustomCommandsConfig::from_subscription(application_id, key, region)?
+            }
+            (region, Err(token)) => {
+                CustomCommandsConfig::from_auth_token(application_id, token, region)?
+            }
+        };
+        self.apply_to(&mut config.config)?;
+        Ok(config)
+    }
+
+    /// Applies proxy, languages, voice, formats, profanity and properties to *config*.
+    /// Authentication settings are not applied, they are used when config is created.
+    pub fn apply_to(&self, config: &mut SpeechConfig) -> Result<()> {
+        if let Some(proxy) = &self.proxy {
+            match (&proxy.host, proxy.port) {
+                (Some(host), Some(port)) => match (&proxy.username, &proxy.password) {
+                    (Some(username), password) => config.set_proxy_with_usrname_and_pwd(
+                        host.clone(),
+                        port.into(),
+                        username.clone(),
+                        password.clone().unwrap_or_default(),
+                    )?,
+                    (None, _) => config.set_proxy(host.clone(), port.into())?,
+                },
+                (None, None) => {}
+                _ => return Err(invalid_settings("proxy requires both host and port")),
+            }
+        }
+        if let Some(language) = &self.recognition_language {
+            config.set_speech_recognition_language(language.clone())?;
+        }
+        if let Some(language) = &self.synthesis_language {
+            config.set_get_speech_synthesis_language(language.clone())?;
+        }
+        if let Some(voice) = &self.voice {
+            config.set_get_speech_synthesis_voice_name(voice.clone())?;
+        }
+        if let Some(format) = self.output_format {
+            config.set_get_output_format(format)?;
+        }
+        if let Some(format) = self.synthesis_output_format {
+            config.set_speech_synthesis_output_format(format)?;
+        }
+        if let Some(profanity) = self.profanity {
+            config.set_profanity_option(profanity)?;
+        }
+        for (id, value) in &self.properties {
+            config.set_property(*id, value.clone())?;
+        }
+        for (name, value) in &self.string_properties {
+            config.set_property_by_string(name.clone(), value.clone())?;
+        }
+        Ok(())
+    }
+}
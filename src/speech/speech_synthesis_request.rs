@@ -1,9 +1,10 @@
-use crate::common::PropertyCollection;
+use crate::common::{PropertyCollection, PropertyId};
 use crate::error::{convert_err, Result};
 use crate::ffi::{
     speech_synthesis_request_create, speech_synthesis_request_finish,
     speech_synthesis_request_get_property_bag, speech_synthesis_request_release,
-    speech_synthesis_request_send_text_piece, SmartHandle, SPXPROPERTYBAGHANDLE, SPXREQUESTHANDLE,
+    speech_synthesis_request_send_text_piece, speech_synthesis_request_set_voice, SmartHandle,
+    SPXPROPERTYBAGHANDLE, SPXREQUESTHANDLE,
 };
 use std::ffi::CString;
 use std::mem::MaybeUninit;
@@ -15,42 +16,106 @@ pub struct SpeechSynthesisRequest {
     pub properties: PropertyCollection,
 }
 
+// Text pieces sent via &self are appended to native input stream
+// which is guarded internally as it is consumed by synthesizer thread.
 unsafe impl Sync for SpeechSynthesisRequest {}
 
 impl SpeechSynthesisRequest {
-    /// Creates a speech synthesis request, with text streaming is enabled.
-    pub fn new_text_streaming_request() -> Result<Self> {
+    fn create(text_streaming: bool, is_ssml: bool, input: Option<&str>) -> Result<Self> {
+        let c_input = input.map(CString::new).transpose()?;
+        let (input_ptr, input_len) = c_input
+            .as_ref()
+            .map_or((std::ptr::null(), 0), |c| (c.as_ptr(), c.as_bytes().len()));
         unsafe {
             let mut request_handle: MaybeUninit<SPXREQUESTHANDLE> = MaybeUninit::uninit();
             let ret = speech_synthesis_request_create(
-                true,
-                false,
-                std::ptr::null(),
-                0,
+                text_streaming,
+                is_ssml,
+                input_ptr,
+                input_len as u32,
                 request_handle.as_mut_ptr(),
             );
             convert_err(ret, "Failed to create speech synthesis request handle")?;
+            // from now on handle is released even if getting of property bag fails
+            let handle = SmartHandle::create(
+                "SpeechSynthesisRequest",
+                request_handle.assume_init(),
+                speech_synthesis_request_release,
+            );
 
             let mut prop_bag_handle: MaybeUninit<SPXPROPERTYBAGHANDLE> = MaybeUninit::uninit();
             let ret = speech_synthesis_request_get_property_bag(
-                request_handle.assume_init(),
+                handle.inner(),
                 prop_bag_handle.as_mut_ptr(),
             );
             convert_err(ret, "Failed to get speech synthesis request property bag")?;
 
-            let request = Self {
-                handle: SmartHandle::create(
-                    "SpeechSynthesisRequest",
-                    request_handle.assume_init(),
-                    speech_synthesis_request_release,
-                ),
+            Ok(Self {
+                handle,
                 properties: PropertyCollection::from_handle(prop_bag_handle.assume_init()),
-            };
+            })
+        }
+    }
+
+    /// Creates a speech synthesis request, with text streaming is enabled.
+    pub fn new_text_streaming_request() -> Result<Self> {
+        SpeechSynthesisRequest::create(true, false, None)
+    }
+
+    /// Creates a speech synthesis request from plain text.
+    pub fn new_text_request(text: &str) -> Result<Self> {
+        SpeechSynthesisRequest::create(false, false, Some(text))
+    }
+
+    /// Creates a speech synthesis request from SSML.
+    pub fn new_ssml_request(ssml: &str) -> Result<Self> {
+        SpeechSynthesisRequest::create(false, true, Some(ssml))
+    }
 
-            Ok(request)
+    /// Sets the voice of the request. *personal_voice* (speaker profile id)
+    /// and *model_name* are used with personal voices only.
+    pub fn set_voice(
+        &mut self,
+        voice: &str,
+        personal_voice: Option<&str>,
+        model_name: Option<&str>,
+    ) -> Result<()> {
+        let c_voice = CString::new(voice)?;
+        let c_personal_voice = personal_voice.map(CString::new).transpose()?;
+        let c_model_name = model_name.map(CString::new).transpose()?;
+        unsafe {
+            let ret = speech_synthesis_request_set_voice(
+                self.handle.inner(),
+                c_voice.as_ptr(),
+                c_personal_voice
+                    .as_ref()
+                    .map_or(std::ptr::null(), |c| c.as_ptr()),
+                c_model_name
+                    .as_ref()
+                    .map_or(std::ptr::null(), |c| c.as_ptr()),
+            );
+            convert_err(ret, "SpeechSynthesisRequest.set_voice error")
         }
     }
 
+    /// Sets the speaking rate, e.g. "+20%", "slow".
+    pub fn set_rate(&mut self, rate: &str) -> Result<()> {
+        self.properties
+            .set_property(PropertyId::SpeechSynthesisRequestRate, rate)
+    }
+
+    /// Sets the pitch, e.g. "-5%", "high".
+    pub fn set_pitch(&mut self, pitch: &str) -> Result<()> {
+        self.properties
+            .set_property(PropertyId::SpeechSynthesisRequestPitch, pitch)
+    }
+
+    /// Sets the volume, e.g. "+10%", "loud".
+    pub fn set_volume(&mut self, volume: &str) -> Result<()> {
+        self.properties
+            .set_property(PropertyId::SpeechSynthesisRequestVolume, volume)
+    }
+
     /// Send a piece of text to the speech synthesis service to be synthesized, used in text streaming mode.
     pub fn send_text_piece<S: AsRef<str>>(&self, text: S) -> Result<()> {
         let c_text = CString::new(text.as_ref())?;
@@ -1,5 +1,7 @@
-use crate::common::{PropertyCollection, ResultReason};
-use crate::error::{convert_err, Result};
+use crate::common::{
+    PropertyCollection, PropertyId, ResultReason, SpeechSynthesisOutputFormat, SynthesisAudioFormat,
+};
+use crate::error::{convert_err, Error, ErrorRootCause, Result, SpxError};
 use crate::ffi::{
     synth_result_get_audio_data, synth_result_get_audio_length_duration,
     synth_result_get_property_bag, synth_result_get_reason, synth_result_get_result_id,
@@ -19,6 +21,9 @@ pub struct SpeechSynthesisResult {
     pub audio_data: Vec<u8>,
     pub audio_duration_ms: u64,
     pub properties: PropertyCollection,
+    // output format of the synthesizer which produced this result,
+    // used when result properties do not carry it
+    configured_format: Option<SpeechSynthesisOutputFormat>,
 }
 
 impl fmt::Debug for SpeechSynthesisResult {
@@ -110,8 +115,44 @@ impl SpeechSynthesisResult {
                 audio_data: slice_buffer.to_vec(),
                 audio_duration_ms: audio_duration,
                 properties,
+                configured_format: None,
             };
             Ok(speech_synthesis_result)
         }
     }
+
+    /// Sets output format configured on the synthesizer which produced this result.
+    pub(crate) fn with_configured_format(
+        mut self,
+        format: Option<SpeechSynthesisOutputFormat>,
+    ) -> Self {
+        self.configured_format = format;
+        self
+    }
+
+    /// Returns format of *audio_data* as set by *SpeechConfig::set_speech_synthesis_output_format*.
+    /// The format is taken from result properties, then from the synthesizer which produced
+    /// the result (see *SpeechSynthesizer::output_format*), finally RIFF header of the audio
+    /// is inspected.
+    pub fn audio_format(&self) -> Option<SynthesisAudioFormat> {
+        let name = self
+            .properties
+            .get_property(PropertyId::SpeechServiceConnectionSynthOutputFormat, "")
+            .unwrap_or_default();
+        match SpeechSynthesisOutputFormat::from_name(&name).or(self.configured_format) {
+            Some(format) => Some(format.audio_format()),
+            None => SynthesisAudioFormat::from_wav(&self.audio_data),
+        }
+    }
+
+    /// Decodes *audio_data* into 16-bit PCM samples (see *SynthesisAudioFormat::decode*).
+    pub fn to_pcm_samples(&self) -> Result<Vec<i16>> {
+        let format = self.audio_format().ok_or_else(|| {
+            Error::new(
+                "SpeechSynthesisResult.to_pcm_samples error: unknown audio format".into(),
+                ErrorRootCause::ApiError(SpxError::UnsupportedFormat),
+            )
+        })?;
+        format.decode(&self.audio_data)
+    }
 }
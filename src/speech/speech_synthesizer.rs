@@ -1,56 +1,204 @@
 use crate::audio::AudioConfig;
-use crate::common::{PropertyCollection, PropertyId};
-use crate::error::{convert_err, Result};
+use crate::common::{
+    catch_callback_panic, EventHandlers, PropertyCollection, PropertyId,
+    SpeechSynthesisOutputFormat, Subscription,
+};
+use crate::error::{convert_err, Error, ErrorRootCause, Result, SpxError};
 use crate::ffi::{
-    synthesizer_bookmark_reached_set_callback, synthesizer_canceled_set_callback,
-    synthesizer_completed_set_callback,
+    connection_close, connection_from_speech_synthesizer, connection_handle_release,
+    connection_open, synthesizer_async_handle_release, synthesizer_bookmark_reached_set_callback,
+    synthesizer_canceled_set_callback, synthesizer_completed_set_callback,
+    synthesizer_connection_connected_set_callback,
+    synthesizer_connection_disconnected_set_callback,
     synthesizer_create_speech_synthesizer_from_auto_detect_source_lang_config,
     synthesizer_create_speech_synthesizer_from_config, synthesizer_get_property_bag,
     synthesizer_get_voices_list, synthesizer_handle_release, synthesizer_speak_ssml,
     synthesizer_speak_text, synthesizer_start_speaking_request, synthesizer_start_speaking_ssml,
     synthesizer_start_speaking_text, synthesizer_started_set_callback, synthesizer_stop_speaking,
-    synthesizer_synthesizing_set_callback, synthesizer_viseme_received_set_callback,
-    synthesizer_word_boundary_set_callback, SmartHandle, SPXEVENTHANDLE, SPXPROPERTYBAGHANDLE,
-    SPXRESULTHANDLE, SPXSYNTHHANDLE,
+    synthesizer_stop_speaking_async, synthesizer_synthesizing_set_callback,
+    synthesizer_viseme_received_set_callback, synthesizer_word_boundary_set_callback, SmartHandle,
+    SPXASYNCHANDLE, SPXCONNECTIONHANDLE, SPXEVENTHANDLE, SPXPROPERTYBAGHANDLE, SPXRESULTHANDLE,
+    SPXSYNTHHANDLE,
 };
 use crate::speech::{
-    AutoDetectSourceLanguageConfig, SpeechConfig, SpeechSynthesisBookmarkEvent,
+    AudioChunk, AutoDetectSourceLanguageConfig, CancellationDetails, ConnectionEvent,
+    ConnectionState, DisconnectReason, SpeechConfig, SpeechSynthesisBookmarkEvent,
     SpeechSynthesisEvent, SpeechSynthesisRequest, SpeechSynthesisResult,
-    SpeechSynthesisVisemeEvent, SpeechSynthesisWordBoundaryEvent, SynthesisVoicesResult,
+    SpeechSynthesisVisemeEvent, SpeechSynthesisWordBoundaryEvent, SynthesisInput, SynthesisMetrics,
+    SynthesisStream, SynthesisVoicesResult, TextChunker, TextStreamSynthesis,
 };
+use futures_core::Stream;
 use log::*;
 use std::boxed::Box;
 use std::ffi::CString;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::os::raw::c_void;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+const TICKS_PER_SECOND: u64 = 10_000_000;
 
 /// A separate internal struct to hold all the callback closures for the speech synthesizer events.
 /// By creating a separate struct, and then boxing this struct inside our SpeechSynthesizer,
 /// we can ensure the SpeechSynthesizer itself can be moved freely by end users,
 /// and the callbacks will remain at a fixed memory address on the heap.
 struct CallbackBag {
-    synthesizer_started_cb: Option<Box<dyn Fn(SpeechSynthesisEvent) + Send>>,
-    synthesizer_synthesizing_cb: Option<Box<dyn Fn(SpeechSynthesisEvent) + Send>>,
-    synthesizer_completed_cb: Option<Box<dyn Fn(SpeechSynthesisEvent) + Send>>,
-    synthesizer_canceled_cb: Option<Box<dyn Fn(SpeechSynthesisEvent) + Send>>,
-    synthesizer_word_boundary_cb: Option<Box<dyn Fn(SpeechSynthesisWordBoundaryEvent) + Send>>,
-    synthesizer_viseme_cb: Option<Box<dyn Fn(SpeechSynthesisVisemeEvent) + Send>>,
-    synthesizer_bookmark_cb: Option<Box<dyn Fn(SpeechSynthesisBookmarkEvent) + Send>>,
+    synthesizer_started_handlers: EventHandlers<SpeechSynthesisEvent>,
+    synthesizer_synthesizing_handlers: EventHandlers<SpeechSynthesisEvent>,
+    synthesizer_completed_handlers: EventHandlers<SpeechSynthesisEvent>,
+    synthesizer_canceled_handlers: EventHandlers<SpeechSynthesisEvent>,
+    synthesizer_word_boundary_handlers: EventHandlers<SpeechSynthesisWordBoundaryEvent>,
+    synthesizer_viseme_handlers: EventHandlers<SpeechSynthesisVisemeEvent>,
+    synthesizer_bookmark_handlers: EventHandlers<SpeechSynthesisBookmarkEvent>,
+    // connection callbacks are attached permanently to keep track of connection state
+    connected_handlers: EventHandlers<ConnectionEvent>,
+    disconnected_handlers: EventHandlers<ConnectionEvent>,
+    connection_state: watch::Sender<ConnectionState>,
+    // set by close_connection so that following disconnect is not reported as remote
+    closing: AtomicBool,
+    // see SpeechSynthesisResult::audio_format
+    output_format: Option<SpeechSynthesisOutputFormat>,
+}
+
+impl CallbackBag {
+    fn new() -> Box<Self> {
+        Box::new(CallbackBag {
+            synthesizer_started_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_started",
+                synthesizer_started_set_callback,
+                SpeechSynthesizer::cb_synthesizer_started,
+            ),
+            synthesizer_synthesizing_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_synthesizing",
+                synthesizer_synthesizing_set_callback,
+                SpeechSynthesizer::cb_synthesizer_synthesizing,
+            ),
+            synthesizer_completed_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_completed",
+                synthesizer_completed_set_callback,
+                SpeechSynthesizer::cb_synthesizer_completed,
+            ),
+            synthesizer_canceled_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_canceled",
+                synthesizer_canceled_set_callback,
+                SpeechSynthesizer::cb_synthesizer_canceled,
+            ),
+            synthesizer_word_boundary_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_word_boundary",
+                synthesizer_word_boundary_set_callback,
+                SpeechSynthesizer::cb_synthesizer_word_boundary,
+            ),
+            synthesizer_viseme_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_viseme",
+                synthesizer_viseme_received_set_callback,
+                SpeechSynthesizer::cb_synthesizer_viseme,
+            ),
+            synthesizer_bookmark_handlers: EventHandlers::new(
+                "SpeechSynthesizer::synthesizer_bookmark",
+                synthesizer_bookmark_reached_set_callback,
+                SpeechSynthesizer::cb_synthesizer_bookmark,
+            ),
+            connected_handlers: EventHandlers::local("SpeechSynthesizer::connected"),
+            disconnected_handlers: EventHandlers::local("SpeechSynthesizer::disconnected"),
+            connection_state: watch::channel(ConnectionState::Disconnected).0,
+            closing: AtomicBool::new(false),
+            output_format: None,
+        })
+    }
+
+    /// # Safety
+    /// `hevent` must be a valid handle to a live speech synthesis event.
+    unsafe fn synthesis_event(&self, hevent: SPXEVENTHANDLE) -> Result<SpeechSynthesisEvent> {
+        unsafe {
+            let mut event = SpeechSynthesisEvent::from_handle(hevent)?;
+            event.result = event.result.with_configured_format(self.output_format);
+            Ok(event)
+        }
+    }
+
+    /// Binds all handler lists to native synthesizer handle.
+    /// Bag address is passed as context into native callbacks.
+    fn bind(&self, handle: SPXSYNTHHANDLE) {
+        let context = self as *const _ as *mut c_void;
+        self.synthesizer_started_handlers.bind(handle, context);
+        self.synthesizer_synthesizing_handlers.bind(handle, context);
+        self.synthesizer_completed_handlers.bind(handle, context);
+        self.synthesizer_canceled_handlers.bind(handle, context);
+        self.synthesizer_word_boundary_handlers
+            .bind(handle, context);
+        self.synthesizer_viseme_handlers.bind(handle, context);
+        self.synthesizer_bookmark_handlers.bind(handle, context);
+    }
+
+    /// Attaches connection callbacks, bag address is passed as context.
+    fn bind_connection(&self, connection: SPXCONNECTIONHANDLE) -> Result<()> {
+        let context = self as *const _ as *mut c_void;
+        unsafe {
+            let ret = synthesizer_connection_connected_set_callback(
+                connection,
+                Some(SpeechSynthesizer::cb_connected),
+                context,
+            );
+            convert_err(ret, "SpeechSynthesizer.bind_connection(connected) error")?;
+            let ret = synthesizer_connection_disconnected_set_callback(
+                connection,
+                Some(SpeechSynthesizer::cb_disconnected),
+                context,
+            );
+            convert_err(ret, "SpeechSynthesizer.bind_connection(disconnected) error")?;
+        }
+        Ok(())
+    }
+
+    fn unbind_connection(&self, connection: SPXCONNECTIONHANDLE) {
+        unsafe {
+            let ret = synthesizer_connection_connected_set_callback(
+                connection,
+                None,
+                std::ptr::null_mut(),
+            );
+            if let Err(err) = convert_err(ret, "SpeechSynthesizer.unbind_connection error") {
+                error!("cannot remove connected callback {:?}", err);
+            }
+            let ret = synthesizer_connection_disconnected_set_callback(
+                connection,
+                None,
+                std::ptr::null_mut(),
+            );
+            if let Err(err) = convert_err(ret, "SpeechSynthesizer.unbind_connection error") {
+                error!("cannot remove disconnected callback {:?}", err);
+            }
+        }
+    }
+
+    fn unbind(&self) {
+        self.synthesizer_started_handlers.unbind();
+        self.synthesizer_synthesizing_handlers.unbind();
+        self.synthesizer_completed_handlers.unbind();
+        self.synthesizer_canceled_handlers.unbind();
+        self.synthesizer_word_boundary_handlers.unbind();
+        self.synthesizer_viseme_handlers.unbind();
+        self.synthesizer_bookmark_handlers.unbind();
+    }
 }
 
 /// SpeechSynthesizer struct holds functionality for text-to-speech synthesis.
 pub struct SpeechSynthesizer {
+    // released before synthesizer handle, None if native connection is not available
+    connection: Option<SmartHandle<SPXCONNECTIONHANDLE>>,
     handle: SmartHandle<SPXSYNTHHANDLE>,
     properties: PropertyCollection,
     callback_bag: Box<CallbackBag>,
 }
 
-// to allow to move synthetizer to tokio::spawn
-// see example speak_text_async_2
-// not sure why it is needed, for recognizer
-// and dialog connector this is not needed.
-// TBD: find the root cause
+// Synthesis futures borrow the synthesizer, so it must be Sync to spawn them.
+// Methods taking &self only pass the handles to native synthesizer and connection
+// which queue speak requests and guard their state internally. Rust side state
+// reachable via &self (handler lists, connection state) is behind Mutex, watch
+// channel or atomics. Handles are released only in drop.
 unsafe impl Sync for SpeechSynthesizer {}
 
 impl fmt::Debug for SpeechSynthesizer {
@@ -72,7 +220,9 @@ impl SpeechSynthesizer {
             convert_err(ret, "SpeechSynthesizer::from_handle error")?;
 
             let property_bag = PropertyCollection::from_handle(prop_bag_handle.assume_init());
-            Ok(SpeechSynthesizer {
+
+            let mut result = SpeechSynthesizer {
+                connection: None,
                 handle: SmartHandle::create(
                     "SpeechSynthesizer",
                     handle,
@@ -82,19 +232,54 @@ impl SpeechSynthesizer {
                 // Here we return a boxed instance of the CallbackBag,
                 // ensure that the pointer we provide to the C library
                 // points to a stable, heap-allocated location that holds the callbacks.
-                callback_bag: Box::new(CallbackBag {
-                    synthesizer_started_cb: None,
-                    synthesizer_synthesizing_cb: None,
-                    synthesizer_completed_cb: None,
-                    synthesizer_canceled_cb: None,
-                    synthesizer_word_boundary_cb: None,
-                    synthesizer_viseme_cb: None,
-                    synthesizer_bookmark_cb: None,
-                }),
-            })
+                callback_bag: CallbackBag::new(),
+            };
+            result.callback_bag.output_format = result.output_format();
+            result.callback_bag.bind(handle);
+            // synthesis works without connection, only connection management is unavailable
+            match SpeechSynthesizer::create_connection(handle, &result.callback_bag) {
+                Ok(connection) => result.connection = Some(connection),
+                Err(err) => warn!("SpeechSynthesizer: connection not available {:?}", err),
+            }
+            Ok(result)
+        }
+    }
+
+    /// # Safety
+    /// `handle` mut be a valid handle to a live speech synthesizer.
+    unsafe fn create_connection(
+        handle: SPXSYNTHHANDLE,
+        callback_bag: &CallbackBag,
+    ) -> Result<SmartHandle<SPXCONNECTIONHANDLE>> {
+        unsafe {
+            let mut connection_handle: MaybeUninit<SPXCONNECTIONHANDLE> = MaybeUninit::uninit();
+            let ret = connection_from_speech_synthesizer(handle, connection_handle.as_mut_ptr());
+            convert_err(ret, "SpeechSynthesizer::from_handle(connection) error")?;
+            let connection = SmartHandle::create(
+                "Connection",
+                connection_handle.assume_init(),
+                connection_handle_release,
+            );
+            if let Err(err) = callback_bag.bind_connection(connection.inner()) {
+                callback_bag.unbind_connection(connection.inner());
+                return Err(err);
+            }
+            Ok(connection)
         }
     }
 
+    fn connection_handle(&self, err_msg: &str) -> Result<SPXCONNECTIONHANDLE> {
+        self.connection
+            .as_ref()
+            .map(SmartHandle::inner)
+            .ok_or_else(|| {
+                Error::new(
+                    format!("{}: connection is not available", err_msg),
+                    ErrorRootCause::ApiError(SpxError::InvalidState),
+                )
+            })
+    }
+
     pub fn from_config(speech_config: SpeechConfig, audio_config: AudioConfig) -> Result<Self> {
         unsafe {
             let mut handle: MaybeUninit<SPXSYNTHHANDLE> = MaybeUninit::uninit();
@@ -162,6 +347,7 @@ impl SpeechSynthesizer {
             );
             convert_err(ret, "SpeechSynthesizer.speak_text_async error")?;
             SpeechSynthesisResult::from_handle(result_handle.assume_init())
+                .map(|result| result.with_configured_format(self.output_format()))
         }
     }
 
@@ -179,6 +365,7 @@ impl SpeechSynthesizer {
             );
             convert_err(ret, "SpeechSynthesizer.speak_ssml_async error")?;
             SpeechSynthesisResult::from_handle(result_handle.assume_init())
+                .map(|result| result.with_configured_format(self.output_format()))
         }
     }
 
@@ -198,6 +385,7 @@ impl SpeechSynthesizer {
             );
             convert_err(ret, "SpeechSynthesizer.start_speaking_text_async error")?;
             SpeechSynthesisResult::from_handle(result_handle.assume_init())
+                .map(|result| result.with_configured_format(self.output_format()))
         }
     }
 
@@ -217,6 +405,7 @@ impl SpeechSynthesizer {
             );
             convert_err(ret, "SpeechSynthesizer.start_speaking_ssml_async error")?;
             SpeechSynthesisResult::from_handle(result_handle.assume_init())
+                .map(|result| result.with_configured_format(self.output_format()))
         }
     }
 
@@ -237,12 +426,138 @@ impl SpeechSynthesizer {
             );
             convert_err(ret, "SpeechSynthesizer.start_speaking_async error")?;
             SpeechSynthesisResult::from_handle(result_handle.assume_init())
+                .map(|result| result.with_configured_format(self.output_format()))
         }
     }
 
+    /// Starts the speech synthesis and returns stream of audio chunks as they are synthesized,
+    /// interleaved with word boundary, viseme and bookmark events. The last item carries
+    /// *SynthesisMetrics* of the synthesis. Synthesizer should not be used for other synthesis
+    /// until the stream ends since events of concurrent synthesis cannot be told apart.
+    pub async fn synthesize_stream(&self, input: SynthesisInput<'_>) -> Result<SynthesisStream> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let byte_offset = Arc::new(AtomicU64::new(0));
+        let mut subscriptions = vec![];
+        // byte offset maps onto time exactly only for uncompressed audio
+        let bytes_per_second = self
+            .output_format()
+            .map(|format| format.audio_format())
+            .filter(|format| !format.codec.is_compressed())
+            .and_then(|format| format.bitrate)
+            .map(|bitrate| u64::from(bitrate) / 8)
+            .filter(|bytes_per_second| *bytes_per_second > 0);
+
+        let tx = sender.clone();
+        let offset = byte_offset.clone();
+        subscriptions.push(self.on_synthesizer_synthesizing(move |event| {
+            let data = event.result.audio_data.clone();
+            let byte_offset = offset.fetch_add(data.len() as u64, Ordering::SeqCst);
+            let audio_offset = bytes_per_second.map(|bytes_per_second| {
+                (u128::from(byte_offset) * u128::from(TICKS_PER_SECOND)
+                    / u128::from(bytes_per_second)) as u64
+            });
+            let _ = tx.send(Ok(AudioChunk::Audio {
+                data,
+                byte_offset,
+                audio_offset,
+            }));
+        })?);
+
+        let tx = sender.clone();
+        subscriptions.push(self.on_synthesizer_word_boundary(move |event| {
+            let _ = tx.send(Ok(AudioChunk::WordBoundary {
+                audio_offset: event.audio_offset,
+                duration: Duration::from_millis(event.duration_ms),
+                text_offset: event.text_offset,
+                word_length: event.word_length,
+                boundary_type: event.boundary_type,
+                text: event.text.clone(),
+            }));
+        })?);
+
+        let tx = sender.clone();
+        subscriptions.push(self.on_synthesizer_viseme(move |event| {
+            let _ = tx.send(Ok(AudioChunk::Viseme {
+                audio_offset: event.audio_offset,
+                viseme_id: event.viseme_id,
+                animation: event.animation.clone(),
+            }));
+        })?);
+
+        let tx = sender.clone();
+        subscriptions.push(self.on_synthesizer_bookmark(move |event| {
+            let _ = tx.send(Ok(AudioChunk::Bookmark {
+                audio_offset: event.audio_offset,
+                text: event.text.clone(),
+            }));
+        })?);
+
+        let tx = sender.clone();
+        subscriptions.push(self.on_synthesizer_completed(move |event| {
+            let item =
+                SynthesisMetrics::from_speech_synthesis_result(&event.result).map(|metrics| {
+                    AudioChunk::Completed {
+                        audio_length: byte_offset.load(Ordering::SeqCst),
+                        audio_duration: Duration::from_millis(event.result.audio_duration_ms),
+                        metrics,
+                    }
+                });
+            let _ = tx.send(item);
+        })?);
+
+        subscriptions.push(self.on_synthesizer_canceled(move |event| {
+            let err = match CancellationDetails::from_speech_synthesis_result_ref(&event.result) {
+                Ok(details) => details.to_error().unwrap_or_else(|| {
+                    Error::new(
+                        "SpeechSynthesizer.synthesize_stream error: synthesis canceled".to_owned(),
+                        ErrorRootCause::ApiError(SpxError::Canceled),
+                    )
+                }),
+                Err(err) => err,
+            };
+            let _ = sender.send(Err(err));
+        })?);
+
+        match input {
+            SynthesisInput::Text(text) => self.start_speaking_text_async(text).await?,
+            SynthesisInput::Ssml(ssml) => self.start_speaking_ssml_async(ssml).await?,
+            SynthesisInput::Request(request) => self.start_speaking_async(request).await?,
+        };
+        Ok(SynthesisStream::new(receiver, subscriptions))
+    }
+
+    /// Synthesizes text produced incrementally (e.g. tokens generated by LLM). Tokens are
+    /// grouped by *chunker* and sent into text streaming request (see
+    /// *SpeechSynthesisRequest::new_text_streaming_request*) while the returned stream
+    /// is polled for synthesized audio. Text streaming requires websocket v2 endpoint.
+    pub async fn synthesize_text_stream<'a, S>(
+        &'a self,
+        tokens: S,
+        chunker: TextChunker,
+    ) -> Result<TextStreamSynthesis<'a>>
+    where
+        S: Stream<Item = String> + Send + 'a,
+    {
+        let request = SpeechSynthesisRequest::new_text_streaming_request()?;
+        let audio = self
+            .synthesize_stream(SynthesisInput::Request(&request))
+            .await?;
+        Ok(TextStreamSynthesis::new(
+            self,
+            request,
+            Box::pin(tokens),
+            chunker,
+            audio,
+        ))
+    }
+
     /// Stops the speech synthesis, asynchronously.
     /// It stops audio speech synthesis and discards any unread data in audio.PullAudioOutputStream.
     pub async fn stop_speaking_async(&self) -> Result<()> {
+        self.stop_speaking()
+    }
+
+    pub(crate) fn stop_speaking(&self) -> Result<()> {
         unsafe {
             let ret = synthesizer_stop_speaking(self.handle.inner());
             convert_err(ret, "SpeechSynthesizer.stop_speaking_async error")?;
@@ -250,6 +565,23 @@ impl SpeechSynthesizer {
         }
     }
 
+    /// Requests stop of the synthesis without waiting for it to stop.
+    /// Returned handle should be kept until the synthesis ends,
+    /// releasing it earlier waits for the stop to complete.
+    pub(crate) fn request_stop_speaking(&self) -> Result<SmartHandle<SPXASYNCHANDLE>> {
+        unsafe {
+            let mut handle_async: MaybeUninit<SPXASYNCHANDLE> = MaybeUninit::uninit();
+            let ret =
+                synthesizer_stop_speaking_async(self.handle.inner(), handle_async.as_mut_ptr());
+            convert_err(ret, "SpeechSynthesizer.request_stop_speaking error")?;
+            Ok(SmartHandle::create(
+                "handle_async_stop_speaking",
+                handle_async.assume_init(),
+                synthesizer_async_handle_release,
+            ))
+        }
+    }
+
     /// Gets the available voices, asynchronously.
     /// The parameter locale specifies the locale of voices, in BCP-47 format; or leave it empty to get all available voices.
     pub async fn get_voices_async(&self, locale: &str) -> Result<SynthesisVoicesResult> {
@@ -280,122 +612,308 @@ impl SpeechSynthesizer {
             .set_property(PropertyId::SpeechServiceAuthorizationToken, token)
     }
 
+    /// Opens connection to the service in advance so that first synthesis does not
+    /// wait for connection setup. Connection is also opened implicitly by *speak_\** methods.
+    /// Completion is reported via connected event, see *warm_up*.
+    pub fn open_connection(&self, for_continuous: bool) -> Result<()> {
+        let connection = self.connection_handle("SpeechSynthesizer.open_connection error")?;
+        self.callback_bag.closing.store(false, Ordering::SeqCst);
+        self.callback_bag
+            .connection_state
+            .send_if_modified(|state| {
+                let disconnected = *state == ConnectionState::Disconnected;
+                if disconnected {
+                    *state = ConnectionState::Connecting;
+                }
+                disconnected
+            });
+        unsafe {
+            let ret = connection_open(connection, for_continuous);
+            if let Err(err) = convert_err(ret, "SpeechSynthesizer.open_connection error") {
+                self.callback_bag
+                    .connection_state
+                    .send_replace(ConnectionState::Disconnected);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens connection to the service and waits until it is established.
+    /// Fails if connection is closed before it was established or
+    /// if it is not established within *timeout*.
+    pub async fn warm_up(&self, timeout: Duration) -> Result<()> {
+        let mut state = self.callback_bag.connection_state.subscribe();
+        self.open_connection(false)?;
+        let connected = tokio::time::timeout(
+            timeout,
+            state.wait_for(|state| *state != ConnectionState::Connecting),
+        )
+        .await
+        .map(|state| state.map(|state| *state == ConnectionState::Connected));
+        match connected {
+            Ok(Ok(true)) => Ok(()),
+            Err(_) => {
+                // connected event may still arrive later, state is left as is
+                Err(Error::new(
+                    format!(
+                        "SpeechSynthesizer.warm_up error: connection not established within {:?}",
+                        timeout
+                    ),
+                    ErrorRootCause::ApiError(SpxError::Timeout),
+                ))
+            }
+            Ok(_) => Err(Error::new(
+                "SpeechSynthesizer.warm_up error: connection closed before it was established"
+                    .to_owned(),
+                ErrorRootCause::ApiError(SpxError::ConnectionFailure),
+            )),
+        }
+    }
+
+    /// Closes connection to the service. Disconnected event is reported
+    /// with *DisconnectReason::Closed*.
+    pub fn close_connection(&self) -> Result<()> {
+        let connection = self.connection_handle("SpeechSynthesizer.close_connection error")?;
+        self.callback_bag.closing.store(true, Ordering::SeqCst);
+        unsafe {
+            let ret = connection_close(connection);
+            convert_err(ret, "SpeechSynthesizer.close_connection error")?;
+        }
+        Ok(())
+    }
+
+    /// Returns output format configured via *SpeechConfig::set_speech_synthesis_output_format*,
+    /// None if it was not set (or is not known to this crate).
+    pub fn output_format(&self) -> Option<SpeechSynthesisOutputFormat> {
+        self.properties
+            .get_property(PropertyId::SpeechServiceConnectionSynthOutputFormat, "")
+            .ok()
+            .and_then(|name| SpeechSynthesisOutputFormat::from_name(&name))
+    }
+
+    /// Returns current state of the connection, e.g. to keep pooled synthesizers connected.
+    pub fn connection_state(&self) -> ConnectionState {
+        *self.callback_bag.connection_state.borrow()
+    }
+
+    pub fn set_connected_cb<F>(&mut self, f: F) -> Result<()>
+    where
+        F: Fn(ConnectionEvent) + 'static + Send,
+    {
+        self.callback_bag
+            .connected_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_connected_cb error")
+    }
+
+    pub fn set_disconnected_cb<F>(&mut self, f: F) -> Result<()>
+    where
+        F: Fn(ConnectionEvent) + 'static + Send,
+    {
+        self.callback_bag
+            .disconnected_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_disconnected_cb error")
+    }
+
+    pub fn on_connected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&ConnectionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .connected_handlers
+            .subscribe(f, "SpeechSynthesizer.on_connected error")
+    }
+
+    pub fn on_disconnected<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&ConnectionEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .disconnected_handlers
+            .subscribe(f, "SpeechSynthesizer.on_disconnected error")
+    }
+
     pub fn set_synthesizer_started_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_started_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_started_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_started),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechSynthesizer.set_synthesizer_started_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_started_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_started_cb error")
     }
 
     pub fn set_synthesizer_synthesizing_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_synthesizing_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_synthesizing_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_synthesizing),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(
-                ret,
-                "SpeechSynthesizer.set_synthesizer_synthesizing_cb error",
-            )?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_synthesizing_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_synthesizing_cb error")
     }
 
     pub fn set_synthesizer_completed_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_completed_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_completed_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_completed),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechSynthesizer.set_synthesizer_completed_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_completed_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_completed_cb error")
     }
 
     pub fn set_synthesizer_canceled_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_canceled_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_canceled_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_canceled),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechSynthesizer.set_synthesizer_canceled_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_canceled_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_canceled_cb error")
     }
 
     pub fn set_synthesizer_word_boundary_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisWordBoundaryEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_word_boundary_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_word_boundary_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_word_boundary),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(
-                ret,
+        self.callback_bag
+            .synthesizer_word_boundary_handlers
+            .set_legacy(
+                f,
                 "SpeechSynthesizer.set_synthesizer_word_boundary_cb error",
-            )?;
-            Ok(())
-        }
+            )
     }
 
     pub fn set_synthesizer_viseme_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisVisemeEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_viseme_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_viseme_received_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_viseme),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechSynthesizer.set_synthesizer_viseme_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_viseme_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_viseme_cb error")
     }
 
     pub fn set_synthesizer_bookmark_cb<F>(&mut self, f: F) -> Result<()>
     where
         F: Fn(SpeechSynthesisBookmarkEvent) + 'static + Send,
     {
-        self.callback_bag.synthesizer_bookmark_cb = Some(Box::new(f));
-        unsafe {
-            let ret = synthesizer_bookmark_reached_set_callback(
-                self.handle.inner(),
-                Some(Self::cb_synthesizer_bookmark),
-                &*self.callback_bag as *const _ as *mut c_void,
-            );
-            convert_err(ret, "SpeechSynthesizer.set_synthesizer_bookmark_cb error")?;
-            Ok(())
-        }
+        self.callback_bag
+            .synthesizer_bookmark_handlers
+            .set_legacy(f, "SpeechSynthesizer.set_synthesizer_bookmark_cb error")
+    }
+
+    /// Registers additional handler of synthesis started event. Unlike *set_synthesizer_started_cb*
+    /// any number of handlers can be registered, each of them is unregistered
+    /// when returned *Subscription* is dropped.
+    pub fn on_synthesizer_started<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_started_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_started error")
+    }
+
+    /// Registers additional handler of synthesis synthesizing event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_synthesizing<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_synthesizing_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_synthesizing error")
+    }
+
+    /// Registers additional handler of synthesis completed event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_completed<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_completed_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_completed error")
+    }
+
+    /// Registers additional handler of synthesis canceled event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_canceled<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_canceled_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_canceled error")
+    }
+
+    /// Registers additional handler of synthesis word boundary event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_word_boundary<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisWordBoundaryEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_word_boundary_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_word_boundary error")
+    }
+
+    /// Registers additional handler of synthesis viseme event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_viseme<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisVisemeEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_viseme_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_viseme error")
+    }
+
+    /// Registers additional handler of synthesis bookmark event, see *on_synthesizer_started*.
+    pub fn on_synthesizer_bookmark<F>(&self, f: F) -> Result<Subscription>
+    where
+        F: Fn(&SpeechSynthesisBookmarkEvent) + 'static + Send + Sync,
+    {
+        self.callback_bag
+            .synthesizer_bookmark_handlers
+            .subscribe(f, "SpeechSynthesizer.on_synthesizer_bookmark error")
+    }
+
+    #[allow(non_snake_case)]
+    unsafe extern "C" fn cb_connected(hevent: SPXEVENTHANDLE, pvContext: *mut c_void) {
+        trace!("SpeechSynthesizer::cb_connected called");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_connected", (), || {
+            callback_bag
+                .connection_state
+                .send_replace(ConnectionState::Connected);
+            match ConnectionEvent::from_handle(hevent, None) {
+                Ok(event) => {
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.connected_handlers.dispatch(event);
+                }
+                Err(err) => {
+                    error!("SpeechSynthesizer::cb_connected error {:?}", err);
+                }
+            }
+        })
+    }
+
+    #[allow(non_snake_case)]
+    unsafe extern "C" fn cb_disconnected(hevent: SPXEVENTHANDLE, pvContext: *mut c_void) {
+        trace!("SpeechSynthesizer::cb_disconnected called");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_disconnected", (), || {
+            let reason = if callback_bag.closing.swap(false, Ordering::SeqCst) {
+                DisconnectReason::Closed
+            } else {
+                DisconnectReason::Remote
+            };
+            callback_bag
+                .connection_state
+                .send_replace(ConnectionState::Disconnected);
+            match ConnectionEvent::from_handle(hevent, Some(reason)) {
+                Ok(event) => {
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.disconnected_handlers.dispatch(event);
+                }
+                Err(err) => {
+                    error!("SpeechSynthesizer::cb_disconnected error {:?}", err);
+                }
+            }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -406,13 +924,14 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_started called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_started_cb {
-            trace!("synthesizer_started_cb defined");
-            match SpeechSynthesisEvent::from_handle(hevent) {
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic(
+            "SpeechSynthesizer::cb_synthesizer_started",
+            (),
+            || match callback_bag.synthesis_event(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.synthesizer_started_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -420,8 +939,8 @@ impl SpeechSynthesizer {
                         err
                     );
                 }
-            }
-        }
+            },
+        )
     }
 
     #[allow(non_snake_case)]
@@ -432,13 +951,14 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_synthesizing called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_synthesizing_cb {
-            trace!("synthesizer_synthesizing_cb defined");
-            match SpeechSynthesisEvent::from_handle(hevent) {
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_synthesizer_synthesizing", (), || {
+            match callback_bag.synthesis_event(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag
+                        .synthesizer_synthesizing_handlers
+                        .dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -447,7 +967,7 @@ impl SpeechSynthesizer {
                     );
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -458,13 +978,12 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_completed called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_completed_cb {
-            trace!("synthesizer_completed_cb defined");
-            match SpeechSynthesisEvent::from_handle(hevent) {
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_synthesizer_completed", (), || {
+            match callback_bag.synthesis_event(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.synthesizer_completed_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -473,7 +992,7 @@ impl SpeechSynthesizer {
                     );
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -484,13 +1003,14 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_canceled called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_canceled_cb {
-            trace!("synthesizer_canceled_cb defined");
-            match SpeechSynthesisEvent::from_handle(hevent) {
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic(
+            "SpeechSynthesizer::cb_synthesizer_canceled",
+            (),
+            || match callback_bag.synthesis_event(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.synthesizer_canceled_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -498,8 +1018,8 @@ impl SpeechSynthesizer {
                         err
                     );
                 }
-            }
-        }
+            },
+        )
     }
 
     #[allow(non_snake_case)]
@@ -510,13 +1030,16 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_word_boundary called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_word_boundary_cb {
-            trace!("synthesizer_word_boundary_cb defined");
-            match SpeechSynthesisWordBoundaryEvent::from_handle(hevent) {
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic(
+            "SpeechSynthesizer::cb_synthesizer_word_boundary",
+            (),
+            || match SpeechSynthesisWordBoundaryEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag
+                        .synthesizer_word_boundary_handlers
+                        .dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -524,8 +1047,8 @@ impl SpeechSynthesizer {
                         err
                     );
                 }
-            }
-        }
+            },
+        )
     }
 
     #[allow(non_snake_case)]
@@ -536,13 +1059,12 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_viseme called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_viseme_cb {
-            trace!("synthesizer_viseme_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_synthesizer_viseme", (), || {
             match SpeechSynthesisVisemeEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.synthesizer_viseme_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -551,7 +1073,7 @@ impl SpeechSynthesizer {
                     );
                 }
             }
-        }
+        })
     }
 
     #[allow(non_snake_case)]
@@ -562,13 +1084,12 @@ impl SpeechSynthesizer {
         pvContext: *mut c_void,
     ) {
         trace!("SpeechSynthesizer::cb_synthesizer_bookmark called");
-        let callback_bag = &mut *(pvContext as *mut CallbackBag);
-        if let Some(cb) = &callback_bag.synthesizer_bookmark_cb {
-            trace!("synthesizer_bookmark_cb defined");
+        let callback_bag = &*(pvContext as *const CallbackBag);
+        catch_callback_panic("SpeechSynthesizer::cb_synthesizer_bookmark", (), || {
             match SpeechSynthesisBookmarkEvent::from_handle(hevent) {
                 Ok(event) => {
-                    trace!("calling cb with event {:?}", event);
-                    cb(event);
+                    trace!("calling handlers with event {:?}", event);
+                    callback_bag.synthesizer_bookmark_handlers.dispatch(event);
                 }
                 Err(err) => {
                     error!(
@@ -577,6 +1098,16 @@ impl SpeechSynthesizer {
                     );
                 }
             }
+        })
+    }
+}
+
+impl Drop for SpeechSynthesizer {
+    fn drop(&mut self) {
+        // outstanding subscriptions must not touch native handle once it is released
+        if let Some(connection) = &self.connection {
+            self.callback_bag.unbind_connection(connection.inner());
         }
+        self.callback_bag.unbind();
     }
 }
@@ -0,0 +1,51 @@
+use crate::common::PropertyId;
+use crate::error::Result;
+use crate::speech::SpeechSynthesisResult;
+use std::time::Duration;
+
+/// SynthesisMetrics contains latency information reported by the service
+/// for completed speech synthesis. Values not reported by the service are None.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SynthesisMetrics {
+    /// Time from the start of synthesis until the first byte of audio was received.
+    pub first_byte_latency: Option<Duration>,
+    /// Time from the start of synthesis until all the audio was received.
+    pub finish_latency: Option<Duration>,
+    /// Total time audio playback was starving for data.
+    pub underrun_time: Option<Duration>,
+    pub connection_latency: Option<Duration>,
+    pub network_latency: Option<Duration>,
+    pub service_latency: Option<Duration>,
+    /// Backend which finished the synthesis (e.g. Online, Offline).
+    pub backend: Option<String>,
+}
+
+impl SynthesisMetrics {
+    pub fn from_speech_synthesis_result(result: &SpeechSynthesisResult) -> Result<Self> {
+        let millis = |id: PropertyId| -> Result<Option<Duration>> {
+            let value = result.properties.get_property(id, "")?;
+            Ok(value.trim().parse::<u64>().ok().map(Duration::from_millis))
+        };
+        let backend = result
+            .properties
+            .get_property(PropertyId::SpeechServiceResponseSynthesisBackend, "")?;
+
+        Ok(SynthesisMetrics {
+            first_byte_latency: millis(
+                PropertyId::SpeechServiceResponseSynthesisFirstByteLatencyMs,
+            )?,
+            finish_latency: millis(PropertyId::SpeechServiceResponseSynthesisFinishLatencyMs)?,
+            underrun_time: millis(PropertyId::SpeechServiceResponseSynthesisUnderrunTimeMs)?,
+            connection_latency: millis(
+                PropertyId::SpeechServiceResponseSynthesisConnectionLatencyMs,
+            )?,
+            network_latency: millis(PropertyId::SpeechServiceResponseSynthesisNetworkLatencyMs)?,
+            service_latency: millis(PropertyId::SpeechServiceResponseSynthesisServiceLatencyMs)?,
+            backend: if backend.is_empty() {
+                None
+            } else {
+                Some(backend)
+            },
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use crate::common::Subscription;
+use crate::error::Result;
+use crate::speech::{AudioChunk, SpeechSynthesisRequest};
+use futures_core::Stream;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Input of *SpeechSynthesizer::synthesize_stream*.
+#[derive(Debug, Clone, Copy)]
+pub enum SynthesisInput<'a> {
+    Text(&'a str),
+    Ssml(&'a str),
+    Request(&'a SpeechSynthesisRequest),
+}
+
+/// Stream of synthesized audio returned by *SpeechSynthesizer::synthesize_stream*.
+/// Stream ends after *AudioChunk::Completed* item or after an error
+/// (e.g. synthesis canceled). Dropping the stream unregisters its event handlers.
+pub struct SynthesisStream {
+    receiver: mpsc::UnboundedReceiver<Result<AudioChunk>>,
+    subscriptions: Vec<Subscription>,
+    finished: bool,
+}
+
+impl fmt::Debug for SynthesisStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SynthesisStream")
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl SynthesisStream {
+    pub(crate) fn new(
+        receiver: mpsc::UnboundedReceiver<Result<AudioChunk>>,
+        subscriptions: Vec<Subscription>,
+    ) -> Self {
+        SynthesisStream {
+            receiver,
+            subscriptions,
+            finished: false,
+        }
+    }
+}
+
+impl Stream for SynthesisStream {
+    type Item = Result<AudioChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        match self.receiver.poll_recv(cx) {
+            Poll::Ready(Some(item)) => {
+                if matches!(item, Ok(AudioChunk::Completed { .. }) | Err(_)) {
+                    self.finished = true;
+                    self.subscriptions.clear();
+                }
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                self.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
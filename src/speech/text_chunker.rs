@@ -0,0 +1,131 @@
+/// TextChunking defines at which boundaries streamed text is passed to the synthesizer.
+/// Larger chunks give the service more context for natural prosody, smaller chunks lower the latency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextChunking {
+    /// Every token is sent as soon as it arrives.
+    Token,
+    /// Text is sent at clause boundaries (commas, semicolons, colons) and sentence boundaries.
+    Clause,
+    /// Text is sent at sentence boundaries.
+    #[default]
+    Sentence,
+}
+
+const DEFAULT_MAX_CHUNK_CHARS: usize = 300;
+
+/// TextChunker accumulates streamed text tokens and splits them into chunks according to *TextChunking*.
+#[derive(Debug, Clone)]
+pub struct TextChunker {
+    chunking: TextChunking,
+    max_chunk_chars: usize,
+    buffer: String,
+}
+
+impl Default for TextChunker {
+    fn default() -> Self {
+        TextChunker::new(TextChunking::default())
+    }
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '\n')
+}
+
+fn is_clause_end(c: char) -> bool {
+    matches!(c, ',' | ';' | ':')
+}
+
+// full width punctuation is not followed by whitespace, boundary is immediate
+fn is_full_width_sentence_end(c: char) -> bool {
+    matches!(c, '。' | '！' | '？')
+}
+
+fn is_full_width_clause_end(c: char) -> bool {
+    matches!(c, '，' | '、' | '；' | '：')
+}
+
+impl TextChunker {
+    pub fn new(chunking: TextChunking) -> Self {
+        TextChunker {
+            chunking,
+            max_chunk_chars: DEFAULT_MAX_CHUNK_CHARS,
+            buffer: String::new(),
+        }
+    }
+
+    /// Text longer than *max_chunk_chars* without any boundary is split
+    /// at the last whitespace, so that synthesis does not stall on long runs of text.
+    pub fn with_max_chunk_chars(mut self, max_chunk_chars: usize) -> Self {
+        self.max_chunk_chars = max_chunk_chars.max(1);
+        self
+    }
+
+    fn is_boundary(&self, c: char, next: Option<char>) -> bool {
+        let clause = self.chunking == TextChunking::Clause;
+        if is_full_width_sentence_end(c) || (clause && is_full_width_clause_end(c)) {
+            return true;
+        }
+        // ASCII punctuation must be followed by whitespace, e.g. to keep "3.14" together.
+        // Punctuation at the end of the buffer is confirmed by the next token.
+        (is_sentence_end(c) || (clause && is_clause_end(c)))
+            && (c == '\n' || next.is_some_and(char::is_whitespace))
+    }
+
+    /// Byte index right after the last boundary in the buffer (including following whitespace).
+    fn last_boundary(&self) -> Option<usize> {
+        let mut last = None;
+        let mut chars = self.buffer.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            let next = chars.peek().map(|(_, next)| *next);
+            if self.is_boundary(c, next) {
+                let mut end = index + c.len_utf8();
+                if let Some(next) = next.filter(|next| *next != '\n' && next.is_whitespace()) {
+                    end += next.len_utf8();
+                }
+                last = Some(end);
+            }
+        }
+        last
+    }
+
+    /// Adds token and returns chunks ready to be synthesized.
+    pub fn push(&mut self, token: &str) -> Vec<String> {
+        if self.chunking == TextChunking::Token {
+            return if token.is_empty() {
+                vec![]
+            } else {
+                vec![token.to_owned()]
+            };
+        }
+
+        self.buffer.push_str(token);
+        let mut chunks = vec![];
+        if let Some(end) = self.last_boundary() {
+            let rest = self.buffer.split_off(end);
+            chunks.push(std::mem::replace(&mut self.buffer, rest));
+        }
+        while self.buffer.chars().count() > self.max_chunk_chars {
+            let limit = self
+                .buffer
+                .char_indices()
+                .nth(self.max_chunk_chars)
+                .map_or(self.buffer.len(), |(index, _)| index);
+            let end = self.buffer[..limit]
+                .char_indices()
+                .rfind(|(_, c)| c.is_whitespace())
+                .map_or(limit, |(index, c)| index + c.len_utf8());
+            let rest = self.buffer.split_off(end);
+            chunks.push(std::mem::replace(&mut self.buffer, rest));
+        }
+        chunks
+    }
+
+    /// Returns remaining text once the token stream has ended.
+    pub fn finish(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.buffer))
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error::{Error, ErrorRootCause, Result, SpxError};
+use crate::ffi::{SmartHandle, SPXASYNCHANDLE};
+use crate::speech::{
+    AudioChunk, SpeechSynthesisRequest, SpeechSynthesizer, SynthesisStream, TextChunker,
+};
+use futures_core::Stream;
+use log::*;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Default)]
+struct CancellationState {
+    canceled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// SynthesisCancellation cancels text streaming synthesis, see *TextStreamSynthesis::cancellation*.
+/// It can be cloned and moved to other tasks or threads.
+#[derive(Clone, Default)]
+pub struct SynthesisCancellation {
+    state: Arc<CancellationState>,
+}
+
+impl fmt::Debug for SynthesisCancellation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SynthesisCancellation")
+            .field("canceled", &self.is_canceled())
+            .finish()
+    }
+}
+
+impl SynthesisCancellation {
+    /// Stops reading of the input text and stops the synthesis.
+    pub fn cancel(&self) {
+        self.state.canceled.store(true, Ordering::SeqCst);
+        if let Some(waker) = self.state.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.state.canceled.load(Ordering::SeqCst)
+    }
+
+    fn register(&self, waker: &Waker) {
+        *self.state.waker.lock().unwrap() = Some(waker.clone());
+    }
+}
+
+/// TextStreamSynthesis is returned by *SpeechSynthesizer::synthesize_text_stream*. It forwards
+/// text tokens into text streaming request while being polled for synthesized audio.
+/// Stream yields the same items as *SynthesisStream*, canceled synthesis ends
+/// with *SpxError::Canceled* error.
+pub struct TextStreamSynthesis<'a> {
+    synthesizer: &'a SpeechSynthesizer,
+    request: SpeechSynthesisRequest,
+    tokens: Pin<Box<dyn Stream<Item = String> + Send + 'a>>,
+    chunker: TextChunker,
+    audio: SynthesisStream,
+    cancellation: SynthesisCancellation,
+    // pending stop request, released once the synthesis ends
+    stopping: Option<SmartHandle<SPXASYNCHANDLE>>,
+    // error reported once the synthesis stops
+    stop_error: Option<Error>,
+    input_finished: bool,
+    finished: bool,
+}
+
+impl<'a> fmt::Debug for TextStreamSynthesis<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextStreamSynthesis")
+            .field("chunker", &self.chunker)
+            .field("cancellation", &self.cancellation)
+            .field("input_finished", &self.input_finished)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<'a> TextStreamSynthesis<'a> {
+    pub(crate) fn new(
+        synthesizer: &'a SpeechSynthesizer,
+        request: SpeechSynthesisRequest,
+        tokens: Pin<Box<dyn Stream<Item = String> + Send + 'a>>,
+        chunker: TextChunker,
+        audio: SynthesisStream,
+    ) -> Self {
+        TextStreamSynthesis {
+            synthesizer,
+            request,
+            tokens,
+            chunker,
+            audio,
+            cancellation: SynthesisCancellation::default(),
+            stopping: None,
+            stop_error: None,
+            input_finished: false,
+            finished: false,
+        }
+    }
+
+    /// Returns handle which cancels this synthesis.
+    pub fn cancellation(&self) -> SynthesisCancellation {
+        self.cancellation.clone()
+    }
+
+    /// Stops reading of the input text and stops the synthesis.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    fn finish_input(&mut self) {
+        self.input_finished = true;
+        if let Err(err) = self.request.finish_input() {
+            warn!("TextStreamSynthesis: finish_input error {:?}", err);
+        }
+    }
+
+    /// Requests stop of the synthesis, *err* is reported once remaining audio is drained.
+    /// Stop is not waited for here as it would block the executor.
+    fn stop(&mut self, err: Error) {
+        if !self.input_finished {
+            self.finish_input();
+        }
+        match self.synthesizer.request_stop_speaking() {
+            Ok(handle) => self.stopping = Some(handle),
+            Err(err) => warn!("TextStreamSynthesis: stop_speaking error {:?}", err),
+        }
+        self.stop_error = Some(err);
+    }
+
+    /// Discards audio until the synthesizer reports the synthesis ended.
+    fn poll_stopped(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<AudioChunk>>> {
+        while self.stopping.is_some() {
+            match Pin::new(&mut self.audio).poll_next(cx) {
+                Poll::Ready(Some(Ok(_))) => continue,
+                Poll::Ready(_) => self.stopping = None,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        self.finished = true;
+        Poll::Ready(self.stop_error.take().map(Err))
+    }
+
+    fn send(&mut self, chunks: Vec<String>) -> Result<()> {
+        for chunk in chunks {
+            trace!("TextStreamSynthesis: sending text piece {:?}", chunk);
+            self.request.send_text_piece(chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Forwards all tokens available without waiting.
+    fn forward_tokens(&mut self, cx: &mut Context<'_>) -> Result<()> {
+        while !self.input_finished {
+            match self.tokens.as_mut().poll_next(cx) {
+                Poll::Ready(Some(token)) => {
+                    let chunks = self.chunker.push(&token);
+                    self.send(chunks)?;
+                }
+                Poll::Ready(None) => {
+                    let rest = self.chunker.finish().into_iter().collect();
+                    self.send(rest)?;
+                    self.finish_input();
+                }
+                Poll::Pending => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Stream for TextStreamSynthesis<'a> {
+    type Item = Result<AudioChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        if this.stop_error.is_some() {
+            return this.poll_stopped(cx);
+        }
+
+        this.cancellation.register(cx.waker());
+        if this.cancellation.is_canceled() {
+            this.stop(Error::new(
+                "TextStreamSynthesis error: synthesis canceled".to_owned(),
+                ErrorRootCause::ApiError(SpxError::Canceled),
+            ));
+            return this.poll_stopped(cx);
+        }
+
+        if let Err(err) = this.forward_tokens(cx) {
+            this.stop(err);
+            return this.poll_stopped(cx);
+        }
+
+        match Pin::new(&mut this.audio).poll_next(cx) {
+            Poll::Ready(None) => {
+                this.finished = true;
+                Poll::Ready(None)
+            }
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::common::{Gender, ResultReason};
+use crate::error::{Error, Result, SpxError};
+use crate::speech::voice_descriptor::normalize_locale;
+use crate::speech::{SpeechSynthesizer, SynthesisVoicesResult, VoiceDescriptor};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// VoiceQuery is a filter applied to voices of *VoiceCatalog*.
+/// Criteria not set match any voice.
+#[derive(Debug, Clone, Default)]
+pub struct VoiceQuery {
+    locale_prefix: Option<String>,
+    gender: Option<Gender>,
+    style: Option<String>,
+    role: Option<String>,
+    neural: Option<bool>,
+    offline: Option<bool>,
+}
+
+impl VoiceQuery {
+    pub fn new() -> Self {
+        VoiceQuery::default()
+    }
+
+    /// Matches primary locale by language or full locale, e.g. *en* or *en-US*.
+    pub fn locale_prefix(mut self, prefix: &str) -> Self {
+        self.locale_prefix = Some(normalize_locale(prefix).trim_end_matches('-').to_owned());
+        self
+    }
+
+    pub fn gender(mut self, gender: Gender) -> Self {
+        self.gender = Some(gender);
+        self
+    }
+
+    pub fn style(mut self, style: &str) -> Self {
+        self.style = Some(style.to_owned());
+        self
+    }
+
+    pub fn role(mut self, role: &str) -> Self {
+        self.role = Some(role.to_owned());
+        self
+    }
+
+    pub fn neural(mut self, neural: bool) -> Self {
+        self.neural = Some(neural);
+        self
+    }
+
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = Some(offline);
+        self
+    }
+
+    pub fn matches(&self, voice: &VoiceDescriptor) -> bool {
+        if let Some(prefix) = &self.locale_prefix {
+            let locale = normalize_locale(&voice.locale);
+            if locale != *prefix && !locale.starts_with(&format!("{}-", prefix)) {
+                return false;
+            }
+        }
+        self.gender.is_none_or(|gender| voice.gender == gender)
+            && self
+                .style
+                .as_ref()
+                .is_none_or(|style| voice.supports_style(style))
+            && self
+                .role
+                .as_ref()
+                .is_none_or(|role| voice.supports_role(role))
+            && self.neural.is_none_or(|neural| voice.is_neural() == neural)
+            && self
+                .offline
+                .is_none_or(|offline| voice.is_offline() == offline)
+    }
+}
+
+/// VoiceCatalog holds list of available synthesis voices and allows to query them.
+/// Catalog can be persisted into JSON file so that voices are not fetched
+/// from the service on every start.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VoiceCatalog {
+    /// Time of retrieval from the service, in seconds since UNIX epoch.
+    pub fetched_at: u64,
+    /// Locale used to fetch the voices, empty for all voices.
+    #[serde(default)]
+    pub locale: String,
+    pub voices: Vec<VoiceDescriptor>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+/// Style name with separators removed and lowercased, e.g. *customer-service* -> *customerservice*.
+fn normalize_style(style: &str) -> String {
+    style
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+impl VoiceCatalog {
+    pub fn new(locale: &str, voices: Vec<VoiceDescriptor>) -> Self {
+        VoiceCatalog {
+            fetched_at: now_secs(),
+            locale: locale.to_owned(),
+            voices,
+        }
+    }
+
+    pub fn from_voices_result(locale: &str, result: &SynthesisVoicesResult) -> Result<Self> {
+        if !matches!(result.reason, ResultReason::VoicesListRetrieved) {
+            return Err(Error::api_error(
+                "VoiceCatalog::from_voices_result error",
+                &result.error_details,
+                SpxError::ServiceError,
+            ));
+        }
+        let voices = result
+            .voices
+            .iter()
+            .map(VoiceDescriptor::from_voice_info)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(VoiceCatalog::new(locale, voices))
+    }
+
+    /// Retrieves voices for given locale (empty for all voices) from the service.
+    pub async fn fetch(synthesizer: &SpeechSynthesizer, locale: &str) -> Result<Self> {
+        let result = synthesizer.get_voices_async(locale).await?;
+        VoiceCatalog::from_voices_result(locale, &result)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Age of the catalog, zero if *fetched_at* lies in the future.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.fetched_at))
+    }
+
+    /// Loads catalog from cache file if it exists, was fetched for the same locale
+    /// and is not older than *max_age* (None means cache never expires).
+    /// Otherwise voices are fetched from the service and cache file is rewritten.
+    /// Failure to write the cache is logged, not returned.
+    pub async fn load_or_fetch<P: AsRef<Path>>(
+        synthesizer: &SpeechSynthesizer,
+        locale: &str,
+        cache_path: P,
+        max_age: Option<Duration>,
+    ) -> Result<Self> {
+        let cache_path = cache_path.as_ref();
+        match VoiceCatalog::load(cache_path) {
+            Ok(catalog)
+                if catalog.locale == locale && max_age.is_none_or(|age| catalog.age() <= age) =>
+            {
+                return Ok(catalog);
+            }
+            Ok(_) => debug!("voice catalog cache {:?} is stale", cache_path),
+            Err(err) => debug!("voice catalog cache {:?} not loaded: {}", cache_path, err),
+        }
+        let catalog = VoiceCatalog::fetch(synthesizer, locale).await?;
+        if let Err(err) = catalog.save(cache_path) {
+            warn!("voice catalog cache {:?} not saved: {}", cache_path, err);
+        }
+        Ok(catalog)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voices.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &VoiceDescriptor> {
+        self.voices.iter()
+    }
+
+    /// Finds voice by full name or short name (case insensitive),
+    /// e.g. *en-US-JennyNeural*.
+    pub fn get(&self, name: &str) -> Option<&VoiceDescriptor> {
+        self.voices.iter().find(|voice| {
+            voice.short_name.eq_ignore_ascii_case(name) || voice.name.eq_ignore_ascii_case(name)
+        })
+    }
+
+    pub fn query(&self, query: &VoiceQuery) -> Vec<&VoiceDescriptor> {
+        self.voices
+            .iter()
+            .filter(|voice| query.matches(voice))
+            .collect()
+    }
+
+    /// Returns all locales present in the catalog, sorted.
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales: Vec<&str> = self.voices.iter().map(|v| v.locale.as_str()).collect();
+        locales.sort_unstable();
+        locales.dedup();
+        locales
+    }
+
+    /// Resolves voice best matching requested locale and optional style.
+    /// Exact locale is preferred, then voices speaking it as secondary locale,
+    /// then voices of the same language (e.g. *en-GB* for *en-AU*).
+    /// Among those exact style match is preferred over partial match
+    /// (e.g. *cheer* for *cheerful*) and neural voices over standard ones.
+    /// Ties are resolved by catalog order. Returns None if no voice speaks the language.
+    pub fn best_voice(&self, locale: &str, style: Option<&str>) -> Option<&VoiceDescriptor> {
+        let locale = normalize_locale(locale);
+        let lang = language(&locale);
+        let style = style.map(normalize_style).filter(|s| !s.is_empty());
+
+        let locale_score = |voice: &VoiceDescriptor| {
+            let secondary = voice.secondary_locales.iter().map(|l| normalize_locale(l));
+            let primary = normalize_locale(&voice.locale);
+            if primary == locale {
+                4
+            } else if secondary.clone().any(|l| l == locale) {
+                3
+            } else if language(&primary) == lang {
+                2
+            } else if secondary.into_iter().any(|l| language(&l) == lang) {
+                1
+            } else {
+                0
+            }
+        };
+        let style_score = |voice: &VoiceDescriptor| match &style {
+            None => 0,
+            Some(style) => voice
+                .style_list
+                .iter()
+                .map(|s| normalize_style(s))
+                .map(|s| {
+                    if s == *style {
+                        2
+                    } else if s.starts_with(style.as_str()) || style.starts_with(s.as_str()) {
+                        1
+                    } else {
+                        0
+                    }
+                })
+                .max()
+                .unwrap_or(0),
+        };
+
+        let mut best: Option<((u8, u8, bool), &VoiceDescriptor)> = None;
+        for voice in &self.voices {
+            let score = (locale_score(voice), style_score(voice), voice.is_neural());
+            if score.0 == 0 {
+                continue;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score > *best_score)
+            {
+                best = Some((score, voice));
+            }
+        }
+        best.map(|(_, voice)| voice)
+    }
+}
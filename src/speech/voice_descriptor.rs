@@ -0,0 +1,104 @@
+use crate::common::{Gender, SynthesisVoiceType};
+use crate::error::Result;
+use crate::speech::VoiceInfo;
+use serde::{Deserialize, Serialize};
+
+/// VoiceDescriptor is owned, serializable counterpart of *VoiceInfo*
+/// with voice properties parsed into typed fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceDescriptor {
+    pub name: String,
+    pub short_name: String,
+    pub local_name: String,
+    pub locale: String,
+    /// Additional locales the voice can speak (multilingual voices).
+    #[serde(default)]
+    pub secondary_locales: Vec<String>,
+    pub voice_type: SynthesisVoiceType,
+    #[serde(default)]
+    pub gender: Gender,
+    #[serde(default)]
+    pub style_list: Vec<String>,
+    /// Roles usable with *role* attribute of *mstts:express-as* element.
+    #[serde(default)]
+    pub role_play_list: Vec<String>,
+    pub sample_rate_hertz: Option<u32>,
+    pub words_per_minute: Option<u32>,
+    /// Path to offline voice, empty for online voices.
+    #[serde(default)]
+    pub voice_path: String,
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(['|', ','])
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    value.trim().parse().ok()
+}
+
+impl VoiceDescriptor {
+    pub fn from_voice_info(voice: &VoiceInfo) -> Result<Self> {
+        let property = |name: &str| voice.properties.get_property_by_string(name, "");
+        Ok(VoiceDescriptor {
+            name: voice.name.clone(),
+            short_name: voice.short_name.clone(),
+            local_name: voice.local_name.clone(),
+            locale: voice.locale.clone(),
+            secondary_locales: split_list(&property("SecondaryLocaleList")?),
+            voice_type: voice.voice_type,
+            gender: Gender::from_name(&property("Gender")?),
+            style_list: voice
+                .style_list
+                .iter()
+                .filter(|style| !style.is_empty())
+                .cloned()
+                .collect(),
+            role_play_list: split_list(&property("RolePlayList")?),
+            sample_rate_hertz: parse_number(&property("SampleRateHertz")?),
+            words_per_minute: parse_number(&property("WordsPerMinute")?),
+            voice_path: voice.voice_path.clone(),
+        })
+    }
+
+    pub fn is_neural(&self) -> bool {
+        self.voice_type.is_neural()
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.voice_type.is_offline()
+    }
+
+    /// Case insensitive check of style support, e.g. *cheerful*.
+    pub fn supports_style(&self, style: &str) -> bool {
+        self.style_list
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(style))
+    }
+
+    /// Case insensitive check of role support, e.g. *YoungAdultFemale*.
+    pub fn supports_role(&self, role: &str) -> bool {
+        self.role_play_list
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case(role))
+    }
+
+    /// Returns true if voice speaks given locale as primary or secondary locale.
+    pub fn speaks(&self, locale: &str) -> bool {
+        let locale = normalize_locale(locale);
+        normalize_locale(&self.locale) == locale
+            || self
+                .secondary_locales
+                .iter()
+                .any(|l| normalize_locale(l) == locale)
+    }
+}
+
+/// Lowercases locale and unifies separators, e.g. *en_US* -> *en-us*.
+pub(crate) fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
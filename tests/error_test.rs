@@ -0,0 +1,104 @@
+use cognitive_services_speech_sdk_rs::common::CancellationErrorCode;
+use cognitive_services_speech_sdk_rs::error::{Error, SpxError};
+use std::collections::HashSet;
+
+const NATIVE_CODES: std::ops::RangeInclusive<usize> = 0x001..=0x032;
+
+#[test]
+fn spx_error_code_roundtrip_test() {
+    let mut names = HashSet::new();
+    for code in NATIVE_CODES.chain(std::iter::once(0xfff)) {
+        let error = SpxError::from_code(code);
+        assert_eq!(error.code(), Some(code), "{:?}", error);
+        if let SpxError::Unknown(_) = error {
+            // 0x02D..=0x02F are not assigned
+            assert!((0x02D..=0x02F).contains(&code));
+            continue;
+        }
+        assert!(error.name().starts_with("SPXERR_"));
+        assert!(
+            names.insert(error.name()),
+            "duplicate name {}",
+            error.name()
+        );
+    }
+    assert_eq!(SpxError::from_code(0x1234), SpxError::Unknown(0x1234));
+    assert_eq!(
+        SpxError::Unknown(0x1234).to_string(),
+        "UNKNOWN SPXERR (0x1234)"
+    );
+    assert_eq!(SpxError::Timeout.to_string(), "SPXERR_TIMEOUT");
+}
+
+#[test]
+fn spx_error_from_cancellation_test() {
+    let codes = [
+        CancellationErrorCode::AuthenticationFailure,
+        CancellationErrorCode::BadRequest,
+        CancellationErrorCode::TooManyRequests,
+        CancellationErrorCode::Forbidden,
+        CancellationErrorCode::ConnectionFailure,
+        CancellationErrorCode::ServiceTimeout,
+        CancellationErrorCode::ServiceError,
+        CancellationErrorCode::ServiceUnavailable,
+        CancellationErrorCode::RuntimeError,
+    ];
+    assert_eq!(
+        SpxError::from_cancellation_error_code(&CancellationErrorCode::NoError),
+        None
+    );
+    for code in codes.iter() {
+        let error = SpxError::from_cancellation_error_code(code).unwrap();
+        // cancellation-only variants have no native code
+        assert_eq!(error.code(), None, "{:?}", error);
+        assert_eq!(error.name(), format!("{:?}", code));
+    }
+
+    let transient: Vec<_> = codes
+        .iter()
+        .map(|code| SpxError::from_cancellation_error_code(code).unwrap())
+        .filter(SpxError::is_transient)
+        .collect();
+    assert_eq!(
+        transient,
+        vec![
+            SpxError::TooManyRequests,
+            SpxError::ConnectionFailure,
+            SpxError::ServiceTimeout,
+            SpxError::ServiceUnavailable,
+        ]
+    );
+    assert!(SpxError::Timeout.is_transient());
+    assert!(!SpxError::InvalidArg.is_transient());
+    assert!(SpxError::AuthenticationFailure.is_auth());
+    assert!(!SpxError::Forbidden.is_auth());
+}
+
+#[test]
+fn api_error_test() {
+    let error = Error::api_error(
+        "SpeechRecognizer.recognize_once_async error",
+        "native message",
+        SpxError::Timeout,
+    );
+    assert_eq!(
+        error.operation.as_deref(),
+        Some("SpeechRecognizer.recognize_once_async")
+    );
+    assert_eq!(error.native_message.as_deref(), Some("native message"));
+    assert_eq!(
+        error.message,
+        "SpeechRecognizer.recognize_once_async error: native message"
+    );
+    assert!(error.is_transient());
+
+    let error = Error::from_cancellation(
+        "CancellationDetails",
+        &CancellationErrorCode::AuthenticationFailure,
+        "invalid key",
+    )
+    .unwrap();
+    assert_eq!(error.operation.as_deref(), Some("CancellationDetails"));
+    assert!(error.is_auth());
+    assert!(Error::from_cancellation("op", &CancellationErrorCode::NoError, "").is_none());
+}
@@ -1,8 +1,21 @@
 use cognitive_services_speech_sdk_rs::{
-    audio::AudioConfig,
-    speech::{PhraseListGrammar, SpeechConfig, SpeechRecognizer},
+    audio::{
+        AudioConfig, AudioInputStream, AudioStreamContainerFormat, AudioStreamFormat,
+        ChunkMetadata, PushAudioInputStream, WaveFormat,
+    },
+    speech::{
+        AudioChunk, AudioDataStream, AutoDetectSourceLanguageConfig,
+        AutoDetectSourceLanguageResult, ConnectionState, DisconnectReason, MultichannelMode,
+        MultichannelRecognizer, PhraseListGrammar, ReconnectPolicy, ResilientRecognizer,
+        SpeechConfig, SpeechRecognizer, SpeechSynthesizer, SynthesisInput, TextChunker,
+        TextChunking,
+    },
 };
+use futures::StreamExt;
 use log::{error, *};
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::{env, path::PathBuf};
 mod common;
 use common::*;
@@ -75,3 +88,327 @@ async fn phrase_list_test() {
     );
     assert!(result.text.to_lowercase().contains("peloozoid"));
 }
+
+#[tokio::test]
+async fn recognize_text_once_test() {
+    let file_path_str = &get_sample_file("myVoiceIsMyPassportVerifyMe01.wav");
+    let mut speech_recognizer = speech_recognizer_from_wav_file(file_path_str);
+
+    let result = speech_recognizer
+        .recognize_text_once_async("turn on the lights")
+        .await
+        .unwrap();
+    info!("got text recognition {result:?}");
+    assert!(result.text.to_lowercase().contains("turn on the lights"));
+}
+
+#[tokio::test]
+async fn multiple_subscribers_test() {
+    let file_path_str = &get_sample_file("myVoiceIsMyPassportVerifyMe01.wav");
+    let mut speech_recognizer = speech_recognizer_from_wav_file(file_path_str);
+
+    let first_count = Arc::new(AtomicUsize::new(0));
+    let second_count = Arc::new(AtomicUsize::new(0));
+
+    let first_count_cb = first_count.clone();
+    let _first = speech_recognizer
+        .on_recognized(move |_| {
+            first_count_cb.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+    let second_count_cb = second_count.clone();
+    let second = speech_recognizer
+        .on_recognized(move |_| {
+            second_count_cb.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+    // dropped subscription must not be called anymore
+    drop(second);
+
+    let result = speech_recognizer.recognize_once_async().await.unwrap();
+    info!("got recognition {result:?}");
+    assert_eq!(first_count.load(Ordering::SeqCst), 1);
+    assert_eq!(second_count.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn auto_detect_source_language_test() {
+    let file_path_str = &get_sample_file("chinese_test.wav");
+    let audio_config = AudioConfig::from_wav_file_input(file_path_str).unwrap();
+    let speech_config = SpeechConfig::from_subscription(
+        env::var("MSSubscriptionKey").unwrap(),
+        env::var("MSServiceRegion").unwrap(),
+    )
+    .unwrap();
+    let lang_config =
+        AutoDetectSourceLanguageConfig::from_languages(vec!["en-US".into(), "zh-CN".into()])
+            .unwrap();
+
+    let mut speech_recognizer = SpeechRecognizer::from_auto_detect_source_lang_config(
+        speech_config,
+        audio_config,
+        lang_config,
+    )
+    .unwrap();
+
+    let result = speech_recognizer.recognize_once_async().await.unwrap();
+    let detected = AutoDetectSourceLanguageResult::from_speech_recognition_result(&result).unwrap();
+    info!("got detected language {detected:?}");
+    assert_eq!(detected.language.as_deref(), Some("zh-CN"));
+}
+
+#[tokio::test]
+async fn resilient_recognizer_test() {
+    let file_path_str = &get_sample_file("myVoiceIsMyPassportVerifyMe01.wav");
+    // skip canonical 44 bytes long wav header, file is 16kHz 16bit mono PCM
+    let audio = std::fs::read(file_path_str).unwrap();
+
+    let mut recognizer = ResilientRecognizer::new(
+        || {
+            SpeechConfig::from_subscription(
+                env::var("MSSubscriptionKey").unwrap(),
+                env::var("MSServiceRegion").unwrap(),
+            )
+        },
+        16000,
+        16,
+        1,
+        ReconnectPolicy::default(),
+    );
+
+    let text = Arc::new(Mutex::new(String::new()));
+    let text_cb = text.clone();
+    let _recognized = recognizer
+        .on_recognized(move |event| {
+            text_cb.lock().unwrap().push_str(&event.result.text);
+        })
+        .unwrap();
+
+    recognizer
+        .start_continuous_recognition_async()
+        .await
+        .unwrap();
+    recognizer.write(&audio[44..]).unwrap();
+    recognizer.close_stream().unwrap();
+    recognizer.wait().await.unwrap();
+
+    let text = text.lock().unwrap().to_lowercase();
+    info!("got recognition {text:?}");
+    assert!(text.contains("passport"));
+}
+
+#[tokio::test]
+async fn synthesize_stream_test() {
+    let (speech_synthesizer, _) = speech_synthesizer_pull();
+
+    let mut stream = speech_synthesizer
+        .synthesize_stream(SynthesisInput::Text("Hello Rust streaming!"))
+        .await
+        .unwrap();
+
+    let mut audio_len = 0;
+    let mut completed = None;
+    while let Some(chunk) = stream.next().await {
+        match chunk.unwrap() {
+            AudioChunk::Audio {
+                data, byte_offset, ..
+            } => {
+                assert_eq!(byte_offset, audio_len);
+                audio_len += data.len() as u64;
+            }
+            AudioChunk::Completed {
+                audio_length,
+                metrics,
+                ..
+            } => {
+                assert_eq!(audio_length, audio_len);
+                completed = Some(metrics);
+            }
+            other => info!("got event {other:?}"),
+        }
+    }
+    info!("got metrics {completed:?}");
+    assert!(audio_len > 0);
+    assert!(completed.unwrap().first_byte_latency.is_some());
+}
+
+#[tokio::test]
+async fn synthesize_text_stream_test() {
+    let region = env::var("MSServiceRegion").unwrap();
+    // text streaming requires websocket v2 endpoint
+    let speech_config = SpeechConfig::from_endpoint_with_subscription(
+        format!("wss://{region}.tts.speech.microsoft.com/cognitiveservices/websocket/v2"),
+        env::var("MSSubscriptionKey").unwrap(),
+    )
+    .unwrap();
+    let speech_synthesizer =
+        SpeechSynthesizer::from_optional_audio_config(speech_config, None).unwrap();
+
+    let tokens = futures::stream::iter(
+        ["Text ", "streaming ", "works. ", "Second ", "sentence."].map(String::from),
+    );
+    let mut stream = speech_synthesizer
+        .synthesize_text_stream(tokens, TextChunker::new(TextChunking::Sentence))
+        .await
+        .unwrap();
+
+    let mut audio_len = 0;
+    let mut completed = false;
+    while let Some(chunk) = stream.next().await {
+        match chunk.unwrap() {
+            AudioChunk::Audio { data, .. } => audio_len += data.len(),
+            AudioChunk::Completed { .. } => completed = true,
+            _ => {}
+        }
+    }
+    assert!(audio_len > 0);
+    assert!(completed);
+}
+
+#[tokio::test]
+async fn synthesizer_connection_test() {
+    let (speech_synthesizer, _) = speech_synthesizer_pull();
+    let reasons = Arc::new(Mutex::new(vec![]));
+    let reasons_clone = reasons.clone();
+    let _subscription = speech_synthesizer
+        .on_disconnected(move |event| reasons_clone.lock().unwrap().push(event.reason))
+        .unwrap();
+
+    assert_eq!(
+        speech_synthesizer.connection_state(),
+        ConnectionState::Disconnected
+    );
+    speech_synthesizer
+        .warm_up(std::time::Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(
+        speech_synthesizer.connection_state(),
+        ConnectionState::Connected
+    );
+
+    speech_synthesizer.close_connection().unwrap();
+    for _ in 0..50 {
+        if speech_synthesizer.connection_state() == ConnectionState::Disconnected {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(
+        *reasons.lock().unwrap(),
+        vec![Some(DisconnectReason::Closed)]
+    );
+}
+
+#[tokio::test]
+async fn audio_data_stream_io_test() {
+    let (speech_synthesizer, _) = speech_synthesizer_pull();
+    let result = speech_synthesizer
+        .speak_text_async("Hello Rust!")
+        .await
+        .unwrap();
+    let audio_data_len = result.audio_data.len() as u64;
+    let mut stream = AudioDataStream::from_speech_synthesis_result(result).unwrap();
+
+    let mut audio = vec![];
+    let copied = std::io::copy(&mut stream, &mut audio).unwrap();
+    assert_eq!(copied, audio_data_len);
+    assert!(stream.cancellation_details().unwrap().is_none());
+
+    assert_eq!(stream.seek(SeekFrom::End(-10)).unwrap(), copied - 10);
+    stream.seek(SeekFrom::Start(0)).unwrap();
+    let mut header = [0u8; 100];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(&header[..], &audio[..100]);
+    assert_eq!(stream.position().unwrap(), 100);
+}
+
+#[test]
+fn audio_stream_format_test() {
+    let format = AudioStreamFormat::from_waveformat(8000, 8, 1, WaveFormat::Mulaw).unwrap();
+    assert_eq!(format.wave_format(), Some(WaveFormat::Mulaw));
+    assert_eq!(format.samples_per_second(), Some(8000));
+    assert_eq!(format.bits_per_sample(), Some(8));
+    assert_eq!(format.channels(), Some(1));
+    assert_eq!(format.container_format(), None);
+    PushAudioInputStream::create_push_stream_from_format(format).unwrap();
+
+    let format = AudioStreamFormat::get_default_input_format().unwrap();
+    assert_eq!(format.wave_format(), Some(WaveFormat::Pcm));
+    assert_eq!(format.samples_per_second(), Some(16000));
+
+    let format = AudioStreamFormat::get_compressed_format(AudioStreamContainerFormat::Mp3).unwrap();
+    assert_eq!(format.wave_format(), None);
+    assert_eq!(
+        format.container_format(),
+        Some(AudioStreamContainerFormat::Mp3)
+    );
+}
+
+#[test]
+fn chunk_metadata_test() {
+    let mut stream = PushAudioInputStream::create_push_stream().unwrap();
+    let alice = ChunkMetadata {
+        timestamp: Some(90_000),
+        user_id: Some("alice".to_owned()),
+    };
+    // 16 kHz 16 bit mono, 32000 bytes is one second (10_000_000 ticks)
+    stream
+        .write_with_metadata(vec![0u8; 32000], &alice)
+        .unwrap();
+    stream.write(vec![0u8; 32000]).unwrap();
+    stream.close_stream().unwrap();
+
+    let timeline = stream.chunk_metadata_timeline().unwrap();
+    assert_eq!(timeline.lookup(5_000_000), Some(alice));
+    assert_eq!(timeline.lookup(15_000_000), None);
+}
+
+#[tokio::test]
+async fn multichannel_fan_out_test() {
+    let file_path_str = &get_sample_file("myVoiceIsMyPassportVerifyMe01.wav");
+    // 16kHz 16bit mono PCM, speech goes to left channel, right channel is silent
+    let audio = std::fs::read(file_path_str).unwrap();
+    let stereo: Vec<u8> = audio[44..]
+        .chunks_exact(2)
+        .flat_map(|sample| [sample[0], sample[1], 0, 0])
+        .collect();
+
+    let mut recognizer = MultichannelRecognizer::new(
+        || {
+            SpeechConfig::from_subscription(
+                env::var("MSSubscriptionKey").unwrap(),
+                env::var("MSServiceRegion").unwrap(),
+            )
+        },
+        16000,
+        2,
+        MultichannelMode::FanOut,
+    )
+    .unwrap();
+    recognizer
+        .start_continuous_recognition_async()
+        .await
+        .unwrap();
+    recognizer.write(&stereo).unwrap();
+    recognizer.close_stream().unwrap();
+
+    for _ in 0..30 {
+        if !recognizer.transcript().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    recognizer
+        .stop_continuous_recognition_async()
+        .await
+        .unwrap();
+
+    let transcript = recognizer.transcript();
+    info!("got transcript {transcript:?}");
+    assert!(transcript
+        .iter()
+        .any(|segment| segment.channel == 0 && segment.text.to_lowercase().contains("passport")));
+    assert!(transcript.iter().all(|segment| segment.channel == 0));
+}
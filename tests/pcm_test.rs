@@ -0,0 +1,109 @@
+use cognitive_services_speech_sdk_rs::audio::pcm::{
+    alaw_to_i16, deinterleave, mulaw_to_i16, ChannelMix, PcmConverter, Resampler, SampleFormat,
+    WavHeader,
+};
+use std::io::Cursor;
+
+#[test]
+fn wav_header_roundtrip() {
+    let header = WavHeader::new(SampleFormat::F32, 48000, 2, 4800);
+    let mut buf = vec![];
+    header.write_to(&mut buf).unwrap();
+    buf.extend(vec![0u8; 4800]);
+
+    let mut reader = Cursor::new(buf);
+    let parsed = WavHeader::read_from(&mut reader).unwrap();
+    assert_eq!(parsed, header);
+    assert_eq!(parsed.block_align(), Some(8));
+    assert_eq!(parsed.byte_rate(), Some(384_000));
+
+    // 65535 channels of 32-bit samples overflow 16-bit block align
+    let header = WavHeader::new(SampleFormat::F32, 48000, u16::MAX, 0);
+    assert_eq!(header.block_align(), None);
+    let mut buf = vec![];
+    assert!(header.write_to(&mut buf).is_err());
+    // byte rate overflows 32 bits
+    let header = WavHeader::new(SampleFormat::I16, u32::MAX, 2, 0);
+    assert_eq!(header.byte_rate(), None);
+    assert!(header.write_to(&mut buf).is_err());
+
+    // the same header crafted by hand is rejected by the parser
+    let mut buf = vec![];
+    WavHeader::new(SampleFormat::I16, 16000, 2, 0)
+        .write_to(&mut buf)
+        .unwrap();
+    buf[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(WavHeader::read_from(&mut Cursor::new(buf)).is_err());
+}
+
+#[test]
+fn g711_decoding() {
+    assert_eq!(mulaw_to_i16(0xFF), 0);
+    assert_eq!(mulaw_to_i16(0x00), -32124);
+    assert_eq!(mulaw_to_i16(0x80), 32124);
+    assert_eq!(alaw_to_i16(0xD5), 8);
+    assert_eq!(alaw_to_i16(0x55), -8);
+}
+
+#[test]
+fn stereo_float_to_mono_16k() {
+    // 48kHz stereo float, left channel full scale, right channel silent
+    let mut data = vec![];
+    for _ in 0..4800 {
+        data.extend_from_slice(&1.0f32.to_le_bytes());
+        data.extend_from_slice(&0.0f32.to_le_bytes());
+    }
+    let mut converter =
+        PcmConverter::new(SampleFormat::F32, 48000, 2, 16000, ChannelMix::Select(0)).unwrap();
+    // feed in odd sized chunks to exercise carry over of incomplete frames
+    let mut converted = vec![];
+    for chunk in data.chunks(1001) {
+        converted.extend(converter.convert(chunk));
+    }
+    let samples: Vec<i16> = converted
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert!((1598..=1600).contains(&samples.len()));
+    assert!(samples.iter().all(|s| *s == i16::MAX));
+
+    assert!(PcmConverter::new(SampleFormat::F32, 48000, 2, 16000, ChannelMix::Select(2)).is_err());
+}
+
+/// Returns peak amplitude of *frequency* tone resampled from 48kHz to 16kHz.
+fn resampled_tone_peak(frequency: f32) -> f32 {
+    let tone: Vec<f32> = (0..4800)
+        .map(|i| (2.0 * std::f32::consts::PI * frequency * i as f32 / 48000.0).sin())
+        .collect();
+    let mut resampler = Resampler::new(48000, 16000);
+    let mut output = vec![];
+    for chunk in tone.chunks(777) {
+        output.extend(resampler.process(chunk));
+    }
+    assert!((1598..=1600).contains(&output.len()));
+    // skip start of the signal where the filter settles
+    output[100..].iter().fold(0.0, |peak, s| s.abs().max(peak))
+}
+
+#[test]
+fn resampler_anti_aliasing() {
+    // 1kHz passes through
+    assert!(resampled_tone_peak(1000.0) > 0.95);
+    // 12kHz is above 8kHz Nyquist frequency of the output and would alias to 4kHz
+    assert!(resampled_tone_peak(12000.0) < 0.01);
+}
+
+#[test]
+fn resampler_upsampling() {
+    let mut resampler = Resampler::new(8000, 16000);
+    let mut output = resampler.process(&[0.0, 1.0]);
+    output.extend(resampler.process(&[0.0]));
+    assert_eq!(output, vec![0.0, 0.5, 1.0, 0.5]);
+}
+
+#[test]
+fn stereo_deinterleave() {
+    let data = [1u8, 1, 2, 2, 3, 3, 4, 4, 5];
+    let split = deinterleave(&data, 2, 2);
+    assert_eq!(split, vec![vec![1, 1, 3, 3], vec![2, 2, 4, 4]]);
+}
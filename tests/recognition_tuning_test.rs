@@ -0,0 +1,58 @@
+use cognitive_services_speech_sdk_rs::common::{LanguageIdMode, SegmentationStrategy};
+use cognitive_services_speech_sdk_rs::speech::{RecognitionTuning, SpeechConfig};
+use std::time::Duration;
+
+#[test]
+fn recognition_tuning_validate_test() {
+    let silence = |silence_ms: u64, max_ms: Option<u64>| {
+        RecognitionTuning::new().segmentation(SegmentationStrategy::Silence {
+            silence_timeout: Duration::from_millis(silence_ms),
+            max_segment_time: max_ms.map(Duration::from_millis),
+        })
+    };
+    assert!(silence(500, Some(30_000)).validate().is_ok());
+    assert!(silence(50, None).validate().is_err());
+    assert!(silence(500, Some(90_000)).validate().is_err());
+    assert!(RecognitionTuning::new()
+        .initial_silence_timeout(Duration::ZERO)
+        .validate()
+        .is_err());
+    assert!(RecognitionTuning::new()
+        .stable_partial_result_threshold(0)
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn recognition_tuning_read_back_test() {
+    let mut speech_config = SpeechConfig::from_subscription("key", "westeurope").unwrap();
+    assert_eq!(
+        speech_config.get_recognition_tuning().unwrap(),
+        RecognitionTuning::default()
+    );
+
+    let tuning = RecognitionTuning::new()
+        .initial_silence_timeout(Duration::from_secs(5))
+        .end_silence_timeout(Duration::from_millis(800))
+        .segmentation(SegmentationStrategy::Silence {
+            silence_timeout: Duration::from_millis(600),
+            max_segment_time: Some(Duration::from_secs(25)),
+        })
+        .stable_partial_result_threshold(3)
+        .request_snr(true)
+        .true_text(true)
+        .language_id_mode(LanguageIdMode::Continuous);
+    speech_config.set_recognition_tuning(&tuning).unwrap();
+    assert_eq!(speech_config.get_recognition_tuning().unwrap(), tuning);
+
+    // switching strategy clears silence segmentation timeouts
+    speech_config
+        .set_recognition_tuning(
+            &RecognitionTuning::new().segmentation(SegmentationStrategy::Semantic),
+        )
+        .unwrap();
+    assert_eq!(
+        speech_config.get_recognition_tuning().unwrap().segmentation,
+        Some(SegmentationStrategy::Semantic)
+    );
+}
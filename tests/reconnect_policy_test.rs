@@ -0,0 +1,30 @@
+use cognitive_services_speech_sdk_rs::speech::ReconnectPolicy;
+use std::time::Duration;
+
+#[test]
+fn reconnect_policy_backoff_test() {
+    let policy = ReconnectPolicy::default();
+    assert_eq!(policy.backoff(0), Duration::from_millis(500));
+    assert_eq!(policy.backoff(1), Duration::from_millis(500));
+    assert_eq!(policy.backoff(2), Duration::from_secs(1));
+    assert_eq!(policy.backoff(4), Duration::from_secs(4));
+    assert_eq!(policy.backoff(10), Duration::from_secs(30));
+    assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+
+    // negative delay is clamped to zero instead of panicking
+    let policy = ReconnectPolicy {
+        backoff_multiplier: -2.0,
+        ..ReconnectPolicy::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(500));
+    assert_eq!(policy.backoff(2), Duration::ZERO);
+    assert_eq!(policy.backoff(3), Duration::from_secs(2));
+    assert_eq!(policy.backoff(u32::MAX), Duration::ZERO);
+
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::ZERO,
+        backoff_multiplier: f64::INFINITY,
+        ..ReconnectPolicy::default()
+    };
+    assert_eq!(policy.backoff(2), Duration::from_secs(30));
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "runtime-loading")]
+use cognitive_services_speech_sdk_rs::error::{ErrorRootCause, LibraryLoadError};
+use cognitive_services_speech_sdk_rs::runtime::{
+    load_speech_sdk, set_library_path, speech_sdk_available,
+};
+use cognitive_services_speech_sdk_rs::speech::SpeechConfig;
+
+#[test]
+fn runtime_loading_missing_library_test() {
+    set_library_path("/nonexistent/libMicrosoft.CognitiveServices.Speech.core.so").unwrap();
+    assert!(!speech_sdk_available());
+    assert!(load_speech_sdk().is_err());
+
+    let err = SpeechConfig::from_subscription("key", "westeurope").unwrap_err();
+    match err.caused_by {
+        ErrorRootCause::LibraryLoadError(LibraryLoadError::LibraryNotFound(library)) => {
+            assert!(library.starts_with("/nonexistent/"))
+        }
+        other => panic!("unexpected root cause {:?}", other),
+    }
+    // library path cannot be changed once loading was attempted
+    assert!(set_library_path("libMicrosoft.CognitiveServices.Speech.core.so").is_err());
+}
@@ -0,0 +1,105 @@
+use cognitive_services_speech_sdk_rs::common::{OutputFormat, PropertyId};
+use cognitive_services_speech_sdk_rs::speech::SpeechSettings;
+
+fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn speech_settings_layered_loading_test() {
+    let dir = std::env::temp_dir().join(format!("speech_settings_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let toml_path = dir.join("speech.toml");
+    let json_path = dir.join("speech.local.json");
+    std::fs::write(
+        &toml_path,
+        r#"
+region = "westeurope"
+recognition_language = "en-US"
+output_format = "Simple"
+
+[proxy]
+host = "proxy.local"
+port = 8080
+
+[properties]
+SpeechServiceConnectionInitialSilenceTimeoutMs = "5000"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        &json_path,
+        r#"{ "recognition_language": "de-DE", "proxy": { "password": "proxy-secret" } }"#,
+    )
+    .unwrap();
+
+    let files = [toml_path, dir.join("missing.toml"), json_path];
+    let mut settings = SpeechSettings::default();
+    for file in files.iter().filter(|f| f.exists()) {
+        settings = settings.merge(SpeechSettings::from_file(file).unwrap());
+    }
+    let settings = settings.merge(
+        SpeechSettings::from_vars(
+            "SPEECH_",
+            vars(&[
+                ("SPEECH_SUBSCRIPTION_KEY", "secret-key"),
+                ("SPEECH_OUTPUT_FORMAT", "Detailed"),
+                ("SPEECH_PROPERTY_SpeechServiceConnectionEndpointId", "abc"),
+                ("SPEECH_PROPERTY_CustomProperty", "1"),
+                ("OTHER_REGION", "eastus"),
+            ]),
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        SpeechSettings::load(&files, "SPEECH_SETTINGS_TEST_UNSET_").unwrap(),
+        SpeechSettings::from_file(&files[0])
+            .unwrap()
+            .merge(SpeechSettings::from_file(&files[2]).unwrap())
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(settings.region.as_deref(), Some("westeurope"));
+    assert_eq!(settings.recognition_language.as_deref(), Some("de-DE"));
+    assert_eq!(settings.output_format, Some(OutputFormat::Detailed));
+    assert_eq!(settings.subscription_key.as_deref(), Some("secret-key"));
+    let proxy = settings.proxy.as_ref().unwrap();
+    assert_eq!(proxy.host.as_deref(), Some("proxy.local"));
+    assert_eq!(proxy.port, Some(8080));
+    assert_eq!(proxy.password.as_deref(), Some("proxy-secret"));
+    assert_eq!(settings.properties.len(), 2);
+    assert_eq!(
+        settings.properties[&PropertyId::SpeechServiceConnectionEndpointId],
+        "abc"
+    );
+    assert_eq!(settings.string_properties["CustomProperty"], "1");
+
+    let debug = format!("{:?}", settings);
+    assert!(!debug.contains("secret-key"));
+    assert!(!debug.contains("proxy-secret"));
+    assert!(debug.contains("westeurope"));
+
+    // secrets set by property string name are redacted as well
+    let mut settings = SpeechSettings::default();
+    for name in [
+        "SpeechServiceConnection_Key",
+        "SpeechServiceAuthorization_Token",
+        "speechserviceconnection_proxypassword",
+    ] {
+        settings
+            .string_properties
+            .insert(name.to_owned(), format!("{}-secret", name));
+    }
+    settings
+        .string_properties
+        .insert("CustomProperty".to_owned(), "visible".to_owned());
+    let debug = format!("{:?}", settings);
+    assert!(!debug.contains("-secret"), "{}", debug);
+    assert!(debug.contains("SpeechServiceConnection_Key"));
+    assert!(debug.contains("visible"));
+
+    assert!(SpeechSettings::from_vars("SPEECH_", vars(&[("SPEECH_PROFANITY", "Loud")])).is_err());
+    assert!(SpeechSettings::from_file("speech.yaml").is_err());
+}
@@ -0,0 +1,108 @@
+use cognitive_services_speech_sdk_rs::audio::pcm::{SampleFormat, WavHeader};
+use cognitive_services_speech_sdk_rs::common::{
+    SpeechSynthesisOutputFormat, SynthesisAudioFormat, SynthesisCodec, SynthesisContainer,
+};
+use cognitive_services_speech_sdk_rs::error::{ErrorRootCause, SpxError};
+
+fn wav(sample_format: SampleFormat, data_len: u32, data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    WavHeader::new(sample_format, 8000, 1, data_len)
+        .write_to(&mut buf)
+        .unwrap();
+    buf.extend_from_slice(data);
+    buf
+}
+
+#[test]
+fn synthesis_audio_format_test() {
+    let format = SpeechSynthesisOutputFormat::Riff24Khz16BitMonoPcm.audio_format();
+    assert_eq!(format.container, SynthesisContainer::Riff);
+    assert_eq!(format.codec, SynthesisCodec::Pcm);
+    assert_eq!(format.samples_per_second, 24000);
+    assert_eq!(format.bits_per_sample, Some(16));
+    assert_eq!(format.bitrate, Some(384_000));
+    assert_eq!(format.channels, 1);
+    assert_eq!(
+        (format.mime_type, format.file_extension),
+        ("audio/wav", "wav")
+    );
+
+    let format = SpeechSynthesisOutputFormat::Audio24Khz48KBitRateMonoMp3.audio_format();
+    assert_eq!(format.container, SynthesisContainer::Raw);
+    assert_eq!(format.codec, SynthesisCodec::Mp3);
+    assert_eq!(format.bits_per_sample, None);
+    assert_eq!(format.bitrate, Some(48_000));
+    assert_eq!(format.mime_type, "audio/mpeg");
+
+    let format = SpeechSynthesisOutputFormat::Raw22050Hz16BitMonoPcm.audio_format();
+    assert_eq!(format.samples_per_second, 22050);
+    assert_eq!(format.file_extension, "pcm");
+
+    let format = SpeechSynthesisOutputFormat::Webm24Khz16Bit24KbpsMonoOpus.audio_format();
+    assert_eq!(format.container, SynthesisContainer::Webm);
+    assert_eq!(format.codec, SynthesisCodec::Opus);
+    assert_eq!(format.bitrate, Some(24_000));
+
+    assert_eq!(
+        SpeechSynthesisOutputFormat::from_name("raw-8khz-8bit-mono-mulaw"),
+        Some(SpeechSynthesisOutputFormat::Raw8Khz8BitMonoMULaw)
+    );
+    assert_eq!(SpeechSynthesisOutputFormat::from_name("flac"), None);
+}
+
+#[test]
+fn synthesis_audio_format_decode_riff_test() {
+    let samples: [i16; 3] = [0, i16::MAX, -1234];
+    let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    let audio = wav(SampleFormat::I16, data.len() as u32, &data);
+    let format = SynthesisAudioFormat::from_wav(&audio).unwrap();
+    assert_eq!(format.container, SynthesisContainer::Riff);
+    assert_eq!(format.codec, SynthesisCodec::Pcm);
+    assert_eq!(format.samples_per_second, 8000);
+    assert_eq!(format.bits_per_sample, Some(16));
+    assert_eq!(format.decode(&audio).unwrap(), samples);
+
+    // trailing bytes after declared data length are ignored
+    let mut padded = audio.clone();
+    padded.extend_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(format.decode(&padded).unwrap(), samples);
+    // streamed WAV without data length is read until end
+    let streamed = wav(SampleFormat::I16, 0, &data);
+    assert_eq!(format.decode(&streamed).unwrap(), samples);
+
+    let audio = wav(SampleFormat::MuLaw, 3, &[0xFF, 0x80, 0x00]);
+    let format = SynthesisAudioFormat::from_wav(&audio).unwrap();
+    assert_eq!(format.codec, SynthesisCodec::MuLaw);
+    assert_eq!(format.bits_per_sample, Some(8));
+    assert_eq!(format.decode(&audio).unwrap(), vec![0, 32124, -32124]);
+
+    assert_eq!(SynthesisAudioFormat::from_wav(&data), None);
+    assert_eq!(SynthesisAudioFormat::from_wav(&[]), None);
+}
+
+#[test]
+fn synthesis_audio_format_decode_raw_test() {
+    let format = SpeechSynthesisOutputFormat::Raw16Khz16BitMonoPcm.audio_format();
+    // odd trailing byte is dropped
+    assert_eq!(
+        format.decode(&[0x01, 0x00, 0xFF, 0xFF, 0x7F]).unwrap(),
+        vec![1, -1]
+    );
+
+    let format = SpeechSynthesisOutputFormat::Raw8Khz8BitMonoMULaw.audio_format();
+    assert_eq!(
+        format.decode(&[0xFF, 0x80, 0x00]).unwrap(),
+        vec![0, 32124, -32124]
+    );
+
+    // RIFF header in raw data is not stripped
+    let audio = wav(SampleFormat::MuLaw, 1, &[0xFF]);
+    assert_eq!(format.decode(&audio).unwrap().len(), audio.len());
+
+    let format = SpeechSynthesisOutputFormat::Ogg16Khz16BitMonoOpus.audio_format();
+    let error = format.decode(&[0; 16]).unwrap_err();
+    assert!(matches!(
+        error.caused_by,
+        ErrorRootCause::ApiError(SpxError::UnsupportedFormat)
+    ));
+}
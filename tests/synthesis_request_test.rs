@@ -0,0 +1,14 @@
+use cognitive_services_speech_sdk_rs::common::PropertyId;
+use cognitive_services_speech_sdk_rs::speech::SpeechSynthesisRequest;
+
+#[test]
+fn synthesis_request_prosody_test() {
+    let mut request = SpeechSynthesisRequest::new_text_streaming_request().unwrap();
+    request.set_rate("+20%").unwrap();
+    request.set_pitch("-5%").unwrap();
+    request.set_volume("loud").unwrap();
+    let property = |id| request.properties.get_property(id, "").unwrap();
+    assert_eq!(property(PropertyId::SpeechSynthesisRequestRate), "+20%");
+    assert_eq!(property(PropertyId::SpeechSynthesisRequestPitch), "-5%");
+    assert_eq!(property(PropertyId::SpeechSynthesisRequestVolume), "loud");
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "telephony")]
+use cognitive_services_speech_sdk_rs::audio::telephony::{G711Codec, RtpDepacketizer, RtpPacket};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+const SSRC: u32 = 0x1234_5678;
+
+fn packet(sequence_number: u16, timestamp: u32, fill: u8) -> RtpPacket {
+    RtpPacket {
+        marker: false,
+        payload_type: 0,
+        sequence_number,
+        timestamp,
+        ssrc: SSRC,
+        payload: vec![fill; 160],
+    }
+}
+
+#[test]
+fn rtp_packet_parse_test() {
+    let packet = packet(65535, 4_294_967_200, 1);
+    assert_eq!(RtpPacket::parse(&packet.to_bytes()).unwrap(), packet);
+
+    // CSRC, header extension and padding are skipped
+    let mut data = vec![0xB1, 0x80, 0, 1, 0, 0, 0, 160, 0, 0, 0, 7];
+    data.extend_from_slice(&[0, 0, 0, 9]);
+    data.extend_from_slice(&[0xBE, 0xDE, 0, 1, 1, 2, 3, 4]);
+    data.extend_from_slice(&[5, 6, 7, 0, 0, 3]);
+    let parsed = RtpPacket::parse(&data).unwrap();
+    assert!(parsed.marker);
+    assert_eq!(parsed.ssrc, 7);
+    assert_eq!(parsed.payload, vec![5, 6, 7]);
+
+    assert!(RtpPacket::parse(&[0x80, 0, 0]).is_err());
+    assert!(RtpPacket::parse(&[0x40; 12]).is_err());
+}
+
+#[test]
+fn jitter_buffer_test() {
+    let mut depacketizer = RtpDepacketizer::new(G711Codec::Pcmu).with_jitter_depth(2);
+    let mut audio = vec![];
+    // sequence wraps around, 65535 arrives after 0 and 1
+    for (sequence, timestamp, fill) in [(65534, 0, 1), (0, 320, 3), (65535, 160, 2), (1, 480, 4)] {
+        audio.extend(depacketizer.push_packet(packet(sequence, timestamp, fill)));
+    }
+    // late duplicate of already released packet
+    audio.extend(depacketizer.push_packet(packet(65534, 0, 1)));
+    audio.extend(depacketizer.flush());
+
+    assert_eq!(audio.len(), 640);
+    for (i, fill) in [1, 2, 3, 4].iter().enumerate() {
+        assert!(audio[i * 160..(i + 1) * 160].iter().all(|s| s == fill));
+    }
+    assert_eq!(depacketizer.stats().received, 4);
+    assert_eq!(depacketizer.stats().late, 1);
+    assert_eq!(depacketizer.stats().lost, 0);
+}
+
+#[test]
+fn gap_and_ssrc_change_test() {
+    let mut depacketizer = RtpDepacketizer::new(G711Codec::Pcma).with_jitter_depth(0);
+    let mut audio = vec![];
+    let alaw = |sequence, timestamp, ssrc| RtpPacket {
+        payload_type: 8,
+        ssrc,
+        ..packet(sequence, timestamp, 1)
+    };
+    audio.extend(depacketizer.push_packet(alaw(10, 1000, SSRC)));
+    // packet 11 lost
+    audio.extend(depacketizer.push_packet(alaw(12, 1320, SSRC)));
+    // DTMF event is skipped
+    audio.extend(depacketizer.push_packet(RtpPacket {
+        payload_type: 101,
+        ..packet(13, 1480, 0)
+    }));
+    // new source after transfer
+    audio.extend(depacketizer.push_packet(alaw(500, 90000, 42)));
+
+    assert_eq!(audio.len(), 4 * 160);
+    assert!(audio[160..320].iter().all(|s| *s == 0xD5));
+    let stats = depacketizer.stats();
+    assert_eq!(stats.lost, 1);
+    assert_eq!(stats.silence_samples, 160);
+    assert_eq!(stats.unexpected_payload_type, 1);
+    assert_eq!(stats.ssrc_changes, 1);
+
+    // 8 kHz sample is 1250 ticks of 100 ns
+    assert_eq!(depacketizer.offset_for_timestamp(SSRC, 1000), Some(0));
+    assert_eq!(
+        depacketizer.offset_for_timestamp(SSRC, 1320),
+        Some(320 * 1250)
+    );
+    assert_eq!(
+        depacketizer.offset_for_timestamp(42, 90080),
+        Some(560 * 1250)
+    );
+    assert_eq!(depacketizer.offset_for_timestamp(42, 80000), None);
+    assert_eq!(depacketizer.offset_for_timestamp(7, 0), None);
+}
+
+#[tokio::test]
+async fn udp_loopback_test() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    sender
+        .connect(receiver.local_addr().unwrap())
+        .await
+        .unwrap();
+
+    for sequence in [1u16, 3, 2, 4] {
+        let packet = packet(sequence, u32::from(sequence) * 160, sequence as u8);
+        sender.send(&packet.to_bytes()).await.unwrap();
+    }
+    sender.send(b"not rtp").await.unwrap();
+
+    let mut depacketizer = RtpDepacketizer::new(G711Codec::Pcmu).with_jitter_depth(1);
+    let mut audio = vec![];
+    for _ in 0..5 {
+        let received =
+            tokio::time::timeout(Duration::from_secs(5), depacketizer.recv_from(&receiver))
+                .await
+                .unwrap()
+                .unwrap();
+        audio.extend(received);
+    }
+    audio.extend(depacketizer.flush());
+
+    let fills: Vec<u8> = audio.chunks(160).map(|chunk| chunk[0]).collect();
+    assert_eq!(fills, vec![1, 2, 3, 4]);
+    assert_eq!(depacketizer.stats().invalid, 1);
+}
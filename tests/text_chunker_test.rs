@@ -0,0 +1,40 @@
+use cognitive_services_speech_sdk_rs::speech::{TextChunker, TextChunking};
+
+fn chunk_all(mut chunker: TextChunker, tokens: &[&str]) -> Vec<String> {
+    let mut chunks = vec![];
+    for token in tokens {
+        chunks.extend(chunker.push(token));
+    }
+    chunks.extend(chunker.finish());
+    chunks
+}
+
+#[test]
+fn sentence_chunking() {
+    let chunks = chunk_all(
+        TextChunker::new(TextChunking::Sentence),
+        &["Pi is 3", ".14, roughly", ". Next", " sentence! Last"],
+    );
+    assert_eq!(
+        chunks,
+        vec!["Pi is 3.14, roughly. ", "Next sentence! ", "Last"]
+    );
+}
+
+#[test]
+fn clause_chunking() {
+    let chunks = chunk_all(
+        TextChunker::new(TextChunking::Clause),
+        &["First, second", "; third。第四，", "end"],
+    );
+    assert_eq!(chunks, vec!["First, ", "second; third。第四，", "end"]);
+}
+
+#[test]
+fn long_text_is_split_at_whitespace() {
+    let chunks = chunk_all(
+        TextChunker::new(TextChunking::Sentence).with_max_chunk_chars(10),
+        &["aaaa bbbb cccc dddd"],
+    );
+    assert_eq!(chunks, vec!["aaaa bbbb ", "cccc dddd"]);
+}
@@ -0,0 +1,22 @@
+use cognitive_services_speech_sdk_rs::audio::{PullAudioInputStream, PushAudioOutputStream};
+use cognitive_services_speech_sdk_rs::dialog::DialogServiceConnector;
+use cognitive_services_speech_sdk_rs::speech::{
+    SpeechRecognizer, SpeechSynthesisRequest, SpeechSynthesizer,
+};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn thread_safety_test() {
+    // synthesizer futures borrow synthesizer, Sync is needed to spawn them
+    assert_send::<SpeechSynthesizer>();
+    assert_sync::<SpeechSynthesizer>();
+    assert_send::<SpeechSynthesisRequest>();
+    assert_sync::<SpeechSynthesisRequest>();
+    assert_send::<SpeechRecognizer>();
+    assert_sync::<SpeechRecognizer>();
+    assert_send::<DialogServiceConnector>();
+    assert_send::<PullAudioInputStream>();
+    assert_send::<PushAudioOutputStream>();
+}
@@ -0,0 +1,91 @@
+use cognitive_services_speech_sdk_rs::audio::vad::{VadConfig, VadEvent, VadGate};
+use std::time::Duration;
+
+const SAMPLES_PER_SECOND: u32 = 16000;
+
+/// Low level noise followed by 300 Hz tone for each (silence, speech) duration in seconds.
+fn audio(segments: &[(f32, f32)]) -> Vec<u8> {
+    let mut seed = 1u32;
+    let mut samples = vec![];
+    for (silence, speech) in segments {
+        for _ in 0..(silence * SAMPLES_PER_SECOND as f32) as usize {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            samples.push(((seed >> 16) % 61) as i16 - 30);
+        }
+        for i in 0..(speech * SAMPLES_PER_SECOND as f32) as usize {
+            let t = i as f32 / SAMPLES_PER_SECOND as f32;
+            samples.push((8000.0 * (2.0 * std::f32::consts::PI * 300.0 * t).sin()) as i16);
+        }
+    }
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+fn run(gate: &mut VadGate, audio: &[u8]) -> (Vec<u8>, Vec<VadEvent>) {
+    let mut written = vec![];
+    let mut events = vec![];
+    // odd chunk size, frames are split between chunks
+    for chunk in audio.chunks(1001) {
+        let output = gate.process(chunk);
+        written.extend(output.audio);
+        events.extend(output.events);
+    }
+    (written, events)
+}
+
+#[test]
+fn vad_gate_test() {
+    let audio = audio(&[(1.0, 1.0), (2.0, 1.0), (1.0, 0.0)]);
+
+    let mut gate = VadGate::new(SAMPLES_PER_SECOND, VadConfig::default()).unwrap();
+    let (written, events) = run(&mut gate, &audio);
+    assert_eq!(written, audio);
+    assert_eq!(
+        events,
+        vec![
+            VadEvent::SpeechStart { offset: 10_000_000 },
+            VadEvent::SpeechEnd { offset: 20_000_000 },
+            VadEvent::SpeechStart { offset: 40_000_000 },
+            VadEvent::SpeechEnd { offset: 50_000_000 },
+        ]
+    );
+    assert!(!gate.is_speech());
+}
+
+#[test]
+fn vad_gate_long_speech_test() {
+    // steady speech is not adopted as noise floor
+    let audio = audio(&[(1.0, 10.0), (1.0, 0.0)]);
+    let mut gate = VadGate::new(SAMPLES_PER_SECOND, VadConfig::default()).unwrap();
+    let (_, events) = run(&mut gate, &audio);
+    assert_eq!(
+        events,
+        vec![
+            VadEvent::SpeechStart { offset: 10_000_000 },
+            VadEvent::SpeechEnd {
+                offset: 110_000_000
+            },
+        ]
+    );
+}
+
+#[test]
+fn vad_gate_suspend_test() {
+    let audio = audio(&[(1.0, 1.0), (2.0, 1.0), (1.0, 0.0)]);
+    let config = VadConfig {
+        suspend_after: Some(Duration::from_millis(500)),
+        ..VadConfig::default()
+    };
+    let mut gate = VadGate::new(SAMPLES_PER_SECOND, config).unwrap();
+    let (written, events) = run(&mut gate, &audio);
+
+    // events are reported in producer timeline regardless of suspension
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0], VadEvent::SpeechStart { offset: 10_000_000 });
+    assert_eq!(events[2], VadEvent::SpeechStart { offset: 40_000_000 });
+    assert!(written.len() < audio.len() * 3 / 4);
+    assert!(gate.is_suspended());
+
+    // 0.5 s written before suspension, resumed with 0.36 s of leading audio starting at 0.7 s
+    assert_eq!(gate.source_offset(4_000_000), 4_000_000);
+    assert_eq!(gate.source_offset(6_000_000), 8_000_000);
+}
@@ -0,0 +1,148 @@
+use cognitive_services_speech_sdk_rs::common::{Gender, SynthesisVoiceType};
+use cognitive_services_speech_sdk_rs::speech::{VoiceCatalog, VoiceDescriptor, VoiceQuery};
+
+fn voice(
+    short_name: &str,
+    gender: Gender,
+    voice_type: SynthesisVoiceType,
+    styles: &[&str],
+) -> VoiceDescriptor {
+    let locale = short_name.rsplit_once('-').unwrap().0.to_owned();
+    VoiceDescriptor {
+        name: format!("Microsoft Server Speech Text to Speech Voice ({locale}, {short_name})"),
+        short_name: short_name.to_owned(),
+        local_name: short_name.to_owned(),
+        locale,
+        secondary_locales: vec![],
+        voice_type,
+        gender,
+        style_list: styles.iter().map(|s| s.to_string()).collect(),
+        role_play_list: vec![],
+        sample_rate_hertz: Some(24000),
+        words_per_minute: None,
+        voice_path: String::new(),
+    }
+}
+
+fn catalog() -> VoiceCatalog {
+    let mut multilingual = voice(
+        "de-DE-SeraphinaMultilingualNeural",
+        Gender::Female,
+        SynthesisVoiceType::OnlineNeural,
+        &[],
+    );
+    multilingual.secondary_locales = vec!["en-AU".to_owned()];
+    VoiceCatalog::new(
+        "",
+        vec![
+            voice(
+                "en-GB-HazelRUS",
+                Gender::Female,
+                SynthesisVoiceType::OnlineStandard,
+                &[],
+            ),
+            voice(
+                "en-US-GuyNeural",
+                Gender::Male,
+                SynthesisVoiceType::OnlineNeural,
+                &["newscast"],
+            ),
+            voice(
+                "en-US-JennyNeural",
+                Gender::Female,
+                SynthesisVoiceType::OnlineNeural,
+                &["cheerful", "customerservice"],
+            ),
+            voice(
+                "en-GB-SoniaNeural",
+                Gender::Female,
+                SynthesisVoiceType::OnlineNeural,
+                &["cheerful", "sad"],
+            ),
+            multilingual,
+        ],
+    )
+}
+
+#[test]
+fn voice_catalog_query_test() {
+    let catalog = catalog();
+    let names = |query: VoiceQuery| -> Vec<String> {
+        catalog
+            .query(&query)
+            .iter()
+            .map(|v| v.short_name.clone())
+            .collect()
+    };
+
+    assert_eq!(names(VoiceQuery::new()).len(), 5);
+    assert_eq!(
+        names(VoiceQuery::new().locale_prefix("en_gb")),
+        vec!["en-GB-HazelRUS", "en-GB-SoniaNeural"]
+    );
+    assert_eq!(
+        names(
+            VoiceQuery::new()
+                .locale_prefix("en")
+                .gender(Gender::Female)
+                .style("Cheerful")
+        ),
+        vec!["en-US-JennyNeural", "en-GB-SoniaNeural"]
+    );
+    assert_eq!(
+        names(VoiceQuery::new().neural(false)),
+        vec!["en-GB-HazelRUS"]
+    );
+    assert!(names(VoiceQuery::new().locale_prefix("e")).is_empty());
+    assert!(names(VoiceQuery::new().offline(true)).is_empty());
+    assert_eq!(
+        catalog.get("EN-US-GUYNEURAL").map(|v| v.gender),
+        Some(Gender::Male)
+    );
+    assert_eq!(catalog.locales(), vec!["de-DE", "en-GB", "en-US"]);
+}
+
+#[test]
+fn voice_catalog_best_voice_test() {
+    let catalog = catalog();
+    let best = |locale: &str, style: Option<&str>| {
+        catalog
+            .best_voice(locale, style)
+            .map(|v| v.short_name.as_str())
+    };
+
+    // neural voice preferred over standard one
+    assert_eq!(best("en-GB", None), Some("en-GB-SoniaNeural"));
+    // exact style match within the locale
+    assert_eq!(best("en-US", Some("cheerful")), Some("en-US-JennyNeural"));
+    assert_eq!(
+        best("en-us", Some("customer-service")),
+        Some("en-US-JennyNeural")
+    );
+    // partial style match
+    assert_eq!(best("en-GB", Some("che")), Some("en-GB-SoniaNeural"));
+    // secondary locale wins over same language
+    assert_eq!(
+        best("en-AU", None),
+        Some("de-DE-SeraphinaMultilingualNeural")
+    );
+    // same language fallback
+    assert_eq!(
+        best("de-AT", None),
+        Some("de-DE-SeraphinaMultilingualNeural")
+    );
+    assert_eq!(best("fr-FR", Some("cheerful")), None);
+}
+
+#[test]
+fn voice_catalog_cache_test() {
+    let catalog = catalog();
+    let path = std::env::temp_dir().join(format!("voice_catalog_{}.json", std::process::id()));
+    catalog.save(&path).unwrap();
+    let loaded = VoiceCatalog::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.fetched_at, catalog.fetched_at);
+    assert_eq!(loaded.voices, catalog.voices);
+    assert!(VoiceCatalog::load(&path).is_err());
+}